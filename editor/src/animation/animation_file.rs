@@ -0,0 +1,113 @@
+//! Animation file is a standalone asset that contains a single animation, so the animation could
+//! be reused in other scenes. Track targets are scene-specific handles, so they are stored as names
+//! of the target nodes and rebound by name on load.
+
+use fyrox::{
+    animation::Animation,
+    core::{
+        futures::executor::block_on,
+        pool::Handle,
+        visitor::{Visit, VisitError, VisitResult, Visitor},
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::path::Path;
+
+#[derive(Default, Visit)]
+struct AnimationFile {
+    animation: Animation,
+    /// Names of target nodes of the tracks in the order of the tracks. Empty name means that the
+    /// track was not bound to any node.
+    target_names: Vec<String>,
+    #[visit(optional)]
+    root_motion_node_name: String,
+}
+
+fn node_name(graph: &Graph, handle: Handle<Node>) -> String {
+    graph
+        .try_get(handle)
+        .map(|n| n.name_owned())
+        .unwrap_or_default()
+}
+
+fn find_node(graph: &Graph, root: Handle<Node>, name: &str) -> Handle<Node> {
+    if name.is_empty() {
+        return Handle::NONE;
+    }
+    graph
+        .find_by_name(root, name)
+        .map(|(handle, _)| handle)
+        .unwrap_or_default()
+}
+
+/// Writes a copy of the animation to the given path, the animation itself is not changed.
+pub fn save_animation(path: &Path, animation: &Animation, graph: &Graph) -> VisitResult {
+    let mut animation = animation.clone();
+
+    let target_names = animation
+        .tracks_mut()
+        .iter_mut()
+        .map(|track| {
+            let name = node_name(graph, track.target());
+            track.set_target(Handle::NONE);
+            name
+        })
+        .collect();
+
+    let root_motion_node_name = match animation.root_motion_settings_mut() {
+        Some(settings) => {
+            let name = node_name(graph, settings.node);
+            settings.node = Handle::NONE;
+            name
+        }
+        None => Default::default(),
+    };
+
+    animation.rewind();
+
+    let mut file = AnimationFile {
+        animation,
+        target_names,
+        root_motion_node_name,
+    };
+
+    let mut visitor = Visitor::new();
+    file.visit("AnimationFile", &mut visitor)?;
+    visitor.save_binary(path)
+}
+
+/// Loads an animation from the given path and binds its tracks to the nodes with matching names
+/// in the hierarchy of the root. Returns the animation and names of the nodes that were not found,
+/// tracks of such nodes are left unbound.
+pub fn load_animation(
+    path: &Path,
+    graph: &Graph,
+    root: Handle<Node>,
+) -> Result<(Animation, Vec<String>), VisitError> {
+    let mut visitor = block_on(Visitor::load_binary(path))?;
+    let mut file = AnimationFile::default();
+    file.visit("AnimationFile", &mut visitor)?;
+
+    let mut unmatched = Vec::new();
+    let mut bind = |name: &str| {
+        let handle = find_node(graph, root, name);
+        if handle.is_none() && !name.is_empty() && !unmatched.iter().any(|n| n == name) {
+            unmatched.push(name.to_owned());
+        }
+        handle
+    };
+
+    let mut animation = file.animation;
+    for (track, name) in animation
+        .tracks_mut()
+        .iter_mut()
+        .zip(file.target_names.iter())
+    {
+        track.set_target(bind(name));
+    }
+    if let Some(settings) = animation.root_motion_settings_mut() {
+        settings.node = bind(&file.root_motion_node_name);
+    }
+
+    Ok((animation, unmatched))
+}
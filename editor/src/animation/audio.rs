@@ -0,0 +1,140 @@
+//! Audio reference is a sound clip attached to an animation, its waveform is shown on the ruler so
+//! keys could be placed in sync with the sound (for example for lip-sync or music-driven
+//! animation). The clip could also be played in sync with preview. This is an editor-only state,
+//! it is not saved.
+
+use crate::animation::ruler::WaveformView;
+use fyrox::{
+    core::{futures::executor::block_on, io::FileLoadError, pool::Handle},
+    scene::{
+        base::BaseBuilder,
+        graph::Graph,
+        node::Node,
+        sound::{DataSource, Sound, SoundBuffer, SoundBufferResource, SoundBuilder, Status},
+    },
+};
+use std::{
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Amount of waveform slices per second of a clip.
+const PEAKS_PER_SECOND: f32 = 100.0;
+/// Max difference (in seconds) between the time of the animation and the playback time of the
+/// clip, the clip is moved to the time of the animation if the difference is larger.
+const MAX_PLAYBACK_DRIFT: f32 = 0.05;
+
+#[derive(Debug)]
+pub enum AudioReferenceError {
+    /// The file could not be read.
+    Io(FileLoadError),
+    /// The file could not be decoded, only WAV and OGG/Vorbis files are supported.
+    UnsupportedFormat,
+    /// The file has no samples.
+    Empty,
+}
+
+impl Display for AudioReferenceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AudioReferenceError::Io(FileLoadError::Io(err)) => {
+                write!(f, "unable to read the file: {}", err)
+            }
+            AudioReferenceError::Io(FileLoadError::Custom(reason)) => {
+                write!(f, "unable to read the file: {}", reason)
+            }
+            AudioReferenceError::UnsupportedFormat => {
+                write!(
+                    f,
+                    "unsupported file format, only WAV and OGG/Vorbis are supported"
+                )
+            }
+            AudioReferenceError::Empty => write!(f, "the file has no samples"),
+        }
+    }
+}
+
+pub struct AudioReference {
+    pub path: PathBuf,
+    pub waveform: WaveformView,
+    buffer: SoundBufferResource,
+}
+
+/// Splits interleaved samples into equal slices and returns peak amplitude of each slice.
+fn make_waveform(samples: &[f32], channel_count: usize, sample_rate: usize) -> WaveformView {
+    let frame_size = channel_count.max(1);
+    let frame_count = samples.len() / frame_size;
+    if frame_count == 0 || sample_rate == 0 {
+        return Default::default();
+    }
+
+    let duration = frame_count as f32 / sample_rate as f32;
+    let frames_per_peak = ((sample_rate as f32 / PEAKS_PER_SECOND) as usize).max(1);
+    let peaks = samples
+        .chunks(frames_per_peak * frame_size)
+        .map(|slice| slice.iter().fold(0.0f32, |peak, s| peak.max(s.abs())))
+        .collect();
+
+    WaveformView { duration, peaks }
+}
+
+impl AudioReference {
+    /// Decodes the whole clip. The clip is decoded once, so its waveform and playback do not
+    /// depend on import options of the file.
+    pub fn load(path: &Path) -> Result<Self, AudioReferenceError> {
+        let source = block_on(DataSource::from_file(path)).map_err(AudioReferenceError::Io)?;
+        let buffer =
+            SoundBuffer::raw_generic(source).map_err(|_| AudioReferenceError::UnsupportedFormat)?;
+
+        let waveform = make_waveform(
+            buffer.samples(),
+            buffer.channel_count(),
+            buffer.sample_rate(),
+        );
+        if waveform.peaks.is_empty() {
+            return Err(AudioReferenceError::Empty);
+        }
+
+        Ok(Self {
+            path: path.to_owned(),
+            waveform,
+            buffer: SoundBufferResource::new_ok(buffer),
+        })
+    }
+
+    /// Creates a paused, non-spatial sound that plays the clip. It should be linked to editor
+    /// objects, so it is never saved with the scene.
+    pub fn create_player(&self, graph: &mut Graph, parent: Handle<Node>) -> Handle<Node> {
+        let player = SoundBuilder::new(BaseBuilder::new().with_name("AudioReference"))
+            .with_buffer(Some(self.buffer.clone()))
+            .with_spatial_blend_factor(0.0)
+            .with_status(Status::Paused)
+            .build(graph);
+        graph.link_nodes(player, parent);
+        player
+    }
+}
+
+/// Plays the clip while the animation is playing and keeps playback time of the clip close to
+/// the time of the animation, so scrubbing and looping move the clip too. The clip is silent
+/// after its end.
+pub fn sync_player(graph: &mut Graph, player: Handle<Node>, time: f32, is_playing: bool) {
+    if let Some(sound) = graph.try_get_mut_of_type::<Sound>(player) {
+        let duration = sound
+            .buffer()
+            .map(|b| b.data_ref().duration().as_secs_f32())
+            .unwrap_or_default();
+
+        if is_playing && time >= 0.0 && time < duration {
+            if (sound.playback_time().as_secs_f32() - time).abs() > MAX_PLAYBACK_DRIFT {
+                sound.set_playback_time(Duration::from_secs_f32(time));
+            }
+            if sound.status() != Status::Playing {
+                sound.play();
+            }
+        } else if sound.status() == Status::Playing {
+            sound.pause();
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{
+    animation::command::fetch_animation_player,
+    command::Command,
+    define_universal_commands,
+    scene::commands::{SceneCommand, SceneContext},
+};
+use fyrox::{
+    animation::Animation,
+    core::{pool::Handle, reflect::prelude::*, uuid::Uuid},
+    scene::node::Node,
+};
+
+define_universal_commands!(
+    make_animation_marker_property_command,
+    Command,
+    SceneCommand,
+    SceneContext,
+    Uuid,
+    ctx,
+    handle,
+    self,
+    {
+        fetch_animation_player(self.node_handle, ctx).animations_mut()[self.animation_handle]
+            .markers_mut()
+            .iter_mut()
+            .find(|m| m.id == self.handle)
+            .unwrap()
+    },
+    node_handle: Handle<Node>,
+    animation_handle: Handle<Animation>
+);
@@ -1,23 +1,31 @@
 use crate::{
-    animation::selection::AnimationSelection,
+    animation::{curve_preset::preset_value, selection::AnimationSelection},
     command::Command,
     scene::{commands::SceneContext, Selection},
+    settings::animation::CurvePreset,
 };
 use fyrox::{
-    animation::{track::Track, Animation, AnimationSignal, RootMotionSettings},
+    animation::{
+        track::Track, value::ValueBinding, Animation, AnimationMarker, AnimationSignal,
+        RootMotionSettings,
+    },
     core::{
-        curve::Curve,
+        algebra::{Matrix4, Point3, Vector2, Vector3},
+        curve::{Curve, CurveKey, CurveKeyKind, CurveKeyTag},
         log::Log,
         pool::{Handle, Ticket},
         uuid::Uuid,
     },
-    scene::{animation::AnimationPlayer, node::Node},
+    fxhash::FxHashSet,
+    scene::{animation::AnimationPlayer, graph::Graph, node::Node},
 };
 use std::{
+    cmp::Ordering,
     fmt::Debug,
     ops::{IndexMut, Range},
 };
 
+pub mod marker;
 pub mod signal;
 
 fn fetch_animation_player<'a>(
@@ -100,6 +108,84 @@ impl Command for RemoveTrackCommand {
     }
 }
 
+/// Inserts a copy of a track right after the track. The copy is bound to the same target and
+/// property, but it has its own ids (of the track, its curves and keys), so it does not alias
+/// the original in selection.
+#[derive(Debug)]
+pub struct DuplicateTrackCommand {
+    animation_player: Handle<Node>,
+    animation: Handle<Animation>,
+    index: usize,
+    duplicate_id: Uuid,
+    track: Option<Track>,
+}
+
+/// Creates a copy of the track with new ids of the track, its curves and their keys.
+pub fn copy_track(source: &Track) -> Track {
+    let mut container = source.data_container().clone();
+    for curve in container.curves_mut() {
+        let mut copy = Curve::from(
+            curve
+                .keys()
+                .iter()
+                .map(|k| {
+                    let mut key = CurveKey::new(k.location(), k.value, k.kind.clone());
+                    key.tag = k.tag;
+                    key
+                })
+                .collect::<Vec<_>>(),
+        );
+        copy.set_name(curve.name());
+        *curve = copy;
+    }
+
+    let mut track = Track::new(container, source.binding().clone()).with_target(source.target());
+    track.set_enabled(source.is_enabled());
+    track
+}
+
+impl DuplicateTrackCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        index: usize,
+        source: &Track,
+    ) -> Self {
+        let track = copy_track(source);
+
+        Self {
+            animation_player,
+            animation,
+            index: index + 1,
+            duplicate_id: track.id(),
+            track: Some(track),
+        }
+    }
+
+    /// Returns id of the new track.
+    pub fn duplicate_id(&self) -> Uuid {
+        self.duplicate_id
+    }
+}
+
+impl Command for DuplicateTrackCommand {
+    fn name(&mut self, _: &SceneContext) -> String {
+        "Duplicate Track".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        fetch_animation_player(self.animation_player, context).animations_mut()[self.animation]
+            .insert_track(self.index, self.track.take().unwrap());
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.track = Some(
+            fetch_animation_player(self.animation_player, context).animations_mut()[self.animation]
+                .remove_track(self.index),
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct ReplaceTrackCurveCommand {
     pub animation_player: Handle<Node>,
@@ -127,7 +213,7 @@ impl ReplaceTrackCurveCommand {
 
 impl Command for ReplaceTrackCurveCommand {
     fn name(&mut self, _context: &SceneContext) -> String {
-        "Replace Track Curve".to_string()
+        "Edit Curve".to_string()
     }
 
     fn execute(&mut self, context: &mut SceneContext) {
@@ -260,6 +346,48 @@ impl Command for AddAnimationCommand {
     }
 }
 
+/// Adds a deep copy of an animation (with every track and curve) to the same animation player.
+/// Tracks of the copy are bound to the same nodes as the tracks of the original. The copy is
+/// selected on execution, the same way as with [`AddAnimationCommand`].
+#[derive(Debug)]
+pub struct DuplicateAnimationCommand {
+    add: AddAnimationCommand,
+}
+
+impl DuplicateAnimationCommand {
+    pub fn new(animation_player: Handle<Node>, source: &Animation) -> Self {
+        let mut animation = source.clone();
+        animation.set_name(format!("Copy of {}", source.name()));
+        // Editor state (selection, track switches) is keyed by ids, so the copy must not share
+        // them with the source.
+        for track in animation.tracks_mut() {
+            *track = copy_track(track);
+        }
+
+        Self {
+            add: AddAnimationCommand::new(animation_player, animation),
+        }
+    }
+}
+
+impl Command for DuplicateAnimationCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Duplicate Animation".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.add.execute(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.add.revert(context);
+    }
+
+    fn finalize(&mut self, context: &mut SceneContext) {
+        self.add.finalize(context);
+    }
+}
+
 #[derive(Debug)]
 pub enum RemoveAnimationCommand {
     Unknown,
@@ -382,6 +510,19 @@ impl Command for ReplaceAnimationCommand {
     }
 }
 
+/// Makes a human-readable name from a type name of a command, for example `SetAnimationSpeedCommand`
+/// becomes `Set Animation Speed`.
+pub fn readable_command_name(type_name: &str) -> String {
+    let mut name = String::new();
+    for (i, c) in type_name.trim_end_matches("Command").chars().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            name.push(' ');
+        }
+        name.push(c);
+    }
+    name
+}
+
 #[macro_export]
 macro_rules! define_animation_swap_command {
     ($name:ident<$value_type:ty>($self:ident, $context:ident) $swap:block) => {
@@ -400,7 +541,7 @@ macro_rules! define_animation_swap_command {
 
         impl Command for $name {
             fn name(&mut self, _context: &SceneContext) -> String {
-                stringify!($name).to_string()
+                $crate::animation::command::readable_command_name(stringify!($name))
             }
 
             fn execute(&mut self, context: &mut SceneContext) {
@@ -535,7 +676,7 @@ pub struct RemoveAnimationSignal {
 
 impl Command for RemoveAnimationSignal {
     fn name(&mut self, _context: &SceneContext) -> String {
-        "Remove Animation".to_string()
+        "Remove Animation Signal".to_string()
     }
 
     fn execute(&mut self, context: &mut SceneContext) {
@@ -551,6 +692,91 @@ impl Command for RemoveAnimationSignal {
     }
 }
 
+#[derive(Debug)]
+pub struct AddAnimationMarker {
+    pub animation_player_handle: Handle<Node>,
+    pub animation_handle: Handle<Animation>,
+    pub marker: Option<AnimationMarker>,
+}
+
+impl Command for AddAnimationMarker {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Add Animation Marker".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        fetch_animation(self.animation_player_handle, self.animation_handle, context)
+            .add_marker(self.marker.take().unwrap());
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.marker = fetch_animation(self.animation_player_handle, self.animation_handle, context)
+            .pop_marker();
+    }
+}
+
+#[derive(Debug)]
+pub struct MoveAnimationMarker {
+    pub animation_player_handle: Handle<Node>,
+    pub animation_handle: Handle<Animation>,
+    pub marker: Uuid,
+    pub time: f32,
+}
+
+impl MoveAnimationMarker {
+    fn swap(&mut self, context: &mut SceneContext) {
+        std::mem::swap(
+            &mut fetch_animation(self.animation_player_handle, self.animation_handle, context)
+                .markers_mut()
+                .iter_mut()
+                .find(|m| m.id == self.marker)
+                .unwrap()
+                .time,
+            &mut self.time,
+        );
+    }
+}
+
+impl Command for MoveAnimationMarker {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Move Animation Marker".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoveAnimationMarker {
+    pub animation_player_handle: Handle<Node>,
+    pub animation_handle: Handle<Animation>,
+    pub marker_index: usize,
+    pub marker: Option<AnimationMarker>,
+}
+
+impl Command for RemoveAnimationMarker {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Remove Animation Marker".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let animation =
+            fetch_animation(self.animation_player_handle, self.animation_handle, context);
+        self.marker = Some(animation.remove_marker(self.marker_index));
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let animation =
+            fetch_animation(self.animation_player_handle, self.animation_handle, context);
+        animation.insert_marker(self.marker_index, self.marker.take().unwrap());
+    }
+}
+
 #[derive(Debug)]
 pub struct SetTrackEnabledCommand {
     pub animation_player_handle: Handle<Node>,
@@ -587,15 +813,18 @@ impl Command for SetTrackEnabledCommand {
     }
 }
 
+/// Binds a track to a new target node and property. The old binding is stored in the command
+/// on execution, so it could be restored on revert.
 #[derive(Debug)]
-pub struct SetTrackTargetCommand {
+pub struct RetargetTrackCommand {
     pub animation_player_handle: Handle<Node>,
     pub animation_handle: Handle<Animation>,
     pub track: Uuid,
     pub target: Handle<Node>,
+    pub binding: ValueBinding,
 }
 
-impl SetTrackTargetCommand {
+impl RetargetTrackCommand {
     fn swap(&mut self, context: &mut SceneContext) {
         let track = fetch_animation(self.animation_player_handle, self.animation_handle, context)
             .tracks_mut()
@@ -603,15 +832,18 @@ impl SetTrackTargetCommand {
             .find(|t| t.id() == self.track)
             .unwrap();
 
-        let old = track.target();
+        let old_target = track.target();
         track.set_target(self.target);
-        self.target = old;
+        self.target = old_target;
+
+        let old_binding = track.binding().clone();
+        track.set_binding(std::mem::replace(&mut self.binding, old_binding));
     }
 }
 
-impl Command for SetTrackTargetCommand {
+impl Command for RetargetTrackCommand {
     fn name(&mut self, _context: &SceneContext) -> String {
-        "Set Track Target".to_string()
+        "Retarget Track".to_string()
     }
 
     fn execute(&mut self, context: &mut SceneContext) {
@@ -622,3 +854,2307 @@ impl Command for SetTrackTargetCommand {
         self.swap(context)
     }
 }
+
+/// Creates a curve with the given keys that keeps id and name of the curve, so it could replace
+/// the curve in its track.
+pub fn rebuild_curve(curve: &Curve, keys: Vec<CurveKey>) -> Curve {
+    let mut new_curve = Curve::from(keys);
+    new_curve.set_id(curve.id());
+    new_curve.set_name(curve.name());
+    new_curve
+}
+
+fn fetch_curve<'a>(
+    animation_player: Handle<Node>,
+    animation: Handle<Animation>,
+    curve: Uuid,
+    ctx: &'a mut SceneContext,
+) -> Option<&'a mut Curve> {
+    fetch_animation(animation_player, animation, ctx)
+        .tracks_mut()
+        .iter_mut()
+        .flat_map(|t| t.data_container_mut().curves_mut().iter_mut())
+        .find(|c| c.id() == curve)
+}
+
+#[derive(Debug)]
+pub struct InsertKeyframeCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub curve: Uuid,
+    pub time: f32,
+    pub value: f32,
+    /// Tangents of a cubic key are calculated from the neighbour keys.
+    pub kind: CurveKeyKind,
+    key: Uuid,
+    inserted: bool,
+}
+
+impl InsertKeyframeCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        curve: Uuid,
+        time: f32,
+        value: f32,
+        kind: CurveKeyKind,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            curve,
+            time,
+            value,
+            kind,
+            key: Uuid::new_v4(),
+            inserted: false,
+        }
+    }
+}
+
+/// Returns the slope between the keys around the given time, or zero if there is no key on
+/// either side.
+fn neighbour_slope(curve: &Curve, time: f32) -> f32 {
+    let prev = curve.keys().iter().rev().find(|k| k.location() < time);
+    let next = curve.keys().iter().find(|k| k.location() > time);
+    match (prev, next) {
+        (Some(prev), Some(next)) => (next.value - prev.value) / (next.location() - prev.location()),
+        _ => 0.0,
+    }
+}
+
+impl Command for InsertKeyframeCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Insert Keyframe".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.inserted = false;
+
+        if let Some(curve) = fetch_curve(self.animation_player, self.animation, self.curve, context)
+        {
+            // Do nothing if there's a key at the exact same time already.
+            if curve.keys().iter().all(|k| k.location() != self.time) {
+                let kind = match self.kind {
+                    CurveKeyKind::Cubic { .. } => {
+                        let tangent = neighbour_slope(curve, self.time);
+                        CurveKeyKind::Cubic {
+                            left_tangent: tangent,
+                            right_tangent: tangent,
+                        }
+                    }
+                    ref kind => kind.clone(),
+                };
+                let mut key = CurveKey::new(self.time, self.value, kind);
+                key.id = self.key;
+                curve.add_key(key);
+                self.inserted = true;
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        if self.inserted {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, self.curve, context)
+            {
+                if let Some(index) = curve.keys().iter().position(|k| k.id == self.key) {
+                    curve.remove_key(index);
+                }
+            }
+            self.inserted = false;
+        }
+    }
+}
+
+/// Keys values of many curves at the same time. Curves that have a key at the time already get the
+/// value of the key replaced, the rest of the curves get a new key.
+#[derive(Debug)]
+pub struct InsertKeyframesCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    /// Pairs of (curve id, value).
+    pub values: Vec<(Uuid, f32)>,
+    pub time: f32,
+    pub kind: CurveKeyKind,
+    original: Vec<Curve>,
+}
+
+impl InsertKeyframesCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        values: Vec<(Uuid, f32)>,
+        time: f32,
+        kind: CurveKeyKind,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            values,
+            time,
+            kind,
+            original: Default::default(),
+        }
+    }
+}
+
+impl Command for InsertKeyframesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Insert Keyframes".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        for (curve_id, value) in self.values.iter() {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, *curve_id, context)
+            {
+                self.original.push(curve.clone());
+
+                if let Some(existing) = curve.keys().iter().position(|k| k.location() == self.time)
+                {
+                    let mut keys = curve.keys().to_vec();
+                    keys[existing].value = *value;
+                    *curve = rebuild_curve(curve, keys);
+                } else {
+                    let kind = match self.kind {
+                        CurveKeyKind::Cubic { .. } => {
+                            let tangent = neighbour_slope(curve, self.time);
+                            CurveKeyKind::Cubic {
+                                left_tangent: tangent,
+                                right_tangent: tangent,
+                            }
+                        }
+                        ref kind => kind.clone(),
+                    };
+                    curve.add_key(CurveKey::new(self.time, *value, kind));
+                }
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        restore_curves(
+            self.animation_player,
+            self.animation,
+            std::mem::take(&mut self.original),
+            context,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoveKeyframesCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    /// Pairs of (curve id, key id).
+    pub keys: Vec<(Uuid, Uuid)>,
+    removed: Vec<(Uuid, CurveKey)>,
+}
+
+impl RemoveKeyframesCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        keys: Vec<(Uuid, Uuid)>,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            keys,
+            removed: Default::default(),
+        }
+    }
+}
+
+impl Command for RemoveKeyframesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Remove Keyframes".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        for &(curve_id, key_id) in self.keys.iter() {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, curve_id, context)
+            {
+                if let Some(index) = curve.keys().iter().position(|k| k.id == key_id) {
+                    if let Some(key) = curve.remove_key(index) {
+                        self.removed.push((curve_id, key));
+                    }
+                }
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        for (curve_id, key) in self.removed.drain(..) {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, curve_id, context)
+            {
+                curve.add_key(key);
+            }
+        }
+    }
+}
+
+/// Removes every key of a set of curves within a time range (including its ends). When the shape
+/// is preserved, keys are added at the ends of the range if the curve has keys on both sides of
+/// it, so the curve keeps its values and slopes at the boundaries.
+#[derive(Debug)]
+pub struct DeleteKeyframesInRangeCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub curves: Vec<Uuid>,
+    pub range: Range<f32>,
+    pub preserve_shape: bool,
+    original: Vec<Curve>,
+}
+
+impl DeleteKeyframesInRangeCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        curves: Vec<Uuid>,
+        range: Range<f32>,
+        preserve_shape: bool,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            curves,
+            range,
+            preserve_shape,
+            original: Default::default(),
+        }
+    }
+}
+
+fn delete_keys_in_range(curve: &Curve, range: &Range<f32>, preserve_shape: bool) -> Curve {
+    let in_range = |k: &CurveKey| k.location() >= range.start && k.location() <= range.end;
+
+    let mut keys = curve
+        .keys()
+        .iter()
+        .filter(|k| !in_range(k))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let any_removed = keys.len() != curve.keys().len();
+    let has_keys_before = keys.iter().any(|k| k.location() < range.start);
+    let has_keys_after = keys.iter().any(|k| k.location() > range.end);
+    if preserve_shape && any_removed && has_keys_before && has_keys_after {
+        keys.push(boundary_key(curve, range.start));
+        // A zero-width range has a single boundary.
+        if range.end - range.start >= KEY_TIME_EPSILON {
+            keys.push(boundary_key(curve, range.end));
+        }
+    }
+
+    rebuild_curve(curve, keys)
+}
+
+/// Creates a key with the value of the curve at the given time. Interpolation of the key is taken
+/// from the span of the curve at that time, cubic keys get the slopes of the curve as tangents.
+fn boundary_key(curve: &Curve, time: f32) -> CurveKey {
+    // Interpolation of a span is defined by its left key.
+    let span_kind = curve
+        .keys()
+        .iter()
+        .rev()
+        .find(|k| k.location() <= time)
+        .map(|k| &k.kind);
+    let kind = match span_kind {
+        Some(CurveKeyKind::Constant) => CurveKeyKind::Constant,
+        Some(CurveKeyKind::Cubic { .. }) => {
+            const STEP: f32 = 1.0e-3;
+            let value = curve.value_at(time);
+            CurveKeyKind::Cubic {
+                left_tangent: (value - curve.value_at(time - STEP)) / STEP,
+                right_tangent: (curve.value_at(time + STEP) - value) / STEP,
+            }
+        }
+        _ => CurveKeyKind::Linear,
+    };
+    CurveKey::new(time, curve.value_at(time), kind)
+}
+
+impl Command for DeleteKeyframesInRangeCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Delete Keyframes In Range".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        for &curve_id in self.curves.iter() {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, curve_id, context)
+            {
+                let new_curve = delete_keys_in_range(curve, &self.range, self.preserve_shape);
+                self.original.push(std::mem::replace(curve, new_curve));
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        restore_curves(
+            self.animation_player,
+            self.animation,
+            std::mem::take(&mut self.original),
+            context,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct PasteKeyframesCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub curve: Uuid,
+    /// Keys to paste, their locations are absolute.
+    pub keys: Vec<CurveKey>,
+    // Keys that were replaced by the pasted ones, because they had the same location.
+    overwritten: Vec<CurveKey>,
+}
+
+impl PasteKeyframesCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        curve: Uuid,
+        keys: Vec<CurveKey>,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            curve,
+            keys,
+            overwritten: Default::default(),
+        }
+    }
+}
+
+/// Adds the keys to the curve and returns the keys of the curve that were replaced, because they
+/// had the same location as some of the pasted keys. Pasted keys are never replaced by each other.
+fn paste_keys(curve: &mut Curve, keys: &[CurveKey]) -> Vec<CurveKey> {
+    let mut overwritten = Vec::new();
+    for key in keys.iter() {
+        while let Some(index) = curve.keys().iter().position(|k| {
+            k.location() == key.location() && !keys.iter().any(|pasted| pasted.id == k.id)
+        }) {
+            if let Some(key) = curve.remove_key(index) {
+                overwritten.push(key);
+            }
+        }
+
+        curve.add_key(key.clone());
+    }
+    overwritten
+}
+
+/// Removes the pasted keys from the curve and brings back the keys they replaced.
+fn unpaste_keys(curve: &mut Curve, keys: &[CurveKey], overwritten: Vec<CurveKey>) {
+    for key in keys.iter() {
+        if let Some(index) = curve.keys().iter().position(|k| k.id == key.id) {
+            curve.remove_key(index);
+        }
+    }
+
+    for key in overwritten {
+        curve.add_key(key);
+    }
+}
+
+impl Command for PasteKeyframesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Paste Keyframes".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        if let Some(curve) = fetch_curve(self.animation_player, self.animation, self.curve, context)
+        {
+            self.overwritten = paste_keys(curve, &self.keys);
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        if let Some(curve) = fetch_curve(self.animation_player, self.animation, self.curve, context)
+        {
+            unpaste_keys(curve, &self.keys, std::mem::take(&mut self.overwritten));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SetAnimationLengthCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub length: f32,
+    old_time_slice: Range<f32>,
+    // Keys that were located after the new end of the animation, as pairs of (curve id, key).
+    truncated: Vec<(Uuid, CurveKey)>,
+}
+
+impl SetAnimationLengthCommand {
+    pub fn new(animation_player: Handle<Node>, animation: Handle<Animation>, length: f32) -> Self {
+        Self {
+            animation_player,
+            animation,
+            length,
+            old_time_slice: Default::default(),
+            truncated: Default::default(),
+        }
+    }
+}
+
+impl Command for SetAnimationLengthCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Animation Length".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let animation = fetch_animation(self.animation_player, self.animation, context);
+
+        self.old_time_slice = animation.time_slice();
+        let start = self.old_time_slice.start;
+        let end = start + self.length.max(0.0);
+        animation.set_time_slice(start..end);
+
+        for curve in animation
+            .tracks_mut()
+            .iter_mut()
+            .flat_map(|t| t.data_container_mut().curves_mut().iter_mut())
+        {
+            while let Some(index) = curve.keys().iter().position(|k| k.location() > end) {
+                if let Some(key) = curve.remove_key(index) {
+                    self.truncated.push((curve.id(), key));
+                }
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let animation = fetch_animation(self.animation_player, self.animation, context);
+
+        animation.set_time_slice(self.old_time_slice.clone());
+
+        for (curve_id, key) in self.truncated.drain(..) {
+            if let Some(curve) = animation
+                .tracks_mut()
+                .iter_mut()
+                .flat_map(|t| t.data_container_mut().curves_mut().iter_mut())
+                .find(|c| c.id() == curve_id)
+            {
+                curve.add_key(key);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BakeCurveCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub curve: Uuid,
+    /// Amount of samples per second.
+    pub fps: f32,
+    original: Option<Curve>,
+}
+
+impl BakeCurveCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        curve: Uuid,
+        fps: f32,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            curve,
+            fps,
+            original: None,
+        }
+    }
+}
+
+impl Command for BakeCurveCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Bake Curve".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let animation = fetch_animation(self.animation_player, self.animation, context);
+        let time_slice = animation.time_slice();
+
+        if let Some(curve) = animation
+            .tracks_mut()
+            .iter_mut()
+            .flat_map(|t| t.data_container_mut().curves_mut().iter_mut())
+            .find(|c| c.id() == self.curve)
+        {
+            // There's nothing to sample.
+            if curve.keys().is_empty() {
+                return;
+            }
+
+            let step = 1.0 / self.fps.max(1.0);
+            // Times are computed from the index of a sample, so rounding errors do not pile up.
+            // Last sample is always at the end of the animation, it must not be too close to the
+            // previous one.
+            let mut times = (0..)
+                .map(|i| time_slice.start + i as f32 * step)
+                .take_while(|time| *time < time_slice.end - 0.5 * step)
+                .collect::<Vec<_>>();
+            times.push(time_slice.end);
+
+            let baked = rebuild_curve(
+                curve,
+                times
+                    .into_iter()
+                    .map(|time| CurveKey::new(time, curve.value_at(time), CurveKeyKind::Linear))
+                    .collect::<Vec<_>>(),
+            );
+
+            self.original = Some(std::mem::replace(curve, baked));
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        if let Some(original) = self.original.take() {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, self.curve, context)
+            {
+                *curve = original;
+            }
+        }
+    }
+}
+
+/// Moves a track from one position to another in the list of tracks of an animation. Order of
+/// the tracks does not affect the result of the animation.
+#[derive(Debug)]
+pub struct ReorderTrackCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub from: usize,
+    pub to: usize,
+}
+
+fn move_track(tracks: &mut [Track], from: usize, to: usize) {
+    if from < to {
+        tracks[from..=to].rotate_left(1);
+    } else if from > to {
+        tracks[to..=from].rotate_right(1);
+    }
+}
+
+impl Command for ReorderTrackCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Reorder Track".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let tracks = fetch_animation(self.animation_player, self.animation, context).tracks_mut();
+        move_track(tracks, self.from, self.to);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let tracks = fetch_animation(self.animation_player, self.animation, context).tracks_mut();
+        move_track(tracks, self.to, self.from);
+    }
+}
+
+/// Reverses a curve in time within its own extent, so the last key becomes the first one.
+#[derive(Debug)]
+pub struct ReverseCurveCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub curve: Uuid,
+    original: Option<Curve>,
+}
+
+impl ReverseCurveCommand {
+    pub fn new(animation_player: Handle<Node>, animation: Handle<Animation>, curve: Uuid) -> Self {
+        Self {
+            animation_player,
+            animation,
+            curve,
+            original: None,
+        }
+    }
+}
+
+fn reverse_curve(curve: &Curve) -> Curve {
+    let keys = curve.keys();
+    let (start, end) = match (keys.first(), keys.last()) {
+        (Some(first), Some(last)) => (first.location(), last.location()),
+        _ => return curve.clone(),
+    };
+
+    rebuild_curve(
+        curve,
+        keys.iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let (left_tangent, right_tangent) = match key.kind {
+                    CurveKeyKind::Cubic {
+                        left_tangent,
+                        right_tangent,
+                    } => (left_tangent, right_tangent),
+                    _ => (0.0, 0.0),
+                };
+                // Interpolation of a span is defined by its left key, after reversal the span
+                // between previous and current keys starts at the current key. Tangents swap
+                // sides and change their sign, because time flows in the opposite direction.
+                let span_kind = if i > 0 { &keys[i - 1].kind } else { &key.kind };
+                let kind = match span_kind {
+                    CurveKeyKind::Constant => CurveKeyKind::Constant,
+                    CurveKeyKind::Linear => CurveKeyKind::Linear,
+                    CurveKeyKind::Cubic { .. } => CurveKeyKind::Cubic {
+                        left_tangent: -right_tangent,
+                        right_tangent: -left_tangent,
+                    },
+                };
+                let mut reversed_key = CurveKey::new(start + end - key.location(), key.value, kind);
+                reversed_key.id = key.id;
+                reversed_key.tag = key.tag;
+                reversed_key
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+impl Command for ReverseCurveCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Reverse Curve".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        if let Some(curve) = fetch_curve(self.animation_player, self.animation, self.curve, context)
+        {
+            let reversed = reverse_curve(curve);
+            self.original = Some(std::mem::replace(curve, reversed));
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        if let Some(original) = self.original.take() {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, self.curve, context)
+            {
+                *curve = original;
+            }
+        }
+    }
+}
+
+/// Remaps values of a curve, so its smallest key value becomes zero and the largest one becomes
+/// one. Tangents are slopes of the curve, so they're scaled by the same factor. A flat curve is
+/// left unchanged.
+#[derive(Debug)]
+pub struct NormalizeCurveCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub curve: Uuid,
+    original: Option<Curve>,
+}
+
+impl NormalizeCurveCommand {
+    pub fn new(animation_player: Handle<Node>, animation: Handle<Animation>, curve: Uuid) -> Self {
+        Self {
+            animation_player,
+            animation,
+            curve,
+            original: None,
+        }
+    }
+}
+
+fn normalize_curve(curve: &Curve) -> Curve {
+    let (min, max) = curve
+        .keys()
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(min, max), k| {
+            (min.min(k.value), max.max(k.value))
+        });
+    let range = max - min;
+    if range <= f32::EPSILON {
+        return curve.clone();
+    }
+
+    rebuild_curve(
+        curve,
+        curve
+            .keys()
+            .iter()
+            .map(|key| {
+                let mut normalized_key = key.clone();
+                normalized_key.value = (key.value - min) / range;
+                if let CurveKeyKind::Cubic {
+                    left_tangent,
+                    right_tangent,
+                } = &mut normalized_key.kind
+                {
+                    *left_tangent /= range;
+                    *right_tangent /= range;
+                }
+                normalized_key
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+impl Command for NormalizeCurveCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Normalize Curve".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        if let Some(curve) = fetch_curve(self.animation_player, self.animation, self.curve, context)
+        {
+            let normalized = normalize_curve(curve);
+            self.original = Some(std::mem::replace(curve, normalized));
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        if let Some(original) = self.original.take() {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, self.curve, context)
+            {
+                *curve = original;
+            }
+        }
+    }
+}
+
+/// Removes keys of a curve, which removal changes the shape of the curve by less than the
+/// tolerance. The first and the last keys are always kept.
+#[derive(Debug)]
+pub struct SimplifyCurveCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub curve: Uuid,
+    /// Maximum allowed difference between values of the original and simplified curves.
+    pub tolerance: f32,
+    original: Option<Curve>,
+}
+
+impl SimplifyCurveCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        curve: Uuid,
+        tolerance: f32,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            curve,
+            tolerance,
+            original: None,
+        }
+    }
+}
+
+/// Amount of samples between adjacent keys, that are used to measure deviation of a simplified
+/// curve. Curves are not linear between keys, so it is not enough to compare values at keys.
+const SIMPLIFY_SAMPLES_PER_SEGMENT: usize = 8;
+
+/// Simplifies a curve in Douglas-Peucker fashion: a span between two kept keys is replaced with
+/// just these keys, if the resulting segment deviates from the original curve by less than the
+/// tolerance. Otherwise the span is split at the key closest to the largest deviation and both
+/// halves are checked the same way.
+pub fn simplify_curve(curve: &Curve, tolerance: f32) -> Curve {
+    let keys = curve.keys();
+    if keys.len() <= 2 {
+        return curve.clone();
+    }
+
+    let mut keep = vec![false; keys.len()];
+    keep[0] = true;
+    keep[keys.len() - 1] = true;
+
+    let mut spans = vec![(0, keys.len() - 1)];
+    while let Some((first, last)) = spans.pop() {
+        if last - first < 2 {
+            continue;
+        }
+
+        let segment = Curve::from(vec![keys[first].clone(), keys[last].clone()]);
+
+        let mut max_deviation = 0.0;
+        let mut split = first + 1;
+        for k in first + 1..=last {
+            let begin = keys[k - 1].location();
+            let end = keys[k].location();
+            for i in 1..=SIMPLIFY_SAMPLES_PER_SEGMENT {
+                let time = begin + (end - begin) * i as f32 / SIMPLIFY_SAMPLES_PER_SEGMENT as f32;
+                let deviation = (curve.value_at(time) - segment.value_at(time)).abs();
+                if deviation > max_deviation {
+                    max_deviation = deviation;
+                    split = if k < last { k } else { k - 1 };
+                }
+            }
+        }
+
+        if max_deviation > tolerance {
+            keep[split] = true;
+            spans.push((first, split));
+            spans.push((split, last));
+        }
+    }
+
+    rebuild_curve(
+        curve,
+        keys.iter()
+            .zip(keep)
+            .filter_map(|(key, keep)| if keep { Some(key.clone()) } else { None })
+            .collect::<Vec<_>>(),
+    )
+}
+
+impl Command for SimplifyCurveCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Simplify Curve".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        if let Some(curve) = fetch_curve(self.animation_player, self.animation, self.curve, context)
+        {
+            let simplified = simplify_curve(curve, self.tolerance);
+            self.original = Some(std::mem::replace(curve, simplified));
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        if let Some(original) = self.original.take() {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, self.curve, context)
+            {
+                *curve = original;
+            }
+        }
+    }
+}
+
+/// Scales values of every key of a curve around a pivot value. Tangents are slopes of the curve,
+/// so they're scaled by the same factor.
+#[derive(Debug)]
+pub struct ScaleCurveValuesCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub curve: Uuid,
+    pub factor: f32,
+    pub pivot: f32,
+    original: Option<Curve>,
+}
+
+impl ScaleCurveValuesCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        curve: Uuid,
+        factor: f32,
+        pivot: f32,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            curve,
+            factor,
+            pivot,
+            original: None,
+        }
+    }
+}
+
+impl Command for ScaleCurveValuesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Scale Curve Values".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        if let Some(curve) = fetch_curve(self.animation_player, self.animation, self.curve, context)
+        {
+            let scaled = rebuild_curve(
+                curve,
+                curve
+                    .keys()
+                    .iter()
+                    .map(|key| {
+                        let kind = match key.kind {
+                            CurveKeyKind::Cubic {
+                                left_tangent,
+                                right_tangent,
+                            } => CurveKeyKind::Cubic {
+                                left_tangent: left_tangent * self.factor,
+                                right_tangent: right_tangent * self.factor,
+                            },
+                            ref kind => kind.clone(),
+                        };
+                        let mut scaled_key = CurveKey::new(
+                            key.location(),
+                            self.pivot + (key.value - self.pivot) * self.factor,
+                            kind,
+                        );
+                        scaled_key.id = key.id;
+                        scaled_key.tag = key.tag;
+                        scaled_key
+                    })
+                    .collect::<Vec<_>>(),
+            );
+
+            self.original = Some(std::mem::replace(curve, scaled));
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        if let Some(original) = self.original.take() {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, self.curve, context)
+            {
+                *curve = original;
+            }
+        }
+    }
+}
+
+/// Moves every key of a track by the same time offset. Keys are never moved before zero, the whole
+/// track stops at zero instead, so the spacing of its keys is preserved.
+#[derive(Debug)]
+pub struct OffsetTrackCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub track: Uuid,
+    pub offset: f32,
+    original: Option<Vec<Curve>>,
+}
+
+impl OffsetTrackCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        track: Uuid,
+        offset: f32,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            track,
+            offset,
+            original: None,
+        }
+    }
+}
+
+fn offset_curve(curve: &Curve, offset: f32) -> Curve {
+    rebuild_curve(
+        curve,
+        curve
+            .keys()
+            .iter()
+            .map(|k| {
+                let mut key = CurveKey::new(k.location() + offset, k.value, k.kind.clone());
+                key.id = k.id;
+                key.tag = k.tag;
+                key
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+impl Command for OffsetTrackCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Offset Track".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        if let Some(track) = fetch_animation(self.animation_player, self.animation, context)
+            .tracks_mut()
+            .iter_mut()
+            .find(|t| t.id() == self.track)
+        {
+            let curves = track.data_container_mut().curves_mut();
+
+            let first_key_location = curves
+                .iter()
+                .filter_map(|c| c.keys().first())
+                .map(|k| k.location())
+                .min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            let offset = match first_key_location {
+                Some(location) => self.offset.max(-location.max(0.0)),
+                None => return,
+            };
+
+            self.original = Some(curves.to_vec());
+            for curve in curves.iter_mut() {
+                *curve = offset_curve(curve, offset);
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        if let Some(original) = self.original.take() {
+            if let Some(track) = fetch_animation(self.animation_player, self.animation, context)
+                .tracks_mut()
+                .iter_mut()
+                .find(|t| t.id() == self.track)
+            {
+                track
+                    .data_container_mut()
+                    .curves_mut()
+                    .clone_from_slice(&original);
+            }
+        }
+    }
+}
+
+/// Makes tangents of a set of keyframes flat, so values of the curves stay constant around the
+/// keys.
+#[derive(Debug)]
+pub struct FlattenTangentsCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    /// Pairs of (curve id, key id).
+    pub keys: Vec<(Uuid, Uuid)>,
+    original: Vec<Curve>,
+}
+
+impl FlattenTangentsCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        keys: Vec<(Uuid, Uuid)>,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            keys,
+            original: Default::default(),
+        }
+    }
+}
+
+/// Sets tangents of the given keys of every affected curve, the tangent of a key is calculated
+/// from the curve by its index. Keys of any kind become cubic, otherwise tangents are ignored.
+/// Returns the original curves.
+fn set_key_tangents<F>(
+    animation_player: Handle<Node>,
+    animation: Handle<Animation>,
+    keys: &[(Uuid, Uuid)],
+    context: &mut SceneContext,
+    tangent: F,
+) -> Vec<Curve>
+where
+    F: Fn(&Curve, usize) -> f32,
+{
+    let curves = keys
+        .iter()
+        .map(|(curve, _)| *curve)
+        .collect::<FxHashSet<_>>();
+
+    let mut original = Vec::new();
+    for curve_id in curves {
+        if let Some(curve) = fetch_curve(animation_player, animation, curve_id, context) {
+            let new_curve = rebuild_curve(
+                curve,
+                curve
+                    .keys()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, k)| {
+                        let mut key = k.clone();
+                        if keys.contains(&(curve_id, k.id)) {
+                            let tangent = tangent(curve, i);
+                            key.kind = CurveKeyKind::Cubic {
+                                left_tangent: tangent,
+                                right_tangent: tangent,
+                            };
+                        }
+                        key
+                    })
+                    .collect::<Vec<_>>(),
+            );
+
+            original.push(std::mem::replace(curve, new_curve));
+        }
+    }
+    original
+}
+
+fn restore_curves(
+    animation_player: Handle<Node>,
+    animation: Handle<Animation>,
+    original: Vec<Curve>,
+    context: &mut SceneContext,
+) {
+    for original in original {
+        if let Some(curve) = fetch_curve(animation_player, animation, original.id(), context) {
+            *curve = original;
+        }
+    }
+}
+
+impl Command for FlattenTangentsCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Flatten Tangents".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.original = set_key_tangents(
+            self.animation_player,
+            self.animation,
+            &self.keys,
+            context,
+            |_, _| 0.0,
+        );
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        restore_curves(
+            self.animation_player,
+            self.animation,
+            std::mem::take(&mut self.original),
+            context,
+        );
+    }
+}
+
+/// Makes tangents of a set of keyframes smooth (Catmull-Rom style): the tangent of a key is the
+/// slope between its neighbour keys. First and last keys of a curve use the slope to their only
+/// neighbour.
+#[derive(Debug)]
+pub struct AutoSmoothTangentsCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    /// Pairs of (curve id, key id).
+    pub keys: Vec<(Uuid, Uuid)>,
+    original: Vec<Curve>,
+}
+
+impl AutoSmoothTangentsCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        keys: Vec<(Uuid, Uuid)>,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            keys,
+            original: Default::default(),
+        }
+    }
+}
+
+fn smooth_tangent(curve: &Curve, index: usize) -> f32 {
+    let keys = curve.keys();
+    let previous = &keys[index.saturating_sub(1)];
+    let next = &keys[(index + 1).min(keys.len() - 1)];
+    let duration = next.location() - previous.location();
+    if duration > 0.0 {
+        (next.value - previous.value) / duration
+    } else {
+        // A single key, or keys at the same time.
+        0.0
+    }
+}
+
+impl Command for AutoSmoothTangentsCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Auto-Smooth Tangents".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.original = set_key_tangents(
+            self.animation_player,
+            self.animation,
+            &self.keys,
+            context,
+            smooth_tangent,
+        );
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        restore_curves(
+            self.animation_player,
+            self.animation,
+            std::mem::take(&mut self.original),
+            context,
+        );
+    }
+}
+
+/// Assigns a tag to a set of keyframes, tags are used to mark breakdowns and in-betweens.
+#[derive(Debug)]
+pub struct SetKeyTagsCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    /// Pairs of (curve id, key id).
+    pub keys: Vec<(Uuid, Uuid)>,
+    pub tag: CurveKeyTag,
+    // Curve id, key id and the previous tag of every changed key.
+    original: Vec<(Uuid, Uuid, CurveKeyTag)>,
+}
+
+impl SetKeyTagsCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        keys: Vec<(Uuid, Uuid)>,
+        tag: CurveKeyTag,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            keys,
+            tag,
+            original: Default::default(),
+        }
+    }
+}
+
+impl Command for SetKeyTagsCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Key Tag".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        for (curve_id, key_id) in self.keys.iter() {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, *curve_id, context)
+            {
+                if let Some(old) = curve.set_key_tag(*key_id, self.tag) {
+                    self.original.push((*curve_id, *key_id, old));
+                }
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        for (curve_id, key_id, tag) in self.original.drain(..) {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, curve_id, context)
+            {
+                curve.set_key_tag(key_id, tag);
+            }
+        }
+    }
+}
+
+/// Rounds values of a set of keyframes to the given amount of decimal places, zero rounds the
+/// values to the nearest integer. Cleans up values like `0.9999998` that appear after scaling
+/// or baking of curves.
+#[derive(Debug)]
+pub struct RoundKeyframeValuesCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    /// Pairs of (curve id, key id).
+    pub keys: Vec<(Uuid, Uuid)>,
+    pub decimals: u32,
+    original: Vec<Curve>,
+}
+
+impl RoundKeyframeValuesCommand {
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        keys: Vec<(Uuid, Uuid)>,
+        decimals: u32,
+    ) -> Self {
+        Self {
+            animation_player,
+            animation,
+            keys,
+            decimals,
+            original: Default::default(),
+        }
+    }
+}
+
+fn round_value(value: f32, decimals: u32) -> f32 {
+    // Rounding is done in double precision, otherwise scaling by the power of ten adds an error
+    // of its own.
+    let scale = 10.0f64.powi(decimals as i32);
+    ((value as f64 * scale).round() / scale) as f32
+}
+
+impl Command for RoundKeyframeValuesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Round Key Values".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let curves = self
+            .keys
+            .iter()
+            .map(|(curve, _)| *curve)
+            .collect::<FxHashSet<_>>();
+
+        for curve_id in curves {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, curve_id, context)
+            {
+                let new_curve = rebuild_curve(
+                    curve,
+                    curve
+                        .keys()
+                        .iter()
+                        .map(|k| {
+                            let mut key = k.clone();
+                            if self.keys.contains(&(curve_id, k.id)) {
+                                key.value = round_value(key.value, self.decimals);
+                            }
+                            key
+                        })
+                        .collect::<Vec<_>>(),
+                );
+
+                self.original.push(std::mem::replace(curve, new_curve));
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        restore_curves(
+            self.animation_player,
+            self.animation,
+            std::mem::take(&mut self.original),
+            context,
+        );
+    }
+}
+
+/// Remaps the time of every key of an animation through an easing preset, so the animation
+/// could start slowly and end fast without re-keying. The easing is applied within the time slice
+/// of the animation, so the length of the animation stays the same. Tangents of cubic keys are
+/// recomputed after the remap.
+#[derive(Debug)]
+pub struct RemapAnimationTimeCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    // Easing preset with points normalized to `[0; 1]` range on both axes.
+    easing: CurvePreset,
+    original: Vec<Curve>,
+}
+
+impl RemapAnimationTimeCommand {
+    /// Creates a new command, the points of the easing preset map normalized time to normalized
+    /// time. Returns an error if the points do not go forward on both axes, such mapping would
+    /// change the order of the keys.
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        easing: &CurvePreset,
+    ) -> Result<Self, String> {
+        let points = &easing.points;
+        if points.len() < 2
+            || points
+                .windows(2)
+                .any(|pair| pair[1].x <= pair[0].x || pair[1].y <= pair[0].y)
+        {
+            return Err(
+                "The easing must always go forward in time, otherwise the order of the \
+                keys would change!"
+                    .to_string(),
+            );
+        }
+
+        // The easing is normalized, so the ends of the time slice stay in place.
+        let (first, last) = (points[0], points[points.len() - 1]);
+        let easing = CurvePreset {
+            name: easing.name.clone(),
+            points: points
+                .iter()
+                .map(|p| {
+                    Vector2::new(
+                        (p.x - first.x) / (last.x - first.x),
+                        (p.y - first.y) / (last.y - first.y),
+                    )
+                })
+                .collect(),
+        };
+
+        Ok(Self {
+            animation_player,
+            animation,
+            easing,
+            original: Default::default(),
+        })
+    }
+
+    fn remap(&self, time: f32, range: &Range<f32>) -> f32 {
+        let duration = range.end - range.start;
+        if duration <= 0.0 || time <= range.start || time >= range.end {
+            return time;
+        }
+
+        let eased = preset_value(&self.easing, (time - range.start) / duration);
+        range.start + eased * duration
+    }
+
+    /// Returns the slope of the remapping at the given time, taken from the left or from the right
+    /// side. Keys at the joints of the easing segments get different slopes on their sides.
+    fn remap_slope(&self, time: f32, range: &Range<f32>, left: bool) -> f32 {
+        let duration = range.end - range.start;
+        if duration <= 0.0 {
+            return 1.0;
+        }
+
+        let t = (time - range.start) / duration;
+        self.easing
+            .points
+            .windows(2)
+            .find(|pair| {
+                if left {
+                    pair[0].x < t && t <= pair[1].x
+                } else {
+                    pair[0].x <= t && t < pair[1].x
+                }
+            })
+            .map_or(1.0, |pair| {
+                (pair[1].y - pair[0].y) / (pair[1].x - pair[0].x)
+            })
+    }
+
+    /// Moves the keys of the curve to the remapped times. Tangents are measured in value per
+    /// second, so they are divided by the local slope of the remapping to keep the shape.
+    fn remap_curve(&self, curve: &Curve, range: &Range<f32>) -> Curve {
+        rebuild_curve(
+            curve,
+            curve
+                .keys()
+                .iter()
+                .map(|k| {
+                    let kind = match k.kind {
+                        CurveKeyKind::Cubic {
+                            left_tangent,
+                            right_tangent,
+                        } => CurveKeyKind::Cubic {
+                            left_tangent: left_tangent
+                                / self.remap_slope(k.location(), range, true),
+                            right_tangent: right_tangent
+                                / self.remap_slope(k.location(), range, false),
+                        },
+                        ref kind => kind.clone(),
+                    };
+                    let mut key = CurveKey::new(self.remap(k.location(), range), k.value, kind);
+                    key.id = k.id;
+                    key.tag = k.tag;
+                    key
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl Command for RemapAnimationTimeCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Remap Animation Time".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let animation = &mut fetch_animation_player(self.animation_player, context)
+            .animations_mut()[self.animation];
+        let range = animation.time_slice();
+
+        let mut original = Vec::new();
+        for track in animation.tracks_mut() {
+            for curve in track.data_container_mut().curves_mut() {
+                let new_curve = self.remap_curve(curve, &range);
+                original.push(std::mem::replace(curve, new_curve));
+            }
+        }
+        self.original = original;
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        restore_curves(
+            self.animation_player,
+            self.animation,
+            std::mem::take(&mut self.original),
+            context,
+        );
+    }
+}
+
+/// New location and value of a keyframe.
+#[derive(Debug, Clone)]
+pub struct MovedKeyframe {
+    pub curve: Uuid,
+    pub key: Uuid,
+    pub location: f32,
+    pub value: f32,
+}
+
+/// Moves a set of keyframes at once, so a group drag is undone in a single step.
+#[derive(Debug)]
+pub struct MoveKeyframesCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub keys: Vec<MovedKeyframe>,
+}
+
+impl MoveKeyframesCommand {
+    fn swap(&mut self, context: &mut SceneContext) {
+        for moved in self.keys.iter_mut() {
+            if let Some(curve) =
+                fetch_curve(self.animation_player, self.animation, moved.curve, context)
+            {
+                if let Some(index) = curve.keys().iter().position(|k| k.id == moved.key) {
+                    let key = &curve.keys()[index];
+                    let (old_location, old_value) = (key.location(), key.value);
+                    if let Some(value) = curve.keys_values().nth(index) {
+                        *value = moved.value;
+                    }
+                    curve.move_key(index, moved.location);
+                    moved.location = old_location;
+                    moved.value = old_value;
+                }
+            }
+        }
+    }
+}
+
+impl Command for MoveKeyframesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        if self.keys.len() == 1 {
+            "Move Key".to_string()
+        } else {
+            format!("Move {} Keys", self.keys.len())
+        }
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+}
+
+/// Time to which [`AlignKeyframesCommand`] moves the keys.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AlignTarget {
+    /// Time of the earliest selected key.
+    First,
+    /// Time of the latest selected key.
+    Last,
+}
+
+/// Defines what happens when a key is moved to the time of another key of the same curve.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KeyCollision {
+    /// The moved key replaces the other one.
+    Merge,
+    /// The whole operation is rejected.
+    Reject,
+}
+
+// Keys closer than this are considered to be at the same time.
+const KEY_TIME_EPSILON: f32 = 1.0e-5;
+
+/// Returns curve id, key id and location of every given key that exists in the animation.
+fn key_locations(animation: &Animation, keys: &[(Uuid, Uuid)]) -> Vec<(Uuid, Uuid, f32)> {
+    animation
+        .tracks()
+        .iter()
+        .flat_map(|t| t.data_container().curves_ref())
+        .flat_map(|c| c.keys().iter().map(move |k| (c.id(), k.id, k.location())))
+        .filter(|(curve, key, _)| keys.contains(&(*curve, *key)))
+        .collect()
+}
+
+/// Returns an error if a moved key ends up at the same time as another key of its curve. Keys that
+/// were at the same time before the move are not reported.
+fn check_key_collisions(animation: &Animation, times: &[(Uuid, Uuid, f32)]) -> Result<(), String> {
+    for curve in animation
+        .tracks()
+        .iter()
+        .flat_map(|t| t.data_container().curves_ref())
+    {
+        // Final time of every key of the curve and whether the key was moved or not.
+        let mut final_times = curve
+            .keys()
+            .iter()
+            .map(|k| {
+                times
+                    .iter()
+                    .find(|(c, key, _)| *c == curve.id() && *key == k.id)
+                    .map_or((k.location(), false), |(_, _, time)| (*time, true))
+            })
+            .collect::<Vec<_>>();
+        final_times.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        if let Some(pair) = final_times
+            .windows(2)
+            .find(|w| (w[0].1 || w[1].1) && w[1].0 - w[0].0 < KEY_TIME_EPSILON)
+        {
+            return Err(format!(
+                "Curve {} would have more than one key at {:.3} s.",
+                curve.name(),
+                pair[0].0
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Moves the keys to the given times, a key that ends up at the time of a moved key of the same
+/// curve is removed. Returns the original curves.
+fn retime_keyframes(
+    animation_player: Handle<Node>,
+    animation: Handle<Animation>,
+    times: &[(Uuid, Uuid, f32)],
+    context: &mut SceneContext,
+) -> Vec<Curve> {
+    let curves = times
+        .iter()
+        .map(|(curve, _, _)| *curve)
+        .collect::<FxHashSet<_>>();
+
+    let mut original = Vec::new();
+    for curve_id in curves {
+        if let Some(curve) = fetch_curve(animation_player, animation, curve_id, context) {
+            let new_time = |key: &CurveKey| {
+                times
+                    .iter()
+                    .find(|(c, k, _)| *c == curve_id && *k == key.id)
+                    .map(|(_, _, time)| *time)
+            };
+
+            // Moved keys go first, so they take the place of other keys at the same time.
+            let (moved, rest): (Vec<_>, Vec<_>) =
+                curve.keys().iter().partition(|k| new_time(k).is_some());
+
+            let mut keys: Vec<CurveKey> = Vec::new();
+            let mut moved_kept = 0;
+            for (is_moved, k) in moved
+                .into_iter()
+                .map(|k| (true, k))
+                .chain(rest.into_iter().map(|k| (false, k)))
+            {
+                let time = new_time(k).unwrap_or_else(|| k.location());
+                let occupied = keys[..moved_kept]
+                    .iter()
+                    .any(|e| (e.location() - time).abs() < KEY_TIME_EPSILON);
+                if !occupied {
+                    let mut key = CurveKey::new(time, k.value, k.kind.clone());
+                    key.id = k.id;
+                    key.tag = k.tag;
+                    keys.push(key);
+                    if is_moved {
+                        moved_kept += 1;
+                    }
+                }
+            }
+
+            let new_curve = rebuild_curve(curve, keys);
+
+            original.push(std::mem::replace(curve, new_curve));
+        }
+    }
+    original
+}
+
+/// Moves a set of keyframes to the same time, which is the time of the earliest or the latest key
+/// of the set. Useful to line up keys of several tracks into a single pose.
+#[derive(Debug)]
+pub struct AlignKeyframesCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    /// Curve id, key id and new time of every moved key.
+    pub times: Vec<(Uuid, Uuid, f32)>,
+    original: Vec<Curve>,
+}
+
+impl AlignKeyframesCommand {
+    /// Creates the command for the given pairs of (curve id, key id). Returns an error if the keys
+    /// collide with other keys and collisions are rejected.
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation_handle: Handle<Animation>,
+        animation: &Animation,
+        keys: &[(Uuid, Uuid)],
+        target: AlignTarget,
+        collision: KeyCollision,
+    ) -> Result<Self, String> {
+        let mut times = key_locations(animation, keys);
+
+        let time = times.iter().map(|(_, _, time)| *time).reduce(|a, b| {
+            if target == AlignTarget::First {
+                a.min(b)
+            } else {
+                a.max(b)
+            }
+        });
+        if let Some(time) = time {
+            for (_, _, key_time) in times.iter_mut() {
+                *key_time = time;
+            }
+        }
+
+        if collision == KeyCollision::Reject {
+            check_key_collisions(animation, &times)?;
+        }
+
+        Ok(Self {
+            animation_player,
+            animation: animation_handle,
+            times,
+            original: Default::default(),
+        })
+    }
+}
+
+impl Command for AlignKeyframesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Align Keys".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.original =
+            retime_keyframes(self.animation_player, self.animation, &self.times, context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        restore_curves(
+            self.animation_player,
+            self.animation,
+            std::mem::take(&mut self.original),
+            context,
+        );
+    }
+}
+
+/// Spreads a set of keyframes evenly in time between the earliest and the latest key of the set.
+/// Keys that share the same time move together, so poses made of keys of several tracks are kept.
+#[derive(Debug)]
+pub struct DistributeKeyframesCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    /// Curve id, key id and new time of every moved key.
+    pub times: Vec<(Uuid, Uuid, f32)>,
+    original: Vec<Curve>,
+}
+
+impl DistributeKeyframesCommand {
+    /// Creates the command for the given pairs of (curve id, key id). Returns an error if the keys
+    /// collide with other keys and collisions are rejected.
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation_handle: Handle<Animation>,
+        animation: &Animation,
+        keys: &[(Uuid, Uuid)],
+        collision: KeyCollision,
+    ) -> Result<Self, String> {
+        let mut times = key_locations(animation, keys);
+
+        let mut distinct = times.iter().map(|(_, _, time)| *time).collect::<Vec<_>>();
+        distinct.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        distinct.dedup_by(|a, b| (*a - *b).abs() < KEY_TIME_EPSILON);
+
+        if let (Some(first), Some(last)) = (distinct.first().cloned(), distinct.last().cloned()) {
+            let step = if distinct.len() > 1 {
+                (last - first) / (distinct.len() - 1) as f32
+            } else {
+                0.0
+            };
+            for (_, _, time) in times.iter_mut() {
+                if let Some(index) = distinct
+                    .iter()
+                    .position(|t| (*t - *time).abs() < KEY_TIME_EPSILON)
+                {
+                    *time = first + step * index as f32;
+                }
+            }
+        }
+
+        if collision == KeyCollision::Reject {
+            check_key_collisions(animation, &times)?;
+        }
+
+        Ok(Self {
+            animation_player,
+            animation: animation_handle,
+            times,
+            original: Default::default(),
+        })
+    }
+}
+
+impl Command for DistributeKeyframesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Distribute Keys".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.original =
+            retime_keyframes(self.animation_player, self.animation, &self.times, context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        restore_curves(
+            self.animation_player,
+            self.animation,
+            std::mem::take(&mut self.original),
+            context,
+        );
+    }
+}
+
+/// Space to which [`ConvertTrackSpaceCommand`] converts the keys of a position track.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TrackSpace {
+    /// Positions are relative to the parent of the target node.
+    Local,
+    /// Positions are in world space.
+    World,
+}
+
+/// Returns the key of the curve at the given time.
+fn key_at(curve: &Curve, time: f32) -> Option<&CurveKey> {
+    curve
+        .keys()
+        .iter()
+        .find(|k| (k.location() - time).abs() < KEY_TIME_EPSILON)
+}
+
+/// Transforms XYZ curves of a position track. Every component of a transformed position depends
+/// on every component of the original one, so each new curve has keys at the times of the keys
+/// of every original curve.
+fn transform_position_curves(curves: &[Curve], transform: &Matrix4<f32>) -> Vec<Curve> {
+    let mut times = curves
+        .iter()
+        .flat_map(|c| c.keys().iter().map(|k| k.location()))
+        .collect::<Vec<_>>();
+    times.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    times.dedup_by(|a, b| (*a - *b).abs() < KEY_TIME_EPSILON);
+
+    let mut new_keys = vec![Vec::new(); curves.len()];
+    for time in times {
+        let keys = curves.iter().map(|c| key_at(c, time)).collect::<Vec<_>>();
+
+        // Tangent of a component without a cubic key is the slope around the time.
+        let tangents = curves
+            .iter()
+            .zip(keys.iter())
+            .map(|(curve, key)| match key.map(|k| &k.kind) {
+                Some(CurveKeyKind::Cubic {
+                    left_tangent,
+                    right_tangent,
+                }) => (*left_tangent, *right_tangent),
+                _ => {
+                    let slope = neighbour_slope(curve, time);
+                    (slope, slope)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let component = |values: &[f32], i: usize| values.get(i).cloned().unwrap_or_default();
+        let values = curves.iter().map(|c| c.value_at(time)).collect::<Vec<_>>();
+        let position = transform.transform_point(&Point3::new(
+            component(&values, 0),
+            component(&values, 1),
+            component(&values, 2),
+        ));
+        let lefts = tangents.iter().map(|t| t.0).collect::<Vec<_>>();
+        let rights = tangents.iter().map(|t| t.1).collect::<Vec<_>>();
+        // Tangents are directions, so the translation does not apply to them.
+        let left = transform.transform_vector(&Vector3::new(
+            component(&lefts, 0),
+            component(&lefts, 1),
+            component(&lefts, 2),
+        ));
+        let right = transform.transform_vector(&Vector3::new(
+            component(&rights, 0),
+            component(&rights, 1),
+            component(&rights, 2),
+        ));
+
+        // Interpolation of the new keys is the "smoothest" interpolation of the original keys.
+        let is_cubic = keys
+            .iter()
+            .flatten()
+            .any(|k| matches!(k.kind, CurveKeyKind::Cubic { .. }));
+        let is_linear = keys
+            .iter()
+            .flatten()
+            .any(|k| matches!(k.kind, CurveKeyKind::Linear));
+
+        for (i, curve_keys) in new_keys.iter_mut().enumerate().take(3) {
+            let kind = if is_cubic {
+                CurveKeyKind::Cubic {
+                    left_tangent: left[i],
+                    right_tangent: right[i],
+                }
+            } else if is_linear {
+                CurveKeyKind::Linear
+            } else {
+                CurveKeyKind::Constant
+            };
+            let mut key = CurveKey::new(time, position[i], kind);
+            if let Some(original) = keys[i] {
+                key.id = original.id;
+                key.tag = original.tag;
+            }
+            curve_keys.push(key);
+        }
+    }
+
+    curves
+        .iter()
+        .zip(new_keys)
+        .map(|(curve, keys)| rebuild_curve(curve, keys))
+        .collect()
+}
+
+/// Converts position keys of a track between local space of the target node and world space.
+/// The current global transform of the parent of the target node is used for the conversion.
+#[derive(Debug)]
+pub struct ConvertTrackSpaceCommand {
+    pub animation_player: Handle<Node>,
+    pub animation: Handle<Animation>,
+    pub track: Uuid,
+    /// Transform that is applied to every position of the track.
+    pub transform: Matrix4<f32>,
+    original: Vec<Curve>,
+}
+
+impl ConvertTrackSpaceCommand {
+    /// Creates the command for the given track. Returns an error if the track is not a position
+    /// track, or if the target node of the track or its parent does not exist.
+    pub fn new(
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+        track: &Track,
+        space: TrackSpace,
+        graph: &Graph,
+    ) -> Result<Self, String> {
+        if *track.binding() != ValueBinding::Position {
+            return Err("Only position tracks can be converted.".to_string());
+        }
+
+        let target = graph
+            .try_get(track.target())
+            .ok_or_else(|| "Target node of the track does not exist.".to_string())?;
+        let parent = graph
+            .try_get(target.parent())
+            .ok_or_else(|| "Target node of the track has no parent.".to_string())?;
+
+        let parent_transform = parent.global_transform();
+        let transform = match space {
+            TrackSpace::World => parent_transform,
+            TrackSpace::Local => parent_transform
+                .try_inverse()
+                .ok_or_else(|| "Transform of the parent node is degenerate.".to_string())?,
+        };
+
+        Ok(Self {
+            animation_player,
+            animation,
+            track: track.id(),
+            transform,
+            original: Default::default(),
+        })
+    }
+}
+
+impl Command for ConvertTrackSpaceCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Convert Track Space".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        if let Some(track) = fetch_animation(self.animation_player, self.animation, context)
+            .tracks_mut()
+            .iter_mut()
+            .find(|t| t.id() == self.track)
+        {
+            let curves = track.data_container_mut().curves_mut();
+            let converted = transform_position_curves(curves, &self.transform);
+            self.original = curves.to_vec();
+            for (curve, converted) in curves.iter_mut().zip(converted) {
+                *curve = converted;
+            }
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        restore_curves(
+            self.animation_player,
+            self.animation,
+            std::mem::take(&mut self.original),
+            context,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn linear_curve(points: &[(f32, f32)]) -> Curve {
+        Curve::from(
+            points
+                .iter()
+                .map(|(t, v)| CurveKey::new(*t, *v, CurveKeyKind::Linear))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn points(curve: &Curve) -> Vec<(f32, f32)> {
+        curve
+            .keys()
+            .iter()
+            .map(|k| (k.location(), k.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_paste_and_unpaste_restores_curve() {
+        let original = linear_curve(&[(0.0, 1.0), (1.0, 2.0), (2.0, 3.0)]);
+        let mut curve = original.clone();
+
+        // Two pasted keys share a location, one more overwrites an existing key.
+        let keys = vec![
+            CurveKey::new(1.0, 10.0, CurveKeyKind::Linear),
+            CurveKey::new(1.0, 20.0, CurveKeyKind::Linear),
+            CurveKey::new(3.0, 30.0, CurveKeyKind::Linear),
+        ];
+
+        let overwritten = paste_keys(&mut curve, &keys);
+        assert_eq!(overwritten.len(), 1);
+        assert!(keys
+            .iter()
+            .all(|key| curve.keys().iter().any(|k| k.id == key.id)));
+
+        unpaste_keys(&mut curve, &keys, overwritten);
+        assert_eq!(points(&curve), points(&original));
+    }
+
+    #[test]
+    fn test_duplicate_animation_has_new_ids() {
+        let mut source = Animation::default();
+        let mut track = Track::new_position();
+        for curve in track.data_container_mut().curves_mut() {
+            *curve = linear_curve(&[(0.0, 1.0), (1.0, 2.0)]);
+        }
+        source.add_track(track);
+
+        let command = DuplicateAnimationCommand::new(Handle::NONE, &source);
+        let copy = match &command.add {
+            AddAnimationCommand::NonExecuted { animation, .. } => animation,
+            _ => unreachable!(),
+        };
+
+        let ids = |animation: &Animation| {
+            let mut ids = Vec::new();
+            for track in animation.tracks() {
+                ids.push(track.id());
+                for curve in track.data_container().curves_ref() {
+                    ids.push(curve.id());
+                    ids.extend(curve.keys().iter().map(|k| k.id));
+                }
+            }
+            ids
+        };
+        let source_ids = ids(&source);
+        let copy_ids = ids(copy);
+        assert_eq!(source_ids.len(), copy_ids.len());
+        assert!(copy_ids.iter().all(|id| !source_ids.contains(id)));
+
+        // Keys themselves are the same.
+        for (source_track, track) in source.tracks().iter().zip(copy.tracks()) {
+            for (source_curve, curve) in source_track
+                .data_container()
+                .curves_ref()
+                .iter()
+                .zip(track.data_container().curves_ref())
+            {
+                assert_eq!(points(source_curve), points(curve));
+            }
+        }
+    }
+
+    #[test]
+    fn test_simplify_curve() {
+        // Keys on a straight line are redundant.
+        let line = linear_curve(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]);
+        assert_eq!(
+            points(&simplify_curve(&line, 0.01)),
+            vec![(0.0, 0.0), (3.0, 3.0)]
+        );
+
+        let curve = linear_curve(&[
+            (0.0, 0.0),
+            (1.0, 0.1),
+            (2.0, -0.1),
+            (3.0, 3.0),
+            (4.0, 0.2),
+            (5.0, 0.0),
+        ]);
+        let tolerance = 0.5;
+        let simplified = simplify_curve(&curve, tolerance);
+        assert!(simplified.keys().len() < curve.keys().len());
+        assert_eq!(simplified.keys().first(), curve.keys().first());
+        assert_eq!(simplified.keys().last(), curve.keys().last());
+        assert_eq!(simplified.id(), curve.id());
+        for i in 0..=100 {
+            let time = i as f32 * 0.05;
+            assert!((simplified.value_at(time) - curve.value_at(time)).abs() <= tolerance);
+        }
+    }
+
+    #[test]
+    fn test_delete_keys_in_range() {
+        let curve = linear_curve(&[(0.0, 0.0), (1.0, 1.0), (2.0, 4.0), (3.0, 9.0), (4.0, 16.0)]);
+
+        // Keys at the ends of the range are deleted too.
+        assert_eq!(
+            points(&delete_keys_in_range(&curve, &(1.0..3.0), false)),
+            vec![(0.0, 0.0), (4.0, 16.0)]
+        );
+        assert_eq!(
+            points(&delete_keys_in_range(&curve, &(1.0..3.0), true)),
+            vec![(0.0, 0.0), (1.0, 1.0), (3.0, 9.0), (4.0, 16.0)]
+        );
+
+        // Nothing to preserve at the boundary that has no keys outside the range.
+        assert_eq!(
+            points(&delete_keys_in_range(&curve, &(0.0..1.0), true)),
+            vec![(2.0, 4.0), (3.0, 9.0), (4.0, 16.0)]
+        );
+
+        // A range without keys changes nothing.
+        assert_eq!(
+            points(&delete_keys_in_range(&curve, &(1.2..1.8), true)),
+            points(&curve)
+        );
+    }
+
+    #[test]
+    fn test_delete_keys_in_zero_width_range() {
+        let curve = linear_curve(&[(0.0, 0.0), (1.0, 1.0), (2.0, 4.0)]);
+
+        assert_eq!(
+            points(&delete_keys_in_range(&curve, &(1.0..1.0), false)),
+            vec![(0.0, 0.0), (2.0, 4.0)]
+        );
+        assert_eq!(
+            points(&delete_keys_in_range(&curve, &(1.0..1.0), true)),
+            vec![(0.0, 0.0), (1.0, 1.0), (2.0, 4.0)]
+        );
+    }
+
+    #[test]
+    fn test_remap_time() {
+        let presets = crate::animation::curve_preset::builtin_presets();
+        let ease_in = presets.iter().find(|p| p.name == "Ease In").unwrap();
+        let command = RemapAnimationTimeCommand::new(Handle::NONE, Handle::NONE, ease_in).unwrap();
+
+        let range = 1.0..3.0;
+        assert_eq!(command.remap(range.start, &range), range.start);
+        assert_eq!(command.remap(range.end, &range), range.end);
+        // Keys outside of the time slice stay in place.
+        assert_eq!(command.remap(0.5, &range), 0.5);
+        assert_eq!(command.remap(3.5, &range), 3.5);
+
+        let mut previous = range.start;
+        for i in 1..=40 {
+            let time = command.remap(range.start + i as f32 * 0.05, &range);
+            assert!(time > previous);
+            assert!(time <= range.end);
+            previous = time;
+        }
+
+        // Tangents are divided by the slope of the remapping, so the value changes at the same
+        // rate per remapped second.
+        let curve = Curve::from(vec![
+            CurveKey::new(
+                1.0,
+                0.0,
+                CurveKeyKind::Cubic {
+                    left_tangent: 2.0,
+                    right_tangent: 4.0,
+                },
+            ),
+            CurveKey::new(
+                2.0,
+                1.0,
+                CurveKeyKind::Cubic {
+                    left_tangent: 3.0,
+                    right_tangent: -1.0,
+                },
+            ),
+            CurveKey::new(3.0, 0.0, CurveKeyKind::Linear),
+        ]);
+        let remapped = command.remap_curve(&curve, &range);
+        for (key, remapped_key) in curve.keys().iter().zip(remapped.keys()) {
+            assert_eq!(remapped_key.id, key.id);
+            assert_eq!(remapped_key.value, key.value);
+            match (&key.kind, &remapped_key.kind) {
+                (
+                    CurveKeyKind::Cubic {
+                        left_tangent,
+                        right_tangent,
+                    },
+                    CurveKeyKind::Cubic {
+                        left_tangent: remapped_left,
+                        right_tangent: remapped_right,
+                    },
+                ) => {
+                    let left_slope = command.remap_slope(key.location(), &range, true);
+                    let right_slope = command.remap_slope(key.location(), &range, false);
+                    assert!(left_slope > 0.0 && right_slope > 0.0);
+                    assert!((remapped_left * left_slope - left_tangent).abs() < 1.0e-4);
+                    assert!((remapped_right * right_slope - right_tangent).abs() < 1.0e-4);
+                }
+                (kind, remapped_kind) => assert_eq!(kind, remapped_kind),
+            }
+        }
+        // The start of the time slice is not moved from the left side.
+        assert_eq!(command.remap_slope(range.start, &range, true), 1.0);
+
+        // A linear easing keeps the tangents.
+        let linear = CurvePreset {
+            name: "Linear".to_owned(),
+            points: vec![Vector2::new(0.0, 0.0), Vector2::new(1.0, 1.0)],
+        };
+        let command = RemapAnimationTimeCommand::new(Handle::NONE, Handle::NONE, &linear).unwrap();
+        assert_eq!(command.remap_curve(&curve, &range).keys(), curve.keys());
+    }
+
+    #[test]
+    fn test_remap_time_rejects_backward_easing() {
+        let easing = CurvePreset {
+            name: "Backward".to_owned(),
+            points: vec![
+                Vector2::new(0.0, 0.0),
+                Vector2::new(0.5, 0.8),
+                Vector2::new(0.7, 0.6),
+                Vector2::new(1.0, 1.0),
+            ],
+        };
+        assert!(RemapAnimationTimeCommand::new(Handle::NONE, Handle::NONE, &easing).is_err());
+    }
+
+    #[test]
+    fn test_reverse_curve() {
+        let curve = linear_curve(&[(0.0, 0.0), (1.0, 1.0), (3.0, 2.0)]);
+
+        let reversed = reverse_curve(&curve);
+        assert_eq!(points(&reversed), vec![(0.0, 2.0), (2.0, 1.0), (3.0, 0.0)]);
+        assert_eq!(reversed.id(), curve.id());
+
+        // Reversal keeps ids of the keys, so reversing twice gives the same keys back.
+        let twice = reverse_curve(&reversed);
+        assert_eq!(points(&twice), points(&curve));
+        assert!(twice
+            .keys()
+            .iter()
+            .zip(curve.keys())
+            .all(|(a, b)| a.id == b.id));
+    }
+}
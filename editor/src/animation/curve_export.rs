@@ -0,0 +1,73 @@
+use crate::animation::ruler::TimeFormat;
+use fyrox::core::curve::{Curve, CurveKeyKind};
+use std::{fmt::Write, path::Path};
+
+/// Writes keys of the curve as CSV rows with a header. Tangents are written only for cubic
+/// keys, other keys have empty tangent columns. Frames are not rounded, so the keys could be
+/// imported back at the same times.
+pub fn curve_to_csv(curve: &Curve, time_format: TimeFormat, fps: f32) -> String {
+    let mut csv = String::new();
+    let _ = writeln!(
+        csv,
+        "{},value,in_tangent,out_tangent",
+        match time_format {
+            TimeFormat::Seconds => "time",
+            TimeFormat::Frames => "frame",
+        }
+    );
+
+    for key in curve.keys() {
+        match time_format {
+            TimeFormat::Seconds => {
+                let _ = write!(csv, "{}", key.location());
+            }
+            TimeFormat::Frames => {
+                let _ = write!(csv, "{}", key.location() * fps);
+            }
+        }
+
+        let _ = write!(csv, ",{}", key.value);
+
+        if let CurveKeyKind::Cubic {
+            left_tangent,
+            right_tangent,
+        } = key.kind
+        {
+            let _ = writeln!(csv, ",{},{}", left_tangent, right_tangent);
+        } else {
+            let _ = writeln!(csv, ",,");
+        }
+    }
+
+    csv
+}
+
+/// Writes each curve to its own file. A single curve is written to the given path, otherwise
+/// the name (or the index) of a curve is appended to the file name.
+pub fn export_curves(
+    path: &Path,
+    curves: &[&Curve],
+    time_format: TimeFormat,
+    fps: f32,
+) -> std::io::Result<()> {
+    for (i, curve) in curves.iter().enumerate() {
+        let curve_path = if curves.len() == 1 {
+            path.to_path_buf()
+        } else {
+            let stem = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let suffix = if curve.name().is_empty() {
+                i.to_string()
+            } else {
+                curve.name().to_string()
+            };
+            path.with_file_name(format!("{}_{}.csv", stem, suffix))
+        };
+
+        std::fs::write(curve_path, curve_to_csv(curve, time_format, fps))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,628 @@
+//! Import of keyframes from CSV and JSON files into a curve of an animation track.
+
+use crate::{
+    animation::{
+        command::{KeyCollision, ReplaceTrackCurveCommand},
+        report_locked_edit,
+        selection::AnimationSelection,
+    },
+    gui::make_dropdown_list_option,
+    message::MessageSender,
+};
+use fyrox::{
+    core::{
+        curve::{Curve, CurveKey, CurveKeyKind},
+        log::Log,
+        pool::Handle,
+        uuid::Uuid,
+    },
+    fxhash::FxHashSet,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        file_browser::{FileSelectorBuilder, FileSelectorMessage, Filter},
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDown, NumericUpDownBuilder},
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    scene::animation::AnimationPlayer,
+};
+use serde::Deserialize;
+use std::{
+    fmt::{Display, Formatter},
+    path::Path,
+};
+
+// Keys closer than this are considered to be at the same time.
+const TIME_EPSILON: f32 = 1.0e-5;
+
+#[derive(Debug)]
+pub enum CurveImportError {
+    Io(std::io::Error),
+    /// A row of a CSV file is neither a `time,value` pair nor a `time,value,in,out` quadruple.
+    Csv {
+        line: usize,
+        reason: String,
+    },
+    Json(serde_json::Error),
+    UnsupportedFormat,
+    /// Time of the point with the given index is not greater than the time of the previous one.
+    NotMonotonic {
+        index: usize,
+    },
+}
+
+impl Display for CurveImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CurveImportError::Io(err) => write!(f, "unable to read the file: {}", err),
+            CurveImportError::Csv { line, reason } => write!(f, "line {}: {}", line, reason),
+            CurveImportError::Json(err) => write!(f, "invalid JSON: {}", err),
+            CurveImportError::UnsupportedFormat => {
+                write!(
+                    f,
+                    "unsupported file format, only CSV and JSON are supported"
+                )
+            }
+            CurveImportError::NotMonotonic { index } => write!(
+                f,
+                "time of the point {} is not greater than the time of the previous point",
+                index + 1
+            ),
+        }
+    }
+}
+
+impl From<std::io::Error> for CurveImportError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for CurveImportError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+/// A point read from a file. Tangents are given only for cubic keys, such as the ones exported
+/// by the curve editor.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ImportedPoint {
+    pub time: f32,
+    pub value: f32,
+    pub tangents: Option<(f32, f32)>,
+}
+
+/// A point of JSON array, it could be either `[time, value]` or `{"time": t, "value": v}`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonPoint {
+    Pair([f32; 2]),
+    Object { time: f32, value: f32 },
+}
+
+/// Parses `time,value` rows, optionally followed by in and out tangents (empty tangents mean a
+/// linear key). Empty lines and lines starting with `#` are ignored, the first row could also be
+/// a header. Times are frames if the first column of the header is `frame`.
+pub fn parse_csv(text: &str, fps: f32) -> Result<Vec<ImportedPoint>, CurveImportError> {
+    let mut points = Vec::new();
+    let mut time_scale = 1.0;
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let error = |reason: String| CurveImportError::Csv {
+            line: i + 1,
+            reason,
+        };
+
+        let fields = line.split(',').map(|f| f.trim()).collect::<Vec<_>>();
+        if fields.len() != 2 && fields.len() != 4 {
+            return Err(error(format!(
+                "expected 2 or 4 columns, got {}",
+                fields.len()
+            )));
+        }
+
+        match (fields[0].parse::<f32>(), fields[1].parse::<f32>()) {
+            (Ok(time), Ok(value)) => {
+                let tangents = match fields[2..] {
+                    [left, right] if !left.is_empty() || !right.is_empty() => {
+                        match (left.parse::<f32>(), right.parse::<f32>()) {
+                            (Ok(left), Ok(right)) => Some((left, right)),
+                            (Err(e), _) | (_, Err(e)) => return Err(error(e.to_string())),
+                        }
+                    }
+                    _ => None,
+                };
+                points.push(ImportedPoint {
+                    time: time * time_scale,
+                    value,
+                    tangents,
+                });
+            }
+            // Skip the header.
+            _ if points.is_empty() && fields.iter().all(|f| f.parse::<f32>().is_err()) => {
+                if fields[0].eq_ignore_ascii_case("frame") {
+                    time_scale = 1.0 / fps;
+                }
+            }
+            (Err(e), _) | (_, Err(e)) => return Err(error(e.to_string())),
+        }
+    }
+    Ok(points)
+}
+
+/// Parses an array of `[time, value]` pairs or `{"time": t, "value": v}` objects.
+pub fn parse_json(text: &str) -> Result<Vec<ImportedPoint>, CurveImportError> {
+    Ok(serde_json::from_str::<Vec<JsonPoint>>(text)?
+        .into_iter()
+        .map(|p| {
+            let (time, value) = match p {
+                JsonPoint::Pair([time, value]) => (time, value),
+                JsonPoint::Object { time, value } => (time, value),
+            };
+            ImportedPoint {
+                time,
+                value,
+                tangents: None,
+            }
+        })
+        .collect())
+}
+
+/// Reads points from a file, the format is selected by the extension of the file. Times of the
+/// points must be strictly increasing.
+pub fn load_points(path: &Path, fps: f32) -> Result<Vec<ImportedPoint>, CurveImportError> {
+    let text = std::fs::read_to_string(path)?;
+    let points = match path.extension().and_then(|e| e.to_str()) {
+        Some("csv") => parse_csv(&text, fps)?,
+        Some("json") => parse_json(&text)?,
+        _ => return Err(CurveImportError::UnsupportedFormat),
+    };
+
+    if let Some(index) = points
+        .windows(2)
+        .position(|pair| pair[1].time <= pair[0].time)
+        .map(|i| i + 1)
+    {
+        return Err(CurveImportError::NotMonotonic { index });
+    }
+
+    Ok(points)
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum CurveImportMode {
+    /// Existing keys of the curve are removed.
+    Replace,
+    /// New keys are added to the existing ones, with the given offset in time.
+    Append,
+}
+
+impl CurveImportMode {
+    const ALL: [CurveImportMode; 2] = [CurveImportMode::Replace, CurveImportMode::Append];
+
+    fn name(self) -> &'static str {
+        match self {
+            CurveImportMode::Replace => "Replace",
+            CurveImportMode::Append => "Append",
+        }
+    }
+}
+
+/// Creates a copy of the curve with imported points. Points without tangents become linear keys,
+/// that suits densely sampled data (such as motion capture) best. Appended points that end up at
+/// the time of an existing key either replace it or fail the import, depending on the collision.
+pub fn make_imported_curve(
+    curve: &Curve,
+    points: &[ImportedPoint],
+    mode: CurveImportMode,
+    offset: f32,
+    collision: KeyCollision,
+) -> Result<Curve, String> {
+    let mut new_curve = curve.clone();
+    let offset = match mode {
+        CurveImportMode::Replace => {
+            new_curve.clear();
+            0.0
+        }
+        CurveImportMode::Append => offset,
+    };
+    for point in points {
+        let time = point.time + offset;
+        if let Some(index) = new_curve
+            .keys()
+            .iter()
+            .position(|k| (k.location() - time).abs() < TIME_EPSILON)
+        {
+            if collision == KeyCollision::Reject {
+                return Err(format!(
+                    "Curve {} would have more than one key at {:.3} s.",
+                    curve.name(),
+                    time
+                ));
+            }
+            new_curve.remove_key(index);
+        }
+
+        let kind = match point.tangents {
+            Some((left_tangent, right_tangent)) => CurveKeyKind::Cubic {
+                left_tangent,
+                right_tangent,
+            },
+            None => CurveKeyKind::Linear,
+        };
+        new_curve.add_key(CurveKey::new(time, point.value, kind));
+    }
+    Ok(new_curve)
+}
+
+pub struct CurveImportDialog {
+    window: Handle<UiNode>,
+    mode: Handle<UiNode>,
+    offset: Handle<UiNode>,
+    select_file: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    file_selector: Handle<UiNode>,
+    selected_mode: CurveImportMode,
+}
+
+impl CurveImportDialog {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        fn text(text: &str, row: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+            TextBuilder::new(
+                WidgetBuilder::new()
+                    .with_vertical_alignment(VerticalAlignment::Center)
+                    .on_row(row)
+                    .on_column(0),
+            )
+            .with_text(text)
+            .build(ctx)
+        }
+
+        let mode = DropdownListBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(1.0))
+                .on_row(0)
+                .on_column(1),
+        )
+        .with_items(
+            CurveImportMode::ALL
+                .iter()
+                .map(|mode| make_dropdown_list_option(ctx, mode.name()))
+                .collect(),
+        )
+        .with_selected(0)
+        .build(ctx);
+        let offset = NumericUpDownBuilder::<f32>::new(
+            WidgetBuilder::new()
+                .with_enabled(false)
+                .with_margin(Thickness::uniform(1.0))
+                .on_row(1)
+                .on_column(1),
+        )
+        .with_value(0.0)
+        .build(ctx);
+        let select_file;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(240.0).with_height(110.0))
+            .open(false)
+            .with_title(WindowTitle::text("Import Curve"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(2.0))
+                        .with_child(text("Mode", 0, ctx))
+                        .with_child(mode)
+                        .with_child(text("Time Offset", 1, ctx))
+                        .with_child(offset)
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .on_row(2)
+                                    .on_column(1)
+                                    .with_child({
+                                        select_file = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(70.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("File...")
+                                        .build(ctx);
+                                        select_file
+                                    })
+                                    .with_child({
+                                        cancel = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(70.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Cancel")
+                                        .build(ctx);
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_column(Column::strict(90.0))
+                .add_column(Column::stretch())
+                .add_row(Row::strict(22.0))
+                .add_row(Row::strict(22.0))
+                .add_row(Row::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        let file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text("Select Curve To Import")),
+        )
+        .with_filter(Filter::new(|p: &Path| {
+            if let Some(ext) = p.extension() {
+                let ext = ext.to_string_lossy();
+                ext.as_ref() == "csv" || ext.as_ref() == "json"
+            } else {
+                p.is_dir()
+            }
+        }))
+        .build(ctx);
+
+        Self {
+            window,
+            mode,
+            offset,
+            select_file,
+            cancel,
+            file_selector,
+            selected_mode: CurveImportMode::Replace,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        sender: &MessageSender,
+        ui: &UserInterface,
+        animation_player: &AnimationPlayer,
+        selection: &AnimationSelection,
+        locked_curves: &FxHashSet<Uuid>,
+        fps: f32,
+        collision: KeyCollision,
+    ) {
+        if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.mode
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.selected_mode = CurveImportMode::ALL[*index];
+                ui.send_message(WidgetMessage::enabled(
+                    self.offset,
+                    MessageDirection::ToWidget,
+                    self.selected_mode == CurveImportMode::Append,
+                ));
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.select_file {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+                ui.send_message(WindowMessage::open_modal(
+                    self.file_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+                ui.send_message(FileSelectorMessage::root(
+                    self.file_selector,
+                    MessageDirection::ToWidget,
+                    Some(std::env::current_dir().unwrap()),
+                ));
+            } else if message.destination() == self.cancel {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.file_selector {
+                let mut selected_curves = selection.curves();
+                let curve = match (selected_curves.next(), selected_curves.next()) {
+                    (Some(id), None) => animation_player
+                        .animations()
+                        .try_get(selection.animation)
+                        .and_then(|a| {
+                            a.tracks()
+                                .iter()
+                                .flat_map(|t| t.data_container().curves_ref())
+                                .find(|c| c.id() == id)
+                        }),
+                    _ => None,
+                };
+
+                let curve = match curve {
+                    Some(curve) => curve,
+                    None => {
+                        Log::warn("Select a single curve to import the keys to!");
+                        return;
+                    }
+                };
+
+                if locked_curves.contains(&curve.id()) {
+                    report_locked_edit(sender);
+                    return;
+                }
+
+                match load_points(path, fps) {
+                    Ok(points) => {
+                        let offset = ui
+                            .node(self.offset)
+                            .query_component::<NumericUpDown<f32>>()
+                            .unwrap()
+                            .value;
+
+                        match make_imported_curve(
+                            curve,
+                            &points,
+                            self.selected_mode,
+                            offset,
+                            collision,
+                        ) {
+                            Ok(curve) => sender.do_scene_command(ReplaceTrackCurveCommand {
+                                animation_player: selection.animation_player,
+                                animation: selection.animation,
+                                curve,
+                            }),
+                            Err(err) => Log::err(format!(
+                                "Failed to import curve from {}! Reason: {}",
+                                path.display(),
+                                err
+                            )),
+                        }
+                    }
+                    Err(err) => Log::err(format!(
+                        "Failed to import curve from {}! Reason: {}",
+                        path.display(),
+                        err
+                    )),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::animation::{curve_export::curve_to_csv, ruler::TimeFormat};
+
+    fn test_curve() -> Curve {
+        Curve::from(vec![
+            CurveKey::new(0.0, 1.0, CurveKeyKind::Linear),
+            CurveKey::new(
+                0.37,
+                -2.5,
+                CurveKeyKind::Cubic {
+                    left_tangent: 0.25,
+                    right_tangent: -1.5,
+                },
+            ),
+            CurveKey::new(1.2, 3.0, CurveKeyKind::Linear),
+        ])
+    }
+
+    fn assert_round_trip(time_format: TimeFormat) {
+        let fps = 30.0;
+        let curve = test_curve();
+        let points = parse_csv(&curve_to_csv(&curve, time_format, fps), fps).unwrap();
+        let imported = make_imported_curve(
+            &curve,
+            &points,
+            CurveImportMode::Replace,
+            0.0,
+            KeyCollision::Reject,
+        )
+        .unwrap();
+
+        assert_eq!(imported.keys().len(), curve.keys().len());
+        for (imported, original) in imported.keys().iter().zip(curve.keys()) {
+            assert!((imported.location() - original.location()).abs() < 1.0e-5);
+            assert_eq!(imported.value, original.value);
+            assert_eq!(imported.kind, original.kind);
+        }
+    }
+
+    #[test]
+    fn test_csv_round_trip_in_seconds() {
+        assert_round_trip(TimeFormat::Seconds);
+    }
+
+    #[test]
+    fn test_csv_round_trip_in_frames() {
+        assert_round_trip(TimeFormat::Frames);
+    }
+
+    #[test]
+    fn test_append_collisions() {
+        let curve = test_curve();
+        let points = [ImportedPoint {
+            time: 0.2,
+            value: 5.0,
+            tangents: None,
+        }];
+
+        assert!(make_imported_curve(
+            &curve,
+            &points,
+            CurveImportMode::Append,
+            1.0,
+            KeyCollision::Reject
+        )
+        .is_err());
+
+        let merged = make_imported_curve(
+            &curve,
+            &points,
+            CurveImportMode::Append,
+            1.0,
+            KeyCollision::Merge,
+        )
+        .unwrap();
+        assert_eq!(merged.keys().len(), curve.keys().len());
+        assert_eq!(merged.keys().last().unwrap().value, 5.0);
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let points = parse_json(r#"[[0.0, 1.0], {"time": 0.5, "value": 2.0}]"#).unwrap();
+        assert_eq!(
+            points.iter().map(|p| (p.time, p.value)).collect::<Vec<_>>(),
+            vec![(0.0, 1.0), (0.5, 2.0)]
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_pairs() {
+        let points = parse_csv("time,value\n0,1\n# comment\n\n0.5,2\n", 30.0).unwrap();
+        assert_eq!(
+            points,
+            vec![
+                ImportedPoint {
+                    time: 0.0,
+                    value: 1.0,
+                    tangents: None
+                },
+                ImportedPoint {
+                    time: 0.5,
+                    value: 2.0,
+                    tangents: None
+                },
+            ]
+        );
+        assert!(parse_csv("0,1,2\n", 30.0).is_err());
+
+        // The message tells which row failed.
+        let err = parse_csv("0,1\n1,2\n2\n", 30.0).unwrap_err();
+        assert!(err.to_string().starts_with("line 3:"));
+    }
+}
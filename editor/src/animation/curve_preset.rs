@@ -0,0 +1,153 @@
+//! Curve presets replace the shape of a curve between its first and last keys, the keys
+//! themselves stay in place.
+
+use crate::{animation::command::rebuild_curve, settings::animation::CurvePreset};
+use fyrox::core::{
+    algebra::Vector2,
+    curve::{Curve, CurveKey, CurveKeyKind},
+};
+use std::f32::consts::PI;
+
+/// Amount of segments that are used to sample a shape of a preset.
+const PRESET_SAMPLES: usize = 32;
+
+fn ease_out_bounce(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+fn ease_out_elastic(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else if t >= 1.0 {
+        1.0
+    } else {
+        2.0f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+    }
+}
+
+fn sampled_preset(name: &str, func: fn(f32) -> f32) -> CurvePreset {
+    CurvePreset {
+        name: name.to_owned(),
+        points: (0..=PRESET_SAMPLES)
+            .map(|i| {
+                let t = i as f32 / PRESET_SAMPLES as f32;
+                Vector2::new(t, func(t))
+            })
+            .collect(),
+    }
+}
+
+/// Returns presets that are always available in the editor.
+pub fn builtin_presets() -> Vec<CurvePreset> {
+    vec![
+        CurvePreset {
+            name: "Linear".to_owned(),
+            points: vec![Vector2::new(0.0, 0.0), Vector2::new(1.0, 1.0)],
+        },
+        sampled_preset("Ease In", |t| t * t * t),
+        sampled_preset("Ease Out", |t| 1.0 - (1.0 - t).powi(3)),
+        sampled_preset("Ease In-Out", |t| {
+            if t < 0.5 {
+                4.0 * t * t * t
+            } else {
+                1.0 - (-2.0 * t + 2.0).powi(3) * 0.5
+            }
+        }),
+        sampled_preset("Bounce", ease_out_bounce),
+        sampled_preset("Elastic", ease_out_elastic),
+    ]
+}
+
+/// Creates a copy of the curve with intermediate keys replaced by the points of the preset. The
+/// preset is scaled to the time and value ranges between the first and the last keys of the
+/// curve. Returns `None` if the curve has less than two keys.
+pub fn apply_preset(curve: &Curve, preset: &CurvePreset) -> Option<Curve> {
+    let keys = curve.keys();
+    let (first, last) = match (keys.first(), keys.last()) {
+        (Some(first), Some(last)) if last.location() > first.location() => (first, last),
+        _ => return None,
+    };
+
+    let duration = last.location() - first.location();
+    let span = last.value - first.value;
+
+    // Points are connected with straight lines, so the first key must be linear too.
+    let mut first_key = first.clone();
+    first_key.kind = CurveKeyKind::Linear;
+
+    let mut new_keys = vec![first_key];
+    new_keys.extend(
+        preset
+            .points
+            .iter()
+            .filter(|p| p.x > 0.0 && p.x < 1.0)
+            .map(|p| {
+                CurveKey::new(
+                    first.location() + p.x * duration,
+                    first.value + p.y * span,
+                    CurveKeyKind::Linear,
+                )
+            }),
+    );
+    new_keys.push(last.clone());
+
+    Some(rebuild_curve(curve, new_keys))
+}
+
+/// Returns a value of the preset at the given normalized time, points of the preset are connected
+/// with straight lines.
+pub fn preset_value(preset: &CurvePreset, t: f32) -> f32 {
+    let points = &preset.points;
+    match points.iter().position(|p| p.x >= t) {
+        Some(0) => points[0].y,
+        Some(index) => {
+            let (a, b) = (points[index - 1], points[index]);
+            let width = b.x - a.x;
+            // Points at the same time make a jump, the value after the jump is taken.
+            if width <= 0.0 {
+                b.y
+            } else {
+                a.y + (b.y - a.y) * (t - a.x) / width
+            }
+        }
+        None => points.last().map_or(t, |p| p.y),
+    }
+}
+
+/// Makes a preset from the shape of the curve. Returns `None` if the first and the last keys of
+/// the curve are at the same time or have the same value, such shape cannot be normalized.
+pub fn make_preset(name: String, curve: &Curve) -> Option<CurvePreset> {
+    let keys = curve.keys();
+    let (first, last) = (keys.first()?, keys.last()?);
+
+    let duration = last.location() - first.location();
+    let span = last.value - first.value;
+    if duration <= 0.0 || span == 0.0 {
+        return None;
+    }
+
+    Some(CurvePreset {
+        name,
+        points: (0..=PRESET_SAMPLES)
+            .map(|i| {
+                let t = i as f32 / PRESET_SAMPLES as f32;
+                let value = curve.value_at(first.location() + t * duration);
+                Vector2::new(t, (value - first.value) / span)
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,315 @@
+//! Diff view lists changes of the selected animation since the scene was loaded or saved last
+//! time: added, removed and modified tracks, curves and keys. A click on a change selects the
+//! affected track or curve.
+
+use crate::animation::{selection::SelectedEntity, track::curve_component_name};
+use fyrox::{
+    animation::{track::Track, Animation},
+    core::{algebra::Vector2, curve::Curve, pool::Handle},
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        expander::ExpanderBuilder,
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+    scene::graph::Graph,
+};
+
+const MAX_DIFF_HEIGHT: f32 = 120.0;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl ChangeKind {
+    fn prefix(self) -> &'static str {
+        match self {
+            ChangeKind::Added => "+",
+            ChangeKind::Removed => "-",
+            ChangeKind::Modified => "~",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationChange {
+    pub kind: ChangeKind,
+    pub description: String,
+    /// Track or curve that is selected when the change is clicked. Removed entities cannot be
+    /// selected.
+    pub entity: Option<SelectedEntity>,
+}
+
+impl AnimationChange {
+    fn new(kind: ChangeKind, description: String, entity: Option<SelectedEntity>) -> Self {
+        Self {
+            kind,
+            description,
+            entity,
+        }
+    }
+}
+
+fn track_name(track: &Track, graph: &Graph) -> String {
+    format!(
+        "{}.{}",
+        graph
+            .try_get(track.target())
+            .map(|n| n.name())
+            .unwrap_or("<Unassigned>"),
+        track.binding()
+    )
+}
+
+/// Returns the amount of added, removed and modified keys of a curve.
+fn diff_keys(saved: &Curve, current: &Curve) -> (usize, usize, usize) {
+    let mut added = 0;
+    let mut modified = 0;
+    for key in current.keys() {
+        match saved.keys().iter().find(|k| k.id == key.id) {
+            Some(saved_key) if saved_key != key => modified += 1,
+            Some(_) => (),
+            None => added += 1,
+        }
+    }
+    let removed = saved
+        .keys()
+        .iter()
+        .filter(|k| !current.keys().iter().any(|c| c.id == k.id))
+        .count();
+    (added, removed, modified)
+}
+
+fn diff_track(saved: &Track, current: &Track, graph: &Graph, changes: &mut Vec<AnimationChange>) {
+    let name = track_name(current, graph);
+
+    let mut properties = Vec::new();
+    if saved.target() != current.target() {
+        properties.push("target");
+    }
+    if saved.binding() != current.binding() {
+        properties.push("binding");
+    }
+    if saved.is_enabled() != current.is_enabled() {
+        properties.push("enabled");
+    }
+    if !properties.is_empty() {
+        changes.push(AnimationChange::new(
+            ChangeKind::Modified,
+            format!("{}: {} changed", name, properties.join(", ")),
+            Some(SelectedEntity::Track(current.id())),
+        ));
+    }
+
+    let value_kind = current.data_container().value_kind();
+    let saved_curves = saved.data_container().curves_ref();
+    for (index, curve) in current.data_container().curves_ref().iter().enumerate() {
+        let curve_name = format!("{}.{}", name, curve_component_name(value_kind, index));
+        match saved_curves.iter().find(|c| c.id() == curve.id()) {
+            Some(saved_curve) => {
+                let (added, removed, modified) = diff_keys(saved_curve, curve);
+                if added + removed + modified > 0 {
+                    changes.push(AnimationChange::new(
+                        ChangeKind::Modified,
+                        format!(
+                            "{}: {} added, {} removed, {} modified keys",
+                            curve_name, added, removed, modified
+                        ),
+                        Some(SelectedEntity::Curve(curve.id())),
+                    ));
+                }
+            }
+            None => changes.push(AnimationChange::new(
+                ChangeKind::Added,
+                curve_name,
+                Some(SelectedEntity::Curve(curve.id())),
+            )),
+        }
+    }
+}
+
+/// Lists changes of the current state of an animation relative to its saved state.
+pub fn diff_animations(
+    saved: &Animation,
+    current: &Animation,
+    graph: &Graph,
+) -> Vec<AnimationChange> {
+    let mut changes = Vec::new();
+
+    let mut properties = Vec::new();
+    if saved.name() != current.name() {
+        properties.push("name");
+    }
+    if saved.time_slice() != current.time_slice() {
+        properties.push("time slice");
+    }
+    if saved.speed() != current.speed() {
+        properties.push("speed");
+    }
+    if saved.is_loop() != current.is_loop() {
+        properties.push("looping");
+    }
+    if saved.root_motion_settings_ref() != current.root_motion_settings_ref() {
+        properties.push("root motion");
+    }
+    if saved.signals() != current.signals() {
+        properties.push("signals");
+    }
+    if saved.markers() != current.markers() {
+        properties.push("markers");
+    }
+    if !properties.is_empty() {
+        changes.push(AnimationChange::new(
+            ChangeKind::Modified,
+            format!("Animation: {} changed", properties.join(", ")),
+            None,
+        ));
+    }
+
+    for track in current.tracks() {
+        match saved.tracks().iter().find(|t| t.id() == track.id()) {
+            Some(saved_track) => diff_track(saved_track, track, graph, &mut changes),
+            None => changes.push(AnimationChange::new(
+                ChangeKind::Added,
+                track_name(track, graph),
+                Some(SelectedEntity::Track(track.id())),
+            )),
+        }
+    }
+
+    for saved_track in saved.tracks() {
+        if !current.tracks().iter().any(|t| t.id() == saved_track.id()) {
+            changes.push(AnimationChange::new(
+                ChangeKind::Removed,
+                track_name(saved_track, graph),
+                None,
+            ));
+        }
+    }
+
+    changes
+}
+
+pub struct AnimationDiff {
+    pub panel: Handle<UiNode>,
+    header: Handle<UiNode>,
+    rows_panel: Handle<UiNode>,
+    rows: Vec<(Handle<UiNode>, Option<SelectedEntity>)>,
+    // Changes at the moment of last sync, if there was any sync since last clear.
+    synced: Option<Option<Vec<AnimationChange>>>,
+}
+
+fn make_row(change: &AnimationChange, ctx: &mut BuildContext) -> Handle<UiNode> {
+    let text = format!("{} {}", change.kind.prefix(), change.description);
+    if change.entity.is_some() {
+        ButtonBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+            .with_content(
+                TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left_right(4.0)))
+                    .with_text(text)
+                    .with_horizontal_text_alignment(HorizontalAlignment::Left)
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .build(ctx),
+            )
+            .build(ctx)
+    } else {
+        TextBuilder::new(
+            WidgetBuilder::new()
+                .with_height(20.0)
+                .with_margin(Thickness::left_right(5.0)),
+        )
+        .with_text(text)
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx)
+    }
+}
+
+impl AnimationDiff {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let rows_panel = StackPanelBuilder::new(WidgetBuilder::new()).build(ctx);
+
+        let header = TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(2.0)))
+            .with_text("Changes")
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .build(ctx);
+
+        let panel = ExpanderBuilder::new(WidgetBuilder::new().on_row(2))
+            .with_header(header)
+            .with_content(
+                ScrollViewerBuilder::new(
+                    WidgetBuilder::new()
+                        .with_max_size(Vector2::new(f32::INFINITY, MAX_DIFF_HEIGHT)),
+                )
+                .with_content(rows_panel)
+                .build(ctx),
+            )
+            .with_expanded(false)
+            .build(ctx);
+
+        Self {
+            panel,
+            header,
+            rows_panel,
+            rows: Default::default(),
+            synced: None,
+        }
+    }
+
+    /// Rebuilds the rows when the changes differ from the ones of last sync. `None` means that
+    /// the animation was not saved yet, so it has no saved state to compare with.
+    pub fn sync_to_model(&mut self, changes: Option<Vec<AnimationChange>>, ui: &mut UserInterface) {
+        if self.synced.as_ref() == Some(&changes) {
+            return;
+        }
+
+        self.clear(ui);
+
+        let header = match changes.as_ref() {
+            Some(changes) if changes.is_empty() => String::from("Changes (None)"),
+            Some(changes) => format!("Changes ({})", changes.len()),
+            None => String::from("Changes (Not Saved Yet)"),
+        };
+        ui.send_message(TextMessage::text(
+            self.header,
+            MessageDirection::ToWidget,
+            header,
+        ));
+
+        for change in changes.iter().flatten() {
+            let row = make_row(change, &mut ui.build_ctx());
+            ui.send_message(WidgetMessage::link(
+                row,
+                MessageDirection::ToWidget,
+                self.rows_panel,
+            ));
+            self.rows.push((row, change.entity.clone()));
+        }
+
+        self.synced = Some(changes);
+    }
+
+    pub fn clear(&mut self, ui: &UserInterface) {
+        for (row, _) in self.rows.drain(..) {
+            ui.send_message(WidgetMessage::remove(row, MessageDirection::ToWidget));
+        }
+        self.synced = None;
+    }
+
+    /// Returns an entity of a change that was clicked in the diff view.
+    pub fn handle_ui_message(&self, message: &UiMessage) -> Option<SelectedEntity> {
+        if let Some(ButtonMessage::Click) = message.data() {
+            return self
+                .rows
+                .iter()
+                .find(|(row, _)| *row == message.destination())
+                .and_then(|(_, entity)| entity.clone());
+        }
+        None
+    }
+}
@@ -0,0 +1,135 @@
+//! Euler view shows curves of rotation tracks as pitch, yaw and roll angles in degrees, instead
+//! of radians. Rotation tracks store Euler angles already, so the view only changes units and
+//! unwraps the angles between adjacent keys.
+
+use crate::animation::command::rebuild_curve;
+use fyrox::{
+    animation::{container::TrackValueKind, Animation},
+    core::curve::{Curve, CurveKeyKind},
+};
+use std::f32::consts::{PI, TAU};
+
+/// Names of the XYZ components of a rotation track.
+pub const EULER_COMPONENT_NAMES: [&str; 3] = ["Pitch", "Yaw", "Roll"];
+
+fn scale_curve(curve: &Curve, scale: f32) -> Curve {
+    rebuild_curve(
+        curve,
+        curve
+            .keys()
+            .iter()
+            .map(|k| {
+                let mut key = k.clone();
+                key.value *= scale;
+                // Tangents are slopes of the curve, so they are scaled the same way.
+                if let CurveKeyKind::Cubic {
+                    left_tangent,
+                    right_tangent,
+                } = &mut key.kind
+                {
+                    *left_tangent *= scale;
+                    *right_tangent *= scale;
+                }
+                key
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Shifts every key by a multiple of a full turn, so the difference between the values of
+/// adjacent keys never exceeds a half of a turn. Such curve represents the same orientations,
+/// but does not spin the other way around between `180` and `-180` degrees.
+fn unwrap_angles(curve: &mut Curve) {
+    let mut previous = None;
+    for value in curve.keys_values() {
+        if let Some(previous) = previous {
+            let delta: f32 = *value - previous;
+            *value -= (delta / TAU).round() * TAU;
+        }
+        previous = Some(*value);
+    }
+}
+
+/// Returns `true` if the curve belongs to a rotation track of the animation.
+pub fn is_rotation_curve(animation: &Animation, curve: &Curve) -> bool {
+    animation.tracks().iter().any(|t| {
+        t.data_container().value_kind() == TrackValueKind::UnitQuaternion
+            && t.data_container()
+                .curves_ref()
+                .iter()
+                .any(|c| c.id() == curve.id())
+    })
+}
+
+/// Converts a curve of a rotation track to the Euler view, angles in the view are in degrees.
+pub fn to_euler_view(curve: &Curve) -> Curve {
+    let mut unwrapped = curve.clone();
+    unwrap_angles(&mut unwrapped);
+    scale_curve(&unwrapped, 180.0 / PI)
+}
+
+/// Converts a curve of the Euler view back to the curve of a rotation track. The original curve
+/// of the animation is returned when the view of it was not changed, so unwrapping of the angles
+/// alone does not modify the animation. Curves of other tracks are returned as is.
+pub fn from_euler_view(animation: &Animation, view: &Curve) -> Curve {
+    if !is_rotation_curve(animation, view) {
+        return view.clone();
+    }
+
+    let original = animation
+        .tracks()
+        .iter()
+        .flat_map(|t| t.data_container().curves_ref())
+        .find(|c| c.id() == view.id());
+
+    match original {
+        Some(original) if to_euler_view(original) == *view => original.clone(),
+        _ => scale_curve(view, PI / 180.0),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fyrox::core::curve::CurveKey;
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_euler_view_is_in_degrees_and_unwrapped() {
+        let curve = Curve::from(vec![
+            CurveKey::new(0.0, 3.0, CurveKeyKind::Linear),
+            CurveKey::new(1.0, -3.0, CurveKeyKind::Linear),
+            CurveKey::new(
+                2.0,
+                FRAC_PI_2,
+                CurveKeyKind::Cubic {
+                    left_tangent: 1.0,
+                    right_tangent: -1.0,
+                },
+            ),
+        ]);
+
+        let view = to_euler_view(&curve);
+        let values = view.keys().iter().map(|k| k.value).collect::<Vec<_>>();
+        assert!((values[0] - 3.0f32.to_degrees()).abs() < 1.0e-3);
+        // -3 radians is the same orientation as `TAU - 3`, it is closer to the previous key.
+        assert!((values[1] - (TAU - 3.0).to_degrees()).abs() < 1.0e-3);
+        for pair in values.windows(2) {
+            assert!((pair[1] - pair[0]).abs() <= 180.0);
+        }
+        assert_eq!(
+            view.keys()[2].kind,
+            CurveKeyKind::Cubic {
+                left_tangent: 180.0 / PI,
+                right_tangent: -180.0 / PI,
+            }
+        );
+        assert_eq!(view.id(), curve.id());
+    }
+
+    #[test]
+    fn test_euler_view_of_other_tracks_is_unchanged() {
+        let view = Curve::from(vec![CurveKey::new(0.0, 90.0, CurveKeyKind::Linear)]);
+        assert_eq!(from_euler_view(&Animation::default(), &view), view);
+    }
+}
@@ -0,0 +1,86 @@
+//! Curve isolation limits preview of an animation to a single curve. Every other animated
+//! property stays at its rest value - the value it had before entering preview mode. Track
+//! weights use the same rest values to scale contribution of a track in preview.
+
+use crate::animation::{command::rebuild_curve, track::bound_values};
+use fyrox::{
+    animation::track::Track,
+    core::{
+        curve::{Curve, CurveKey, CurveKeyKind},
+        uuid::Uuid,
+    },
+    scene::node::Node,
+};
+
+/// Returns the rest value of the curve with the given index, the first key of the curve is used
+/// if the property cannot be read from the rest node.
+fn rest_value(rest: Option<&Vec<f32>>, index: usize, curve: &Curve) -> f32 {
+    rest.and_then(|rest| rest.get(index).cloned())
+        .or_else(|| curve.keys().first().map(|k| k.value))
+        .unwrap_or_default()
+}
+
+fn flat_curve(curve: &Curve, value: f32) -> Curve {
+    rebuild_curve(
+        curve,
+        vec![CurveKey::new(0.0, value, CurveKeyKind::Constant)],
+    )
+}
+
+/// Replaces every curve of the track, except the isolated one, with a flat curve that holds the
+/// rest value of respective component of the property. The first key of a curve is used as rest
+/// value, if the property cannot be read from the rest node.
+pub fn isolate_curve(track: &mut Track, isolated: Uuid, rest_node: Option<&Node>) {
+    let rest = rest_node.and_then(|node| bound_values(node, track.binding()));
+
+    for (index, curve) in track
+        .data_container_mut()
+        .curves_mut()
+        .iter_mut()
+        .enumerate()
+    {
+        if curve.id() == isolated {
+            continue;
+        }
+
+        let value = rest_value(rest.as_ref(), index, curve);
+        *curve = flat_curve(curve, value);
+    }
+}
+
+/// Scales every curve of the track towards the rest value of respective component of the
+/// property, so the track contributes to the pose only partially.
+pub fn weight_track(track: &mut Track, weight: f32, rest_node: Option<&Node>) {
+    let rest = rest_node.and_then(|node| bound_values(node, track.binding()));
+
+    for (index, curve) in track
+        .data_container_mut()
+        .curves_mut()
+        .iter_mut()
+        .enumerate()
+    {
+        let rest_value = rest_value(rest.as_ref(), index, curve);
+
+        let weighted = rebuild_curve(
+            curve,
+            curve
+                .keys()
+                .iter()
+                .map(|k| {
+                    let mut key = k.clone();
+                    key.value = rest_value + (key.value - rest_value) * weight;
+                    if let CurveKeyKind::Cubic {
+                        left_tangent,
+                        right_tangent,
+                    } = &mut key.kind
+                    {
+                        *left_tangent *= weight;
+                        *right_tangent *= weight;
+                    }
+                    key
+                })
+                .collect::<Vec<_>>(),
+        );
+        *curve = weighted;
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{
+    animation::{
+        command::ReplaceTrackCurveCommand,
+        report_locked_edit,
+        selection::{AnimationSelection, SelectedEntity},
+    },
+    message::MessageSender,
+    send_sync_message,
+};
+use fyrox::{
+    animation::Animation,
+    core::{
+        color::Color,
+        curve::{Curve, CurveKey, CurveKeyKind},
+        pool::Handle,
+        uuid::Uuid,
+    },
+    fxhash::FxHashSet,
+    gui::{
+        brush::Brush,
+        message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+};
+
+// Keys closer than this are considered to be at the same time.
+const TIME_EPSILON: f32 = 1.0e-5;
+
+/// A panel with numeric fields for precise editing of a single selected keyframe.
+pub struct KeyframeInspector {
+    pub panel: Handle<UiNode>,
+    time: Handle<UiNode>,
+    value: Handle<UiNode>,
+    left_tangent: Handle<UiNode>,
+    right_tangent: Handle<UiNode>,
+    error: Handle<UiNode>,
+    // Curve and key that are shown in the inspector.
+    key: Option<(Uuid, Uuid)>,
+}
+
+fn find_key<'a>(
+    animation: &'a Animation,
+    curve: Uuid,
+    key: Uuid,
+) -> Option<(&'a Curve, usize, &'a CurveKey)> {
+    let curve = animation
+        .tracks()
+        .iter()
+        .flat_map(|t| t.data_container().curves_ref())
+        .find(|c| c.id() == curve)?;
+    let (index, key) = curve.keys().iter().enumerate().find(|(_, k)| k.id == key)?;
+    Some((curve, index, key))
+}
+
+impl KeyframeInspector {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        fn text(text: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+            TextBuilder::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness::left_right(4.0))
+                    .with_vertical_alignment(VerticalAlignment::Center),
+            )
+            .with_text(text)
+            .build(ctx)
+        }
+
+        fn field(ctx: &mut BuildContext) -> Handle<UiNode> {
+            NumericUpDownBuilder::<f32>::new(
+                WidgetBuilder::new()
+                    .with_width(70.0)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_precision(3)
+            .build(ctx)
+        }
+
+        let time = field(ctx);
+        let value = field(ctx);
+        let left_tangent = field(ctx);
+        let right_tangent = field(ctx);
+        let error = TextBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_margin(Thickness::left_right(4.0))
+                .with_vertical_alignment(VerticalAlignment::Center)
+                .with_foreground(Brush::Solid(Color::opaque(220, 70, 70))),
+        )
+        .with_text("Another key already exists at this time")
+        .build(ctx);
+
+        let panel = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .on_row(2)
+                .with_child(text("Time", ctx))
+                .with_child(time)
+                .with_child(text("Value", ctx))
+                .with_child(value)
+                .with_child(text("In", ctx))
+                .with_child(left_tangent)
+                .with_child(text("Out", ctx))
+                .with_child(right_tangent)
+                .with_child(error),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
+        Self {
+            panel,
+            time,
+            value,
+            left_tangent,
+            right_tangent,
+            error,
+            key: None,
+        }
+    }
+
+    fn set_error_visibility(&self, visible: bool, ui: &UserInterface) {
+        send_sync_message(
+            ui,
+            WidgetMessage::visibility(self.error, MessageDirection::ToWidget, visible),
+        );
+    }
+
+    fn sync_fields(&self, key: &CurveKey, ui: &UserInterface) {
+        let (tangents, left_tangent, right_tangent) = match key.kind {
+            CurveKeyKind::Cubic {
+                left_tangent,
+                right_tangent,
+            } => (true, left_tangent, right_tangent),
+            _ => (false, 0.0, 0.0),
+        };
+
+        for (field, value) in [
+            (self.time, key.location()),
+            (self.value, key.value),
+            (self.left_tangent, left_tangent),
+            (self.right_tangent, right_tangent),
+        ] {
+            send_sync_message(
+                ui,
+                NumericUpDownMessage::value(field, MessageDirection::ToWidget, value),
+            );
+        }
+
+        // Tangents are used only by cubic keys.
+        for field in [self.left_tangent, self.right_tangent] {
+            send_sync_message(
+                ui,
+                WidgetMessage::enabled(field, MessageDirection::ToWidget, tangents),
+            );
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        sender: &MessageSender,
+        ui: &UserInterface,
+        animation: &Animation,
+        selection: &AnimationSelection,
+        locked_curves: &FxHashSet<Uuid>,
+    ) {
+        if let Some(NumericUpDownMessage::<f32>::Value(value)) = message.data() {
+            let destination = message.destination();
+            if message.direction() != MessageDirection::FromWidget
+                || ![self.time, self.value, self.left_tangent, self.right_tangent]
+                    .contains(&destination)
+            {
+                return;
+            }
+
+            let (curve, index, key) = match self
+                .key
+                .and_then(|(curve, key)| find_key(animation, curve, key))
+            {
+                Some(found) => found,
+                None => return,
+            };
+
+            if locked_curves.contains(&curve.id()) {
+                // Forced sync brings the fields back to the values of the key.
+                report_locked_edit(sender);
+                return;
+            }
+
+            let mut new_curve = curve.clone();
+            if destination == self.time {
+                // Merging keys silently would lose one of them, so such times are rejected.
+                if curve
+                    .keys()
+                    .iter()
+                    .any(|k| k.id != key.id && (k.location() - *value).abs() < TIME_EPSILON)
+                {
+                    self.set_error_visibility(true, ui);
+                    self.sync_fields(key, ui);
+                    return;
+                }
+
+                new_curve.move_key(index, *value);
+            } else if destination == self.value {
+                if let Some(key_value) = new_curve.keys_values().nth(index) {
+                    *key_value = *value;
+                }
+            } else if let Some(mut new_key) = new_curve.remove_key(index) {
+                if let CurveKeyKind::Cubic {
+                    ref mut left_tangent,
+                    ref mut right_tangent,
+                } = new_key.kind
+                {
+                    if destination == self.left_tangent {
+                        *left_tangent = *value;
+                    } else {
+                        *right_tangent = *value;
+                    }
+                }
+                new_curve.add_key(new_key);
+            }
+
+            self.set_error_visibility(false, ui);
+
+            if &new_curve != curve {
+                sender.do_scene_command(ReplaceTrackCurveCommand {
+                    animation_player: selection.animation_player,
+                    animation: selection.animation,
+                    curve: new_curve,
+                });
+            }
+        }
+    }
+
+    pub fn clear(&mut self, ui: &UserInterface) {
+        self.key = None;
+        self.set_error_visibility(false, ui);
+        send_sync_message(
+            ui,
+            WidgetMessage::visibility(self.panel, MessageDirection::ToWidget, false),
+        );
+    }
+
+    pub fn sync_to_model(
+        &mut self,
+        animation: &Animation,
+        selection: &AnimationSelection,
+        ui: &UserInterface,
+    ) {
+        let mut selected_keys = selection.entities.iter().filter_map(|e| {
+            if let SelectedEntity::Keyframe { curve, key } = e {
+                Some((*curve, *key))
+            } else {
+                None
+            }
+        });
+
+        // The inspector works only with a single key.
+        let selected_key = match (selected_keys.next(), selected_keys.next()) {
+            (Some(selected_key), None) => Some(selected_key),
+            _ => None,
+        };
+
+        match selected_key.and_then(|(curve, key)| find_key(animation, curve, key)) {
+            Some((curve, _, key)) => {
+                let key_ref = Some((curve.id(), key.id));
+                if self.key != key_ref {
+                    self.key = key_ref;
+                    self.set_error_visibility(false, ui);
+                }
+
+                self.sync_fields(key, ui);
+                send_sync_message(
+                    ui,
+                    WidgetMessage::visibility(self.panel, MessageDirection::ToWidget, true),
+                );
+            }
+            None => self.clear(ui),
+        }
+    }
+}
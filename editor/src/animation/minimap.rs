@@ -0,0 +1,277 @@
+//! Minimap is a thin strip that shows the whole time range of an animation with a compressed
+//! silhouette of the visible curves. The part of the time range that is visible in the curve
+//! editor is shown as a viewport indicator, dragging the indicator pans the curve editor.
+
+use fyrox::{
+    core::{algebra::Vector2, color::Color, curve::Curve, math::Rect, pool::Handle},
+    gui::{
+        brush::Brush,
+        define_constructor, define_widget_deref,
+        draw::{CommandTexture, Draw, DrawingContext},
+        message::{MessageDirection, MouseButton, UiMessage},
+        widget::{Widget, WidgetBuilder, WidgetMessage},
+        BuildContext, Control, UiNode, UserInterface, BRUSH_BRIGHT, BRUSH_DARKER,
+    },
+};
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut},
+};
+
+/// Horizontal distance between samples of the silhouette of a curve, in pixels.
+const SILHOUETTE_STEP: f32 = 2.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MinimapMessage {
+    Zoom(f32),
+    /// Time view position of the curve editor. The minimap emits it when the viewport indicator
+    /// is dragged.
+    ViewPosition(f32),
+    /// Length of the animation, the minimap shows at least `[0; length]` range.
+    Length(f32),
+    /// Curves that are shown as the silhouette.
+    SyncCurves(Vec<Curve>),
+}
+
+impl MinimapMessage {
+    define_constructor!(MinimapMessage:Zoom => fn zoom(f32), layout: false);
+    define_constructor!(MinimapMessage:ViewPosition => fn view_position(f32), layout: false);
+    define_constructor!(MinimapMessage:Length => fn length(f32), layout: false);
+    define_constructor!(MinimapMessage:SyncCurves => fn sync_curves(Vec<Curve>), layout: false);
+}
+
+#[derive(Clone)]
+struct DragContext {
+    initial_mouse_x: f32,
+    initial_view_position: f32,
+}
+
+#[derive(Clone)]
+pub struct Minimap {
+    widget: Widget,
+    zoom: f32,
+    view_position: f32,
+    length: f32,
+    curves: Vec<Curve>,
+    drag_context: Option<DragContext>,
+}
+
+define_widget_deref!(Minimap);
+
+impl Minimap {
+    /// Returns time range shown by the minimap.
+    fn time_range(&self) -> (f32, f32) {
+        let (min, max) = self
+            .curves
+            .iter()
+            .flat_map(|c| c.keys())
+            .fold((0.0f32, self.length), |(min, max), k| {
+                (min.min(k.location()), max.max(k.location()))
+            });
+        if max > min {
+            (min, max)
+        } else {
+            (min, min + 1.0)
+        }
+    }
+
+    /// Returns time range visible in the curve editor. The curve editor has the same width as
+    /// the minimap and scales its view around its center.
+    fn visible_range(&self) -> (f32, f32) {
+        let half_width = self.actual_local_size().x * 0.5;
+        let center = half_width - self.view_position;
+        let half_range = half_width / self.zoom.max(f32::EPSILON);
+        (center - half_range, center + half_range)
+    }
+
+    fn time_to_local(&self, time: f32) -> f32 {
+        let (min, max) = self.time_range();
+        (time - min) / (max - min) * self.actual_local_size().x
+    }
+
+    fn local_to_time(&self, x: f32) -> f32 {
+        let (min, max) = self.time_range();
+        min + x / self.actual_local_size().x.max(f32::EPSILON) * (max - min)
+    }
+
+    fn indicator_bounds(&self) -> Rect<f32> {
+        let (start, end) = self.visible_range();
+        let left = self.time_to_local(start);
+        let right = self.time_to_local(end);
+        Rect::new(
+            left,
+            0.0,
+            (right - left).max(2.0),
+            self.actual_local_size().y,
+        )
+    }
+
+    fn set_view_position(&mut self, view_position: f32, ui: &UserInterface) {
+        if self.view_position != view_position {
+            self.view_position = view_position;
+            ui.send_message(MinimapMessage::view_position(
+                self.handle,
+                MessageDirection::FromWidget,
+                view_position,
+            ));
+        }
+    }
+}
+
+impl Control for Minimap {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawingContext) {
+        let local_bounds = self.bounding_rect();
+
+        ctx.push_rect_filled(&local_bounds, None);
+        ctx.commit(
+            self.clip_bounds(),
+            self.background.clone(),
+            CommandTexture::None,
+            None,
+        );
+
+        let (min_value, max_value) = self
+            .curves
+            .iter()
+            .flat_map(|c| c.keys())
+            .fold((f32::MAX, f32::MIN), |(min, max), k| {
+                (min.min(k.value), max.max(k.value))
+            });
+        let value_to_local = |value: f32| {
+            let height = local_bounds.h() - 2.0;
+            if max_value > min_value {
+                1.0 + height * (1.0 - (value - min_value) / (max_value - min_value))
+            } else {
+                1.0 + height * 0.5
+            }
+        };
+
+        let samples = (local_bounds.w() / SILHOUETTE_STEP).ceil() as usize;
+        for curve in self.curves.iter().filter(|c| !c.keys().is_empty()) {
+            let mut previous = None;
+            for i in 0..=samples {
+                let x = (i as f32 * SILHOUETTE_STEP).min(local_bounds.w());
+                let point = Vector2::new(x, value_to_local(curve.value_at(self.local_to_time(x))));
+                if let Some(previous) = previous {
+                    ctx.push_line(previous, point, 1.0);
+                }
+                previous = Some(point);
+            }
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            self.foreground.clone(),
+            CommandTexture::None,
+            None,
+        );
+
+        let indicator = self.indicator_bounds();
+        ctx.push_rect_filled(&indicator, None);
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::from_rgba(255, 255, 255, 30)),
+            CommandTexture::None,
+            None,
+        );
+        ctx.push_rect(&indicator, 1.0);
+        ctx.commit(self.clip_bounds(), BRUSH_BRIGHT, CommandTexture::None, None);
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<MinimapMessage>() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    MinimapMessage::Zoom(zoom) => {
+                        self.zoom = *zoom;
+                    }
+                    MinimapMessage::ViewPosition(position) => {
+                        self.view_position = *position;
+                    }
+                    MinimapMessage::Length(length) => {
+                        self.length = *length;
+                    }
+                    MinimapMessage::SyncCurves(curves) => {
+                        self.curves = curves.clone();
+                    }
+                }
+            }
+        } else if let Some(msg) = message.data::<WidgetMessage>() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::FromWidget
+            {
+                match msg {
+                    WidgetMessage::MouseDown { pos, button } => {
+                        if *button == MouseButton::Left {
+                            let x = self.screen_to_local(*pos).x;
+
+                            // A click outside of the indicator centers the view at the click.
+                            if !self.indicator_bounds().contains(Vector2::new(x, 0.0)) {
+                                let half_width = self.actual_local_size().x * 0.5;
+                                self.set_view_position(half_width - self.local_to_time(x), ui);
+                            }
+
+                            self.drag_context = Some(DragContext {
+                                initial_mouse_x: x,
+                                initial_view_position: self.view_position,
+                            });
+                            ui.capture_mouse(self.handle);
+                        }
+                    }
+                    WidgetMessage::MouseMove { pos, .. } => {
+                        if let Some(drag_context) = self.drag_context.clone() {
+                            let x = self.screen_to_local(*pos).x;
+                            let delta = self.local_to_time(x)
+                                - self.local_to_time(drag_context.initial_mouse_x);
+                            self.set_view_position(drag_context.initial_view_position - delta, ui);
+                        }
+                    }
+                    WidgetMessage::MouseUp { button, .. } => {
+                        if *button == MouseButton::Left && self.drag_context.take().is_some() {
+                            ui.release_mouse_capture();
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+pub struct MinimapBuilder {
+    widget_builder: WidgetBuilder,
+}
+
+impl MinimapBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self { widget_builder }
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let minimap = Minimap {
+            widget: self
+                .widget_builder
+                .with_background(BRUSH_DARKER)
+                .with_foreground(Brush::Solid(Color::opaque(130, 130, 130)))
+                .build(),
+            zoom: 1.0,
+            view_position: 0.0,
+            length: 0.0,
+            curves: Default::default(),
+            drag_context: None,
+        };
+
+        ctx.add_node(UiNode::new(minimap))
+    }
+}
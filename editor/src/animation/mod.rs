@@ -1,59 +1,649 @@
 use crate::message::MessageSender;
 use crate::{
     animation::{
+        audio::AudioReference,
         command::{
-            AddAnimationSignal, MoveAnimationSignal, RemoveAnimationSignal,
-            ReplaceTrackCurveCommand,
+            copy_track, rebuild_curve, AddAnimationMarker, AddAnimationSignal, AddTrackCommand,
+            AlignKeyframesCommand, AutoSmoothTangentsCommand, DeleteKeyframesInRangeCommand,
+            DistributeKeyframesCommand, FlattenTangentsCommand, InsertKeyframesCommand,
+            KeyCollision, MoveAnimationMarker, MoveAnimationSignal, MoveKeyframesCommand,
+            MovedKeyframe, PasteKeyframesCommand, RemoveAnimationMarker, RemoveAnimationSignal,
+            RemoveKeyframesCommand, ReplaceTrackCurveCommand, RoundKeyframeValuesCommand,
+            SetKeyTagsCommand,
         },
-        ruler::{RulerBuilder, RulerMessage, SignalView},
-        selection::{AnimationSelection, SelectedEntity},
+        diff::{diff_animations, AnimationDiff},
+        keyframe::KeyframeInspector,
+        minimap::{MinimapBuilder, MinimapMessage},
+        onion_skin::{draw_ghost, NEXT_GHOST_COLOR, PREVIOUS_GHOST_COLOR},
+        overview::AnimationOverview,
+        ruler::{MarkerView, RulerBuilder, RulerMessage, SignalView, TimeFormat},
+        selection::{selected_curves, AnimationSelection, SelectedEntity},
         thumb::{ThumbBuilder, ThumbMessage},
         toolbar::{Toolbar, ToolbarAction},
-        track::TrackList,
+        track::{
+            bound_values, current_key_values, curve_color, curve_component_name,
+            select_all_track_keys, TrackList, TrackListAction, ValueLimits,
+        },
+    },
+    scene::{
+        commands::{ChangeSelectionCommand, CommandGroup, SceneCommand},
+        EditorScene, Selection,
     },
-    scene::{commands::ChangeSelectionCommand, EditorScene, Selection},
-    send_sync_message, Message,
+    send_sync_message,
+    settings::{
+        animation::AnimationEditorSettings,
+        keys::KeyBindings,
+        windows::{AnimationEditorWindowSettings, DEFAULT_TRACK_LIST_WIDTH},
+    },
+    Message,
 };
 use fyrox::{
-    animation::AnimationSignal,
-    core::{algebra::Vector2, math::Rect, pool::Handle, uuid::Uuid},
+    animation::{
+        track::Track, value::BoundValueCollection, Animation, AnimationMarker, AnimationSignal,
+    },
+    core::{
+        algebra::Vector2,
+        color::Color,
+        curve::{Curve, CurveKey, CurveKeyKind, CurveKeyTag},
+        log::Log,
+        math::{round_to_step, Rect},
+        pool::Handle,
+        uuid::Uuid,
+    },
     engine::Engine,
-    fxhash::FxHashSet,
+    fxhash::{FxHashMap, FxHashSet},
     gui::{
         border::BorderBuilder,
+        brush::Brush,
         check_box::CheckBoxMessage,
         curve::{CurveEditorBuilder, CurveEditorMessage, HighlightZone},
-        grid::{Column, GridBuilder, Row},
-        message::{MessageDirection, UiMessage},
+        grid::{Column, Grid, GridBuilder, Row},
+        key::HotKey,
+        message::{CursorIcon, KeyCode, MessageDirection, UiMessage},
+        numeric::{NumericUpDown, NumericUpDownBuilder, NumericUpDownMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::TextBox,
+        utils::make_simple_tooltip,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
-        BuildContext, UiNode, UserInterface, BRUSH_DARK, BRUSH_PRIMARY,
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment, BRUSH_DARK, BRUSH_LIGHT, BRUSH_PRIMARY,
     },
-    scene::{animation::AnimationPlayer, node::Node, Scene},
+    scene::{animation::AnimationPlayer, graph::Graph, node::Node, Scene},
 };
+use std::{cmp::Ordering, path::PathBuf};
 
+mod animation_file;
+mod audio;
 pub mod command;
+mod curve_export;
+mod curve_import;
+mod curve_preset;
+mod diff;
+mod euler;
+mod isolate;
+mod keyframe;
+mod minimap;
+mod onion_skin;
+mod overview;
 mod ruler;
 pub mod selection;
 mod thumb;
 mod toolbar;
 mod track;
 
+/// Moves every key of the curve to the closest multiple of the given step. Keys that land on the
+/// same frame either merge, in which case a key changed relative to the original curve is kept, or
+/// fail the snapping, depending on the collision.
+fn snap_curve_keys(
+    curve: &Curve,
+    original: &Curve,
+    step: f32,
+    collision: KeyCollision,
+) -> Result<Curve, String> {
+    let changed = |k: &CurveKey| {
+        original
+            .keys()
+            .iter()
+            .find(|o| o.id == k.id)
+            .map_or(true, |o| o.location() != k.location() || o.value != k.value)
+    };
+
+    // Changed keys go first, so they take the place of other keys at the same frame.
+    let (changed, rest): (Vec<_>, Vec<_>) = curve.keys().iter().partition(|k| changed(k));
+
+    let mut keys: Vec<CurveKey> = Vec::new();
+    for k in changed.into_iter().chain(rest) {
+        let time = round_to_step(k.location(), step);
+        if keys.iter().any(|e| e.location() == time) {
+            if collision == KeyCollision::Reject {
+                return Err(format!(
+                    "Curve {} would have more than one key at {:.3} s.",
+                    curve.name(),
+                    time
+                ));
+            }
+            continue;
+        }
+        let mut key = CurveKey::new(time, k.value, k.kind.clone());
+        key.id = k.id;
+        key.tag = k.tag;
+        keys.push(key);
+    }
+    Ok(rebuild_curve(curve, keys))
+}
+
+/// Moves values of the keys, that were moved relative to the original curve, to the closest
+/// value of the given set.
+fn snap_moved_key_values(curve: &mut Curve, original: &Curve, values: &[f32]) {
+    let moved = curve
+        .keys()
+        .iter()
+        .map(|k| {
+            original
+                .keys()
+                .iter()
+                .find(|o| o.id == k.id)
+                .map_or(true, |o| o.location() != k.location() || o.value != k.value)
+        })
+        .collect::<Vec<_>>();
+
+    for (value, _) in curve.keys_values().zip(moved).filter(|(_, moved)| *moved) {
+        if let Some(closest) = values.iter().min_by(|a, b| {
+            (*a - *value)
+                .abs()
+                .partial_cmp(&(*b - *value).abs())
+                .unwrap_or(Ordering::Equal)
+        }) {
+            *value = *closest;
+        }
+    }
+}
+
+/// Clamps values of the keys, that were changed relative to the original curve, to the given
+/// limits. Keys that weren't touched are left as is, even if they are out of the limits.
+fn clamp_changed_key_values(curve: &mut Curve, original: &Curve, limits: ValueLimits) {
+    let changed = curve
+        .keys()
+        .iter()
+        .map(|k| {
+            original
+                .keys()
+                .iter()
+                .find(|o| o.id == k.id)
+                .map_or(true, |o| o.location() != k.location() || o.value != k.value)
+        })
+        .collect::<Vec<_>>();
+
+    for (value, _) in curve
+        .keys_values()
+        .zip(changed)
+        .filter(|(_, changed)| *changed)
+    {
+        *value = limits.clamp(*value);
+    }
+}
+
+/// Tells a user that keys of locked tracks were not changed and brings the curve editor back to
+/// the actual state of the curves.
+fn report_locked_edit(sender: &MessageSender) {
+    Log::warn("Keys of locked tracks cannot be edited!");
+    sender.send(Message::ForceSync);
+}
+
+/// Returns what happens when keys are moved (or added) to the time of another key of their curve.
+fn key_collision(editor_settings: &AnimationEditorSettings) -> KeyCollision {
+    if editor_settings.merge_aligned_keys {
+        KeyCollision::Merge
+    } else {
+        KeyCollision::Reject
+    }
+}
+
+/// Drops the curves of locked tracks from the curves to be edited, the user is told about the
+/// dropped curves.
+fn unlocked_curves<'a>(
+    curves: impl Iterator<Item = &'a Curve>,
+    locked_curves: &FxHashSet<Uuid>,
+    sender: &MessageSender,
+) -> Vec<&'a Curve> {
+    let (locked, unlocked): (Vec<_>, Vec<_>) =
+        curves.partition(|curve| locked_curves.contains(&curve.id()));
+    if !locked.is_empty() {
+        report_locked_edit(sender);
+    }
+    unlocked
+}
+
+/// A track copied into the track clipboard of the animation editor. Name of the target node is
+/// used to find a target for the pasted track, the clipboard could be pasted to another scene.
+struct CopiedTrack {
+    track: Track,
+    target_name: Option<String>,
+}
+
+/// Returns a target for a track pasted from the clipboard. The original target is kept if it
+/// still has the same name, otherwise the first node with the name in the hierarchy of the root
+/// is used, so editor-only nodes are never picked. Returns [`Handle::NONE`] if there's no such
+/// node.
+fn pasted_track_target(graph: &Graph, root: Handle<Node>, copied: &CopiedTrack) -> Handle<Node> {
+    let name = match copied.target_name {
+        Some(ref name) => name,
+        None => return Handle::NONE,
+    };
+
+    match graph.try_get(copied.track.target()) {
+        Some(node) if node.name() == name => copied.track.target(),
+        _ => graph
+            .find_by_name(root, name)
+            .map(|(handle, _)| handle)
+            .unwrap_or_default(),
+    }
+}
+
+/// A keyframe copied into the clipboard of the animation editor. Time of the key is relative
+/// to the earliest copied key.
+struct CopiedKeyframe {
+    curve: Uuid,
+    time: f32,
+    value: f32,
+    kind: CurveKeyKind,
+    tag: CurveKeyTag,
+}
+
+/// Returns `true` if the selected entity still exists in the animation.
+fn entity_exists(animation: &Animation, entity: &SelectedEntity) -> bool {
+    let mut curves = animation
+        .tracks()
+        .iter()
+        .flat_map(|t| t.data_container().curves_ref());
+    match entity {
+        SelectedEntity::Track(id) => animation.tracks().iter().any(|t| t.id() == *id),
+        SelectedEntity::Curve(id) => curves.any(|c| c.id() == *id),
+        SelectedEntity::Keyframe { curve, key } => {
+            curves.any(|c| c.id() == *curve && c.keys().iter().any(|k| k.id == *key))
+        }
+        SelectedEntity::Signal(id) => animation.signals().iter().any(|s| s.id == *id),
+        SelectedEntity::Marker(id) => animation.markers().iter().any(|m| m.id == *id),
+    }
+}
+
+/// Returns a list of moved keys, if the new curve differs from the old one only by locations and
+/// values of its keys.
+fn moved_keyframes(old: &Curve, new: &Curve) -> Option<Vec<MovedKeyframe>> {
+    if old.keys().len() != new.keys().len() {
+        return None;
+    }
+
+    let mut moved = Vec::new();
+    for new_key in new.keys() {
+        let old_key = old.keys().iter().find(|k| k.id == new_key.id)?;
+        if old_key.kind != new_key.kind {
+            return None;
+        }
+        if old_key.location() != new_key.location() || old_key.value != new_key.value {
+            moved.push(MovedKeyframe {
+                curve: new.id(),
+                key: new_key.id,
+                location: new_key.location(),
+                value: new_key.value,
+            });
+        }
+    }
+    Some(moved)
+}
+
+/// Creates a command that replaces old curves with new ones. Keys that were only moved (for
+/// example by dragging a group of keys) are stored in a single command.
+fn curve_edit_command(
+    selection: &AnimationSelection,
+    changed_curves: Vec<(&Curve, Curve)>,
+) -> Option<SceneCommand> {
+    let moved_keys = changed_curves
+        .iter()
+        .map(|(old, new)| moved_keyframes(old, new))
+        .collect::<Option<Vec<_>>>()
+        .map(|keys| keys.into_iter().flatten().collect::<Vec<_>>());
+    if let Some(keys) = moved_keys {
+        if keys.is_empty() {
+            None
+        } else {
+            Some(SceneCommand::new(MoveKeyframesCommand {
+                animation_player: selection.animation_player,
+                animation: selection.animation,
+                keys,
+            }))
+        }
+    } else {
+        let mut commands = changed_curves
+            .into_iter()
+            .map(|(_, curve)| {
+                SceneCommand::new(ReplaceTrackCurveCommand {
+                    animation_player: selection.animation_player,
+                    animation: selection.animation,
+                    curve,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        match commands.len() {
+            0 => None,
+            1 => commands.pop(),
+            _ => Some(SceneCommand::new(CommandGroup::from(commands))),
+        }
+    }
+}
+
+/// An edit of curves that is applied directly while it is in progress (a drag in the curve editor
+/// or a series of nudges) and turned into a single command when it ends.
+struct PendingCurveEdit {
+    // Selection at the moment when the edit has started.
+    selection: AnimationSelection,
+    // Curves as they were before the edit.
+    originals: FxHashMap<Uuid, Curve>,
+}
+
+impl PendingCurveEdit {
+    fn new(selection: &AnimationSelection) -> Self {
+        Self {
+            selection: selection.clone(),
+            originals: Default::default(),
+        }
+    }
+
+    /// Puts the original curves back and sends the changes as a single command, so the whole
+    /// edit could be done (and undone) at once.
+    fn commit(self, animation_player: &mut AnimationPlayer, sender: &MessageSender) {
+        if let Some(animation) = animation_player
+            .animations_mut()
+            .try_get_mut(self.selection.animation)
+        {
+            let mut changed_curves = Vec::new();
+            for curve in animation
+                .tracks_mut()
+                .iter_mut()
+                .flat_map(|t| t.data_container_mut().curves_mut().iter_mut())
+            {
+                if let Some(original) = self.originals.get(&curve.id()) {
+                    let edited = std::mem::replace(curve, original.clone());
+                    if edited != *original {
+                        changed_curves.push((original, edited));
+                    }
+                }
+            }
+
+            if let Some(command) = curve_edit_command(&self.selection, changed_curves) {
+                sender.send(Message::DoSceneCommand(command));
+            }
+        }
+    }
+
+    fn commit_to_scene(self, scene: &mut Scene, sender: &MessageSender) {
+        if let Some(animation_player) = scene
+            .graph
+            .try_get_mut_of_type::<AnimationPlayer>(self.selection.animation_player)
+        {
+            self.commit(animation_player, sender);
+        }
+    }
+}
+
+/// Returns location of the closest key after (or before) the given time, only keys of the
+/// curves that pass the filter are considered.
+fn neighbour_key_location<F>(
+    animation: &Animation,
+    time: f32,
+    forward: bool,
+    filter: F,
+) -> Option<f32>
+where
+    F: Fn(&Curve) -> bool,
+{
+    let locations = animation
+        .tracks()
+        .iter()
+        .flat_map(|t| t.data_container().curves_ref())
+        .filter(|c| filter(c))
+        .flat_map(|c| c.keys().iter().map(|k| k.location()));
+
+    if forward {
+        locations
+            .filter(|l| *l > time)
+            .min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+    } else {
+        locations
+            .filter(|l| *l < time)
+            .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+    }
+}
+
+/// Samples every enabled track of the animation at its current time.
+fn sample_pose(animation: &Animation) -> Vec<(Handle<Node>, BoundValueCollection)> {
+    animation
+        .tracks()
+        .iter()
+        .filter(|t| t.is_enabled())
+        .filter_map(|t| {
+            t.fetch(animation.time_position()).map(|value| {
+                (
+                    t.target(),
+                    BoundValueCollection {
+                        values: vec![value],
+                    },
+                )
+            })
+        })
+        .collect()
+}
+
 struct PreviewModeData {
     nodes: Vec<(Handle<Node>, Node)>,
 }
 
+impl PreviewModeData {
+    /// Returns the animation as it was before preview. Preview changes some of the settings of
+    /// the animation (such as looping or root motion), while the toolbar shows the saved ones.
+    fn saved_animation(
+        &self,
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+    ) -> Option<&Animation> {
+        self.nodes
+            .iter()
+            .rev()
+            .find(|(handle, _)| *handle == animation_player)
+            .and_then(|(_, node)| node.query_component_ref::<AnimationPlayer>())
+            .and_then(|animation_player| animation_player.animations().try_get(animation))
+    }
+}
+
 pub struct AnimationEditor {
     pub window: Handle<UiNode>,
     track_list: TrackList,
     curve_editor: Handle<UiNode>,
     toolbar: Toolbar,
+    overview: AnimationOverview,
+    diff: AnimationDiff,
+    // Animations of every animation player of the scene at the moment when the scene was loaded
+    // or saved last time, the diff view compares the selected animation with its saved state.
+    saved_animations: FxHashMap<(Handle<Node>, Handle<Animation>), Animation>,
+    // Reference audio clips of the animations of the scene. This is an editor-only state, it is
+    // not saved.
+    audio_references: FxHashMap<(Handle<Node>, Handle<Animation>), AudioReference>,
+    // Path of the clip and the sound node that plays it in preview mode.
+    audio_player: Option<(PathBuf, Handle<Node>)>,
+    // Animation and path of the clip, which waveform was sent to the ruler last time.
+    synced_audio: Option<((Handle<Node>, Handle<Animation>), Option<PathBuf>)>,
     content: Handle<UiNode>,
+    payload: Handle<UiNode>,
+    // Draggable separator between the track list and the curve editor.
+    splitter: Handle<UiNode>,
+    dragging_splitter: bool,
     ruler: Handle<UiNode>,
     preview_mode_data: Option<PreviewModeData>,
+    // Curve that is applied alone in preview mode, the rest of the animation stays at rest.
+    isolated_curve: Option<Uuid>,
     thumb: Handle<UiNode>,
+    minimap: Handle<UiNode>,
+    // View position of the curve editor, as it was reported last time.
+    view_position: Vector2<f32>,
+    clipboard: Vec<CopiedKeyframe>,
+    track_clipboard: Vec<CopiedTrack>,
+    // Entity that is isolated by a double click in the track list and the selection before the
+    // isolation, which is restored by the next double click on the entity.
+    isolation: Option<(SelectedEntity, Vec<SelectedEntity>)>,
+    // Frames every shown curve when the curves of the curve editor are synced next time.
+    zoom_to_fit_on_sync: bool,
+    // Loop range of the ruler, it is used only in preview mode.
+    loop_start: Option<f32>,
+    loop_end: Option<f32>,
+    // Length of the animation at the moment of last sync.
+    synced_length: Option<(Handle<Animation>, f32)>,
+    value_readout: Handle<UiNode>,
+    // Curve id, text widget and currently shown text of each line of the value readout.
+    value_readout_lines: Vec<(Uuid, Handle<UiNode>, String)>,
+    keyframe_inspector: KeyframeInspector,
+    // A drag in the curve editor, it ends when the mouse button is released, when the curve
+    // editor loses the mouse capture, or when another animation is selected.
+    curve_drag: Option<PendingCurveEdit>,
+    // Value nudges of a held arrow key, they end when the key is released, when the curve editor
+    // loses the focus, or when the selection changes.
+    key_nudge: Option<PendingCurveEdit>,
+    zoom_x: Handle<UiNode>,
+    zoom_y: Handle<UiNode>,
+    // Curves that were sent to the curve editor by the last sync, `None` if the curve editor
+    // could have changed them on its own.
+    synced_curves: Option<Vec<Curve>>,
+    // Time zoom that was sent to the ruler and the thumb last time.
+    time_zoom: Option<f32>,
+    // Curves that are pinned as read-only references, they are shown for every animation.
+    reference_curves: Vec<Curve>,
+    // Set when a drag tried to edit keys of a locked track.
+    locked_edit_rejected: bool,
+    // Set when a drag tried to snap two keys of a curve to the same frame.
+    snap_collision: Option<String>,
+    status_bar: Handle<UiNode>,
+    // Currently shown text of the status bar.
+    status_text: String,
+    // Values of the animated properties of the selected animation at the moment of last sync by
+    // track id. Auto key compares them with the values after scene commands.
+    auto_key_values: FxHashMap<Uuid, Vec<f32>>,
+    // Set when a scene command was issued while auto key was on.
+    auto_key_pending: bool,
+}
+
+/// Samples every selected curve at current time of the animation. Curves are clamped to their
+/// boundary values outside of their range.
+/// Name of a curve of a track, for example `Position.X`.
+fn curve_display_name(track: &Track, index: usize) -> String {
+    format!(
+        "{}.{}",
+        track.binding(),
+        curve_component_name(track.data_container().value_kind(), index)
+    )
+}
+
+/// Makes a text of the status bar: the time of the cursor and a short summary of the selection.
+fn status_bar_text(
+    animation: &Animation,
+    selection: &AnimationSelection,
+    time_format: TimeFormat,
+    fps: f32,
+) -> String {
+    let time = animation.time_position();
+    let mut parts = vec![match time_format {
+        TimeFormat::Seconds => format!("{:.3} s", time),
+        TimeFormat::Frames => format!("Frame {}", (time * fps).round()),
+    }];
+
+    let mut tracks = Vec::new();
+    let mut curves = Vec::new();
+    let mut keys = 0;
+    for entity in selection.entities.iter() {
+        match entity {
+            SelectedEntity::Track(id) => {
+                if let Some(track) = animation.tracks().iter().find(|t| t.id() == *id) {
+                    tracks.push(track.binding().to_string());
+                }
+            }
+            SelectedEntity::Curve(id) => {
+                if let Some(name) = animation.tracks().iter().find_map(|t| {
+                    t.data_container()
+                        .curves_ref()
+                        .iter()
+                        .position(|c| c.id() == *id)
+                        .map(|i| curve_display_name(t, i))
+                }) {
+                    curves.push(name);
+                }
+            }
+            SelectedEntity::Keyframe { .. } => keys += 1,
+            SelectedEntity::Signal(_) | SelectedEntity::Marker(_) => (),
+        }
+    }
+
+    if tracks.is_empty() && curves.is_empty() && keys == 0 {
+        parts.push("No selection".to_string());
+    } else {
+        match tracks.len() {
+            0 => (),
+            1 => parts.push(format!("Track: {}", tracks[0])),
+            n => parts.push(format!("{} tracks", n)),
+        }
+        match curves.len() {
+            0 => (),
+            1 => parts.push(format!("Curve: {}", curves[0])),
+            n => parts.push(format!("{} curves", n)),
+        }
+        match keys {
+            0 => (),
+            1 => parts.push("1 key".to_string()),
+            n => parts.push(format!("{} keys", n)),
+        }
+    }
+
+    parts.join(" | ")
+}
+
+fn value_readout_lines(
+    animation: &Animation,
+    selection: &AnimationSelection,
+) -> Vec<(Uuid, Color, String)> {
+    let time = animation.time_position();
+    selection
+        .curves()
+        .filter_map(|id| {
+            animation.tracks().iter().find_map(|t| {
+                let kind = t.data_container().value_kind();
+                t.data_container()
+                    .curves_ref()
+                    .iter()
+                    .enumerate()
+                    .find(|(_, c)| c.id() == id)
+                    .map(|(i, c)| {
+                        (
+                            c.id(),
+                            curve_color(kind, i),
+                            format!("{}: {:.3}", curve_display_name(t, i), c.value_at(time)),
+                        )
+                    })
+            })
+        })
+        .collect()
 }
 
+const SPLITTER_WIDTH: f32 = 4.0;
+const MIN_PANE_WIDTH: f32 = 100.0;
+const MIN_ZOOM: f32 = 0.001;
+const MAX_ZOOM: f32 = 1000.0;
+const RULER_HEIGHT: f32 = 22.0;
+/// Max distance (in frames) between the time entered in the "Go To Time" field and a key that
+/// is selected.
+const GO_TO_TIME_KEY_DISTANCE: f32 = 5.0;
+const COMPACT_RULER_HEIGHT: f32 = 6.0;
+const MINIMAP_HEIGHT: f32 = 24.0;
+
 fn fetch_selection(editor_selection: &Selection) -> AnimationSelection {
     if let Selection::Animation(ref selection) = editor_selection {
         // Some selection in an animation.
@@ -79,25 +669,70 @@ fn fetch_selection(editor_selection: &Selection) -> AnimationSelection {
     }
 }
 
+fn make_zoom_label(text: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    TextBuilder::new(
+        WidgetBuilder::new()
+            .with_margin(Thickness::left_right(2.0))
+            .with_vertical_alignment(VerticalAlignment::Center),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+fn make_zoom_field(tooltip: &str, ctx: &mut BuildContext) -> Handle<UiNode> {
+    NumericUpDownBuilder::<f32>::new(
+        WidgetBuilder::new()
+            .with_width(60.0)
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_min_value(MIN_ZOOM)
+    .with_max_value(MAX_ZOOM)
+    .with_precision(3)
+    .with_value(1.0)
+    .build(ctx)
+}
+
 impl AnimationEditor {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let curve_editor;
         let ruler;
         let thumb;
+        let minimap;
+        let value_readout;
+        let splitter;
+        let zoom_x;
+        let zoom_y;
 
         let track_list = TrackList::new(ctx);
         let toolbar = Toolbar::new(ctx);
+        let overview = AnimationOverview::new(ctx);
+        let diff = AnimationDiff::new(ctx);
+        let keyframe_inspector = KeyframeInspector::new(ctx);
 
         let payload = GridBuilder::new(
             WidgetBuilder::new()
-                .on_row(1)
+                .on_row(3)
                 .on_column(0)
                 .with_child(track_list.panel)
+                .with_child({
+                    splitter = BorderBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(0)
+                            .on_column(1)
+                            .with_background(BRUSH_LIGHT)
+                            .with_cursor(Some(CursorIcon::WResize))
+                            .with_tooltip(make_simple_tooltip(ctx, "Double Click To Reset")),
+                    )
+                    .with_stroke_thickness(Thickness::uniform(0.0))
+                    .build(ctx);
+                    splitter
+                })
                 .with_child(
                     BorderBuilder::new(
                         WidgetBuilder::new()
                             .on_row(0)
-                            .on_column(1)
+                            .on_column(2)
                             .with_child(
                                 GridBuilder::new(
                                     WidgetBuilder::new()
@@ -105,6 +740,8 @@ impl AnimationEditor {
                                             ruler =
                                                 RulerBuilder::new(WidgetBuilder::new().on_row(0))
                                                     .with_value(0.0)
+                                                    .with_frame_rate(toolbar.fps)
+                                                    .with_time_format(toolbar.time_format)
                                                     .build(ctx);
                                             ruler
                                         })
@@ -115,12 +752,53 @@ impl AnimationEditor {
                                                     .on_row(1),
                                             )
                                             .with_show_x_values(false)
+                                            .with_handle_delete_key(false)
+                                            .with_live_drag_sync(true)
+                                            .with_min_zoom(Vector2::repeat(MIN_ZOOM))
+                                            .with_max_zoom(Vector2::repeat(MAX_ZOOM))
                                             .build(ctx);
                                             curve_editor
-                                        }),
+                                        })
+                                        .with_child({
+                                            zoom_x = make_zoom_field("Time Axis Zoom", ctx);
+                                            zoom_y = make_zoom_field("Value Axis Zoom", ctx);
+                                            StackPanelBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .on_row(1)
+                                                    .with_margin(Thickness::uniform(4.0))
+                                                    .with_horizontal_alignment(
+                                                        HorizontalAlignment::Left,
+                                                    )
+                                                    .with_vertical_alignment(VerticalAlignment::Top)
+                                                    .with_child(make_zoom_label("X", ctx))
+                                                    .with_child(zoom_x)
+                                                    .with_child(make_zoom_label("Y", ctx))
+                                                    .with_child(zoom_y),
+                                            )
+                                            .with_orientation(Orientation::Horizontal)
+                                            .build(ctx)
+                                        })
+                                        .with_child({
+                                            value_readout = StackPanelBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .on_row(1)
+                                                    .with_margin(Thickness::uniform(4.0))
+                                                    .with_hit_test_visibility(false)
+                                                    .with_horizontal_alignment(
+                                                        HorizontalAlignment::Right,
+                                                    )
+                                                    .with_vertical_alignment(
+                                                        VerticalAlignment::Top,
+                                                    ),
+                                            )
+                                            .build(ctx);
+                                            value_readout
+                                        })
+                                        .with_child(keyframe_inspector.panel),
                                 )
-                                .add_row(Row::strict(22.0))
+                                .add_row(Row::strict(RULER_HEIGHT))
                                 .add_row(Row::stretch())
+                                .add_row(Row::auto())
                                 .add_column(Column::stretch())
                                 .build(ctx),
                             )
@@ -130,20 +808,47 @@ impl AnimationEditor {
                             }),
                     )
                     .build(ctx),
-                ),
+                )
+                .with_child({
+                    minimap = MinimapBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(1)
+                            .on_column(2)
+                            .with_margin(Thickness::top(1.0)),
+                    )
+                    .build(ctx);
+                    minimap
+                }),
         )
         .add_row(Row::stretch())
-        .add_column(Column::strict(250.0))
+        .add_row(Row::strict(MINIMAP_HEIGHT))
+        .add_column(Column::strict(DEFAULT_TRACK_LIST_WIDTH))
+        .add_column(Column::strict(SPLITTER_WIDTH))
         .add_column(Column::stretch())
         .build(ctx);
 
+        let status_bar = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_row(4)
+                .with_margin(Thickness::left_right(4.0))
+                .with_vertical_alignment(VerticalAlignment::Center),
+        )
+        .with_text("No selection")
+        .build(ctx);
+
         let content = GridBuilder::new(
             WidgetBuilder::new()
                 .with_child(toolbar.panel)
-                .with_child(payload),
+                .with_child(overview.panel)
+                .with_child(diff.panel)
+                .with_child(payload)
+                .with_child(status_bar),
         )
-        .add_row(Row::strict(26.0))
+        .add_row(Row::auto())
+        .add_row(Row::auto())
+        .add_row(Row::auto())
         .add_row(Row::stretch())
+        .add_row(Row::strict(20.0))
         .add_column(Column::stretch())
         .build(ctx);
 
@@ -158,91 +863,951 @@ impl AnimationEditor {
             track_list,
             curve_editor,
             toolbar,
+            overview,
+            diff,
+            saved_animations: Default::default(),
+            audio_references: Default::default(),
+            audio_player: None,
+            synced_audio: None,
             content,
+            payload,
+            splitter,
+            dragging_splitter: false,
+            curve_drag: None,
+            key_nudge: None,
             ruler,
             preview_mode_data: None,
+            isolated_curve: None,
+            clipboard: Default::default(),
+            track_clipboard: Default::default(),
+            isolation: None,
+            zoom_to_fit_on_sync: false,
+            loop_start: None,
+            loop_end: None,
+            synced_length: None,
+            synced_curves: None,
+            time_zoom: None,
+            reference_curves: Default::default(),
+            locked_edit_rejected: false,
+            snap_collision: None,
+            status_bar,
+            status_text: "No selection".to_string(),
+            auto_key_values: Default::default(),
+            auto_key_pending: false,
+            value_readout,
+            value_readout_lines: Default::default(),
+            keyframe_inspector,
             thumb,
+            minimap,
+            view_position: Default::default(),
+            zoom_x,
+            zoom_y,
         }
     }
 
-    pub fn open(&self, ui: &UserInterface) {
+    pub fn open(
+        &mut self,
+        ui: &mut UserInterface,
+        settings: &AnimationEditorWindowSettings,
+        editor_settings: &AnimationEditorSettings,
+    ) {
+        self.toolbar.sync_presets(editor_settings, ui);
+        self.toolbar.sync_key_interpolation(editor_settings, ui);
+        self.toolbar.sync_tangent_visibility(editor_settings, ui);
+        ui.send_message(CurveEditorMessage::show_tangents(
+            self.curve_editor,
+            MessageDirection::ToWidget,
+            editor_settings.show_tangent_handles,
+        ));
+        ui.send_message(CurveEditorMessage::show_value_grid(
+            self.curve_editor,
+            MessageDirection::ToWidget,
+            editor_settings.show_value_grid,
+        ));
+        ui.send_message(CurveEditorMessage::new_key_kind(
+            self.curve_editor,
+            MessageDirection::ToWidget,
+            editor_settings.new_key_interpolation.key_kind(),
+        ));
+
+        ui.send_message(WidgetMessage::width(
+            self.window,
+            MessageDirection::ToWidget,
+            settings.size.x,
+        ));
+        ui.send_message(WidgetMessage::height(
+            self.window,
+            MessageDirection::ToWidget,
+            settings.size.y,
+        ));
+        self.set_track_list_width(settings.track_list_width, ui);
+
         ui.send_message(WindowMessage::open(
             self.window,
             MessageDirection::ToWidget,
-            true,
+            settings.position.is_none(),
+        ));
+        if let Some(position) = settings.position {
+            ui.send_message(WindowMessage::move_to(
+                self.window,
+                MessageDirection::ToWidget,
+                position,
+            ));
+        }
+    }
+
+    fn set_track_list_width(&self, width: f32, ui: &UserInterface) {
+        let payload = ui.node(self.payload);
+        // Keep both panes visible. Payload has zero size until the window was shown at least once.
+        let payload_width = payload.actual_local_size().x;
+        let max_width = if payload_width > 0.0 {
+            (payload_width - SPLITTER_WIDTH - MIN_PANE_WIDTH).max(MIN_PANE_WIDTH)
+        } else {
+            f32::MAX
+        };
+        if let Some(grid) = payload.query_component::<Grid>() {
+            grid.columns.borrow_mut()[0] = Column::strict(width.clamp(MIN_PANE_WIDTH, max_width));
+            payload.invalidate_layout();
+        }
+    }
+
+    /// Adds the delta to values of the selected keys. Values of rotation tracks are nudged in
+    /// degrees when they are shown as Euler angles.
+    fn nudge_selected_keys(
+        &mut self,
+        animation: &mut Animation,
+        selection: &AnimationSelection,
+        delta: f32,
+        sender: &MessageSender,
+    ) {
+        let mut locked_edit = false;
+        let mut nudged = false;
+
+        for entity in selection.entities.iter() {
+            if let SelectedEntity::Keyframe { curve, key } = entity {
+                if self.track_list.is_curve_locked(animation, *curve) {
+                    locked_edit = true;
+                    continue;
+                }
+
+                let is_rotation = animation
+                    .tracks()
+                    .iter()
+                    .flat_map(|t| t.data_container().curves_ref())
+                    .find(|c| c.id() == *curve)
+                    .map_or(false, |c| euler::is_rotation_curve(animation, c));
+                let delta = if is_rotation && self.toolbar.show_euler_angles {
+                    delta.to_radians()
+                } else {
+                    delta
+                };
+                let limits = self.track_list.curve_value_limits(animation, *curve);
+
+                if let Some(existing) = animation
+                    .tracks_mut()
+                    .iter_mut()
+                    .flat_map(|t| t.data_container_mut().curves_mut().iter_mut())
+                    .find(|c| c.id() == *curve)
+                {
+                    let nudge = self
+                        .key_nudge
+                        .get_or_insert_with(|| PendingCurveEdit::new(selection));
+                    nudge
+                        .originals
+                        .entry(existing.id())
+                        .or_insert_with(|| existing.clone());
+
+                    let mut keys = existing.keys().to_vec();
+                    if let Some(key) = keys.iter_mut().find(|k| k.id == *key) {
+                        key.value += delta;
+                        if let Some(limits) = limits {
+                            key.value = limits.clamp(key.value);
+                        }
+                        nudged = true;
+                    }
+                    *existing = rebuild_curve(existing, keys);
+                }
+            }
+        }
+
+        if locked_edit {
+            report_locked_edit(sender);
+        } else if nudged {
+            sender.send(Message::ForceSync);
+        }
+    }
+
+    fn set_ruler_compact(&self, compact: bool, ui: &UserInterface) {
+        ui.send_message(RulerMessage::compact(
+            self.ruler,
+            MessageDirection::ToWidget,
+            compact,
         ));
+
+        // The ruler is the first row of the grid that also holds the curve editor.
+        let grid = ui.node(ui.node(self.ruler).parent());
+        if let Some(grid_ref) = grid.query_component::<Grid>() {
+            grid_ref.rows.borrow_mut()[0] = Row::strict(if compact {
+                COMPACT_RULER_HEIGHT
+            } else {
+                RULER_HEIGHT
+            });
+            grid.invalidate_layout();
+        }
+    }
+
+    /// Writes current layout of the window to the settings. It does nothing if the window is
+    /// closed, because the layout of a closed window is not up to date.
+    pub fn store_layout(&self, ui: &UserInterface, settings: &mut AnimationEditorWindowSettings) {
+        let window = ui.node(self.window);
+        if !window.visibility() {
+            return;
+        }
+
+        settings.position = Some(window.desired_local_position());
+        settings.size = window.actual_local_size();
+        if let Some(grid) = ui.node(self.payload).query_component::<Grid>() {
+            settings.track_list_width = grid.columns.borrow()[0].desired_size;
+        }
     }
 
     pub fn handle_ui_message(
         &mut self,
         message: &UiMessage,
-        editor_scene: Option<&mut EditorScene>,
+        mut editor_scene: Option<&mut EditorScene>,
         engine: &mut Engine,
         sender: &MessageSender,
+        settings: &mut AnimationEditorWindowSettings,
+        editor_settings: &mut AnimationEditorSettings,
+        key_bindings: &KeyBindings,
     ) {
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            if message.destination() == self.splitter {
+                match msg {
+                    WidgetMessage::MouseDown { .. } => {
+                        self.dragging_splitter = true;
+                        engine.user_interface.capture_mouse(self.splitter);
+                    }
+                    WidgetMessage::MouseUp { .. } => {
+                        if self.dragging_splitter {
+                            self.dragging_splitter = false;
+                            engine.user_interface.release_mouse_capture();
+                        }
+                    }
+                    WidgetMessage::MouseMove { pos, .. } => {
+                        if self.dragging_splitter {
+                            let ui = &engine.user_interface;
+                            let payload_x = ui.node(self.payload).screen_position().x;
+                            self.set_track_list_width(pos.x - payload_x - SPLITTER_WIDTH * 0.5, ui);
+                        }
+                    }
+                    WidgetMessage::DoubleClick { .. } => {
+                        self.set_track_list_width(DEFAULT_TRACK_LIST_WIDTH, &engine.user_interface);
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        if let Some(MinimapMessage::ViewPosition(position)) = message.data() {
+            if message.destination() == self.minimap
+                && message.direction() == MessageDirection::FromWidget
+            {
+                // The curve editor reports the new position back, the ruler and the thumb are
+                // synced with it using that report.
+                engine
+                    .user_interface
+                    .send_message(CurveEditorMessage::view_position(
+                        self.curve_editor,
+                        MessageDirection::ToWidget,
+                        Vector2::new(*position, self.view_position.y),
+                    ));
+            }
+        }
+
+        if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window
+                && message.direction() == MessageDirection::ToWidget
+            {
+                self.store_layout(&engine.user_interface, settings);
+
+                // The scene must not keep the previewed pose when the editor is closed.
+                if let Some(editor_scene) = editor_scene.as_deref_mut() {
+                    let scene = &mut engine.scenes[editor_scene.scene];
+                    for edit in [self.curve_drag.take(), self.key_nudge.take()]
+                        .into_iter()
+                        .flatten()
+                    {
+                        edit.commit_to_scene(scene, sender);
+                    }
+                    self.try_leave_preview_mode(editor_scene, engine);
+                }
+            }
+        } else if let Some(NumericUpDownMessage::<f32>::Value(_)) = message.data() {
+            if (message.destination() == self.zoom_x || message.destination() == self.zoom_y)
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let ui = &engine.user_interface;
+                let zoom = |field: Handle<UiNode>| {
+                    ui.node(field)
+                        .query_component::<NumericUpDown<f32>>()
+                        .unwrap()
+                        .value
+                        .clamp(MIN_ZOOM, MAX_ZOOM)
+                };
+
+                // The curve editor reports the new zoom back, the ruler and the thumb are synced
+                // with it using that report.
+                ui.send_message(CurveEditorMessage::zoom(
+                    self.curve_editor,
+                    MessageDirection::ToWidget,
+                    Vector2::new(zoom(self.zoom_x), zoom(self.zoom_y)),
+                ));
+            }
+        }
+
         if let Some(editor_scene) = editor_scene {
             let selection = fetch_selection(&editor_scene.selection);
 
             let scene = &mut engine.scenes[editor_scene.scene];
 
+            // An edit in progress ends when its selection is gone, or when the mouse button (or
+            // the arrow key) release that ends it will never come to the curve editor.
+            let destination = message.destination();
+            let in_curve_editor = destination == self.curve_editor
+                || engine
+                    .user_interface
+                    .is_node_child_of(destination, self.curve_editor);
+            let drag_interrupted = self.curve_drag.as_ref().map_or(false, |drag| {
+                drag.selection.animation_player != selection.animation_player
+                    || drag.selection.animation != selection.animation
+                    || (!in_curve_editor
+                        && matches!(
+                            message.data(),
+                            Some(WidgetMessage::MouseDown { .. } | WidgetMessage::MouseUp { .. })
+                        ))
+            });
+            if drag_interrupted {
+                if let Some(drag) = self.curve_drag.take() {
+                    drag.commit_to_scene(scene, sender);
+                }
+            }
+            let nudge_interrupted = self.key_nudge.as_ref().map_or(false, |nudge| {
+                nudge.selection != selection
+                    || (in_curve_editor && matches!(message.data(), Some(WidgetMessage::Unfocus)))
+            });
+            if nudge_interrupted {
+                if let Some(nudge) = self.key_nudge.take() {
+                    nudge.commit_to_scene(scene, sender);
+                }
+            }
+
             if let Some(animation_player) = scene
                 .graph
                 .try_get_of_type::<AnimationPlayer>(selection.animation_player)
             {
-                let toolbar_action = self.toolbar.handle_ui_message(
+                let locked_curves = animation_player
+                    .animations()
+                    .try_get(selection.animation)
+                    .map(|animation| self.track_list.locked_curves(animation))
+                    .unwrap_or_default();
+
+                let mut toolbar_action = self.toolbar.handle_ui_message(
                     message,
                     sender,
                     scene,
                     &mut engine.user_interface,
                     selection.animation_player,
                     animation_player,
+                    self.preview_mode_data.as_ref().and_then(|d| {
+                        d.saved_animation(selection.animation_player, selection.animation)
+                    }),
                     editor_scene,
                     &selection,
+                    &locked_curves,
+                    editor_settings,
                 );
 
+                if let Some(entity) = self.diff.handle_ui_message(message) {
+                    sender.do_scene_command(ChangeSelectionCommand::new(
+                        Selection::Animation(AnimationSelection {
+                            animation_player: selection.animation_player,
+                            animation: selection.animation,
+                            entities: vec![entity],
+                        }),
+                        editor_scene.selection.clone(),
+                    ));
+                    self.zoom_to_fit_on_sync = true;
+                }
+
+                if let Some(animation) = self.overview.handle_ui_message(message) {
+                    sender.do_scene_command(ChangeSelectionCommand::new(
+                        Selection::Animation(AnimationSelection {
+                            animation_player: selection.animation_player,
+                            animation,
+                            entities: vec![],
+                        }),
+                        editor_scene.selection.clone(),
+                    ));
+                    toolbar_action = ToolbarAction::SelectAnimation(animation);
+                }
+
                 let animation_player = scene
                     .graph
                     .try_get_mut_of_type::<AnimationPlayer>(selection.animation_player)
                     .unwrap();
 
-                if let Some(msg) = message.data::<CurveEditorMessage>() {
-                    if message.destination() == self.curve_editor
-                        && message.direction() == MessageDirection::FromWidget
+                if let Some(WidgetMessage::KeyDown(key)) = message.data() {
+                    let ui = &engine.user_interface;
+                    let destination = message.destination();
+                    // Shortcuts work only when the editor has keyboard focus and a user isn't
+                    // typing something.
+                    if (destination == self.window || ui.is_node_child_of(destination, self.window))
+                        && ui.node(destination).query_component::<TextBox>().is_none()
                     {
-                        let ui = &engine.user_interface;
-                        match msg {
-                            CurveEditorMessage::Sync(curve) => {
-                                sender.do_scene_command(ReplaceTrackCurveCommand {
-                                    animation_player: selection.animation_player,
-                                    animation: selection.animation,
-                                    curve: curve.clone(),
+                        // Undo and redo are handled here, because widgets of the editor could
+                        // mark key presses as handled and the main window would ignore them.
+                        let hot_key = HotKey::Some {
+                            code: *key,
+                            modifiers: ui.keyboard_modifiers(),
+                        };
+                        if hot_key == key_bindings.undo || hot_key == key_bindings.redo {
+                            // A drag in the curve editor or a nudge of keys becomes a single
+                            // command when it ends, it must not be undone in the middle.
+                            if self.curve_drag.is_none() && self.key_nudge.is_none() {
+                                sender.send(if hot_key == key_bindings.undo {
+                                    Message::UndoSceneCommand
+                                } else {
+                                    Message::RedoSceneCommand
                                 });
                             }
-                            CurveEditorMessage::ViewPosition(position) => {
-                                ui.send_message(RulerMessage::view_position(
-                                    self.ruler,
-                                    MessageDirection::ToWidget,
-                                    position.x,
-                                ));
-                                ui.send_message(ThumbMessage::view_position(
-                                    self.thumb,
-                                    MessageDirection::ToWidget,
-                                    position.x,
-                                ));
-                            }
-                            CurveEditorMessage::Zoom(zoom) => {
-                                ui.send_message(RulerMessage::zoom(
-                                    self.ruler,
-                                    MessageDirection::ToWidget,
-                                    zoom.x,
-                                ));
-                                ui.send_message(ThumbMessage::zoom(
-                                    self.thumb,
-                                    MessageDirection::ToWidget,
-                                    zoom.x,
-                                ))
-                            }
-                            _ => (),
+                            // The command must not be undone once again by the main window.
+                            message.set_handled(true);
+                        }
+
+                        match key {
+                            KeyCode::Space => toolbar_action = ToolbarAction::PlayPause,
+                            KeyCode::Home => toolbar_action = ToolbarAction::ResetView,
+                            KeyCode::Comma => toolbar_action = ToolbarAction::PreviousKey,
+                            KeyCode::Period => toolbar_action = ToolbarAction::NextKey,
+                            KeyCode::Up | KeyCode::Down
+                                if destination == self.curve_editor
+                                    || ui.is_node_child_of(destination, self.curve_editor) =>
+                            {
+                                if let Some(animation) = animation_player
+                                    .animations_mut()
+                                    .try_get_mut(selection.animation)
+                                {
+                                    let step = if ui.keyboard_modifiers().shift {
+                                        editor_settings.key_nudge_step * 10.0
+                                    } else {
+                                        editor_settings.key_nudge_step
+                                    };
+                                    let delta = if *key == KeyCode::Up { step } else { -step };
+                                    self.nudge_selected_keys(animation, &selection, delta, sender);
+                                }
+                            }
+                            KeyCode::Left | KeyCode::Right => {
+                                if let Some(animation) = animation_player
+                                    .animations_mut()
+                                    .try_get_mut(selection.animation)
+                                {
+                                    let forward = *key == KeyCode::Right;
+                                    let time = animation.time_position();
+                                    let new_time = if ui.keyboard_modifiers().shift {
+                                        neighbour_key_location(animation, time, forward, |c| {
+                                            self.is_curve_shown(&selection, c)
+                                        })
+                                        .unwrap_or(time)
+                                    } else if forward {
+                                        time + 1.0 / self.toolbar.fps
+                                    } else {
+                                        time - 1.0 / self.toolbar.fps
+                                    };
+
+                                    self.move_time_cursor(animation, new_time, ui);
+                                }
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+
+                if let Some(WidgetMessage::KeyUp(KeyCode::Up | KeyCode::Down)) = message.data() {
+                    if let Some(nudge) = self.key_nudge.take() {
+                        nudge.commit(animation_player, sender);
+                    }
+                }
+
+                if let Some(animation) = animation_player.animations().try_get(selection.animation)
+                {
+                    self.keyframe_inspector.handle_ui_message(
+                        message,
+                        sender,
+                        &engine.user_interface,
+                        animation,
+                        &selection,
+                        &locked_curves,
+                    );
+                }
+
+                if let Some(msg) = message.data::<CurveEditorMessage>() {
+                    if message.destination() == self.curve_editor
+                        && message.direction() == MessageDirection::FromWidget
+                    {
+                        let ui = &engine.user_interface;
+                        match msg {
+                            CurveEditorMessage::Sync(curves) => {
+                                // The editor has its own copy of the curves now, it must be
+                                // synced again even if the model will stay the same.
+                                self.synced_curves = None;
+
+                                // Curves of locked tracks are only shown, their edits are
+                                // discarded.
+                                let mut locked_edit = false;
+                                let curves = match animation_player
+                                    .animations()
+                                    .try_get(selection.animation)
+                                {
+                                    Some(animation) => curves
+                                        .iter()
+                                        .map(|curve| {
+                                            let mut curve = if self.toolbar.show_euler_angles {
+                                                euler::from_euler_view(animation, curve)
+                                            } else {
+                                                curve.clone()
+                                            };
+                                            if let (Some(limits), Some(original)) = (
+                                                self.track_list
+                                                    .curve_value_limits(animation, curve.id()),
+                                                animation
+                                                    .tracks()
+                                                    .iter()
+                                                    .flat_map(|t| t.data_container().curves_ref())
+                                                    .find(|c| c.id() == curve.id()),
+                                            ) {
+                                                clamp_changed_key_values(
+                                                    &mut curve, original, limits,
+                                                );
+                                            }
+                                            curve
+                                        })
+                                        .filter(|curve| {
+                                            if self
+                                                .track_list
+                                                .is_curve_locked(animation, curve.id())
+                                            {
+                                                locked_edit |= animation
+                                                    .tracks()
+                                                    .iter()
+                                                    .flat_map(|t| t.data_container().curves_ref())
+                                                    .any(|c| c.id() == curve.id() && c != curve);
+                                                false
+                                            } else {
+                                                true
+                                            }
+                                        })
+                                        .collect::<Vec<_>>(),
+                                    None => Vec::new(),
+                                };
+                                if locked_edit {
+                                    if self.curve_drag.is_some() {
+                                        // Reported once, when the drag ends.
+                                        self.locked_edit_rejected = true;
+                                    } else {
+                                        report_locked_edit(sender);
+                                    }
+                                }
+
+                                // Snapped keys of a curve could end up at the same frame, such
+                                // curves are merged or their edits are discarded.
+                                let curves = match (
+                                    self.toolbar.snap_step(),
+                                    animation_player.animations().try_get(selection.animation),
+                                ) {
+                                    (Some(step), Some(animation)) => {
+                                        let collision = key_collision(editor_settings);
+                                        let dragging = self.curve_drag.is_some();
+                                        let snap_collision = &mut self.snap_collision;
+                                        curves
+                                            .into_iter()
+                                            .filter_map(|curve| {
+                                                let original = animation
+                                                    .tracks()
+                                                    .iter()
+                                                    .flat_map(|t| t.data_container().curves_ref())
+                                                    .find(|c| c.id() == curve.id())?;
+                                                match snap_curve_keys(
+                                                    &curve, original, step, collision,
+                                                ) {
+                                                    Ok(curve) => Some(curve),
+                                                    Err(err) => {
+                                                        if dragging {
+                                                            // Reported once, when the drag ends.
+                                                            *snap_collision = Some(err);
+                                                        } else {
+                                                            Log::warn(err);
+                                                        }
+                                                        None
+                                                    }
+                                                }
+                                            })
+                                            .collect()
+                                    }
+                                    _ => curves,
+                                }
+                                .into_iter();
+
+                                if let Some(drag) = self.curve_drag.as_mut() {
+                                    // Ctrl snaps values of the dragged keys to the "nice" ones.
+                                    let snap_values = ui.keyboard_modifiers().control;
+
+                                    // The drag is still in progress, apply the changes directly
+                                    // and remember the curves as they were before the drag.
+                                    if let Some(animation) = animation_player
+                                        .animations_mut()
+                                        .try_get_mut(selection.animation)
+                                    {
+                                        for mut curve in curves {
+                                            if let Some(existing) = animation
+                                                .tracks_mut()
+                                                .iter_mut()
+                                                .flat_map(|t| {
+                                                    t.data_container_mut().curves_mut().iter_mut()
+                                                })
+                                                .find(|c| c.id() == curve.id())
+                                            {
+                                                let original = drag
+                                                    .originals
+                                                    .entry(curve.id())
+                                                    .or_insert_with(|| existing.clone());
+                                                if snap_values {
+                                                    snap_moved_key_values(
+                                                        &mut curve,
+                                                        original,
+                                                        &editor_settings.value_snap_set,
+                                                    );
+                                                }
+                                                *existing = curve;
+                                            }
+                                        }
+                                    }
+                                } else if let Some(animation) =
+                                    animation_player.animations().try_get(selection.animation)
+                                {
+                                    // Each curve is routed to its track by its id. Curves that
+                                    // were removed from the animation (for example by undo) are
+                                    // ignored, as well as the ones that weren't changed.
+                                    let changed_curves = curves
+                                        .filter_map(|curve| {
+                                            animation
+                                                .tracks()
+                                                .iter()
+                                                .flat_map(|t| t.data_container().curves_ref())
+                                                .find(|c| c.id() == curve.id() && *c != &curve)
+                                                .map(|old| (old, curve))
+                                        })
+                                        .collect::<Vec<_>>();
+
+                                    if let Some(command) =
+                                        curve_edit_command(&selection, changed_curves)
+                                    {
+                                        sender.send(Message::DoSceneCommand(command));
+                                    }
+                                }
+                            }
+                            CurveEditorMessage::DragStarted => {
+                                self.curve_drag = Some(PendingCurveEdit::new(&selection));
+                            }
+                            CurveEditorMessage::DragEnded => {
+                                if let Some(err) = self.snap_collision.take() {
+                                    Log::warn(err);
+                                }
+                                if std::mem::take(&mut self.locked_edit_rejected) {
+                                    report_locked_edit(sender);
+                                }
+                                if let Some(drag) = self.curve_drag.take() {
+                                    drag.commit(animation_player, sender);
+                                }
+                            }
+                            CurveEditorMessage::SelectionChanged(keys) => {
+                                if let Some(animation) =
+                                    animation_player.animations().try_get(selection.animation)
+                                {
+                                    let mut entities = selection
+                                        .entities
+                                        .iter()
+                                        .filter(|e| !matches!(e, SelectedEntity::Keyframe { .. }))
+                                        .cloned()
+                                        .collect::<Vec<_>>();
+
+                                    for key in keys {
+                                        if let Some(curve) = animation
+                                            .tracks()
+                                            .iter()
+                                            .flat_map(|t| t.data_container().curves_ref())
+                                            .find(|c| c.keys().iter().any(|k| k.id == *key))
+                                        {
+                                            entities.push(SelectedEntity::Keyframe {
+                                                curve: curve.id(),
+                                                key: *key,
+                                            });
+                                        }
+                                    }
+
+                                    if entities != selection.entities {
+                                        sender.do_scene_command(ChangeSelectionCommand::new(
+                                            Selection::Animation(AnimationSelection {
+                                                animation_player: selection.animation_player,
+                                                animation: selection.animation,
+                                                entities,
+                                            }),
+                                            editor_scene.selection.clone(),
+                                        ));
+                                    }
+                                }
+                            }
+                            CurveEditorMessage::ViewPosition(position) => {
+                                self.view_position = *position;
+                                ui.send_message(MinimapMessage::view_position(
+                                    self.minimap,
+                                    MessageDirection::ToWidget,
+                                    position.x,
+                                ));
+                                ui.send_message(RulerMessage::view_position(
+                                    self.ruler,
+                                    MessageDirection::ToWidget,
+                                    position.x,
+                                ));
+                                ui.send_message(ThumbMessage::view_position(
+                                    self.thumb,
+                                    MessageDirection::ToWidget,
+                                    position.x,
+                                ));
+                            }
+                            CurveEditorMessage::Zoom(zoom) => {
+                                // Value axis could be zoomed alone (Alt+Wheel), the ruler shows
+                                // only time so it is left untouched in this case.
+                                if self.time_zoom != Some(zoom.x) {
+                                    self.time_zoom = Some(zoom.x);
+                                    ui.send_message(RulerMessage::zoom(
+                                        self.ruler,
+                                        MessageDirection::ToWidget,
+                                        zoom.x,
+                                    ));
+                                    ui.send_message(ThumbMessage::zoom(
+                                        self.thumb,
+                                        MessageDirection::ToWidget,
+                                        zoom.x,
+                                    ));
+                                    ui.send_message(MinimapMessage::zoom(
+                                        self.minimap,
+                                        MessageDirection::ToWidget,
+                                        zoom.x,
+                                    ));
+                                }
+                                for (field, value) in [(self.zoom_x, zoom.x), (self.zoom_y, zoom.y)]
+                                {
+                                    send_sync_message(
+                                        ui,
+                                        NumericUpDownMessage::value(
+                                            field,
+                                            MessageDirection::ToWidget,
+                                            value,
+                                        ),
+                                    );
+                                }
+                            }
+                            _ => (),
+                        }
+                    }
+                } else if let Some(WidgetMessage::KeyUp(KeyCode::Delete)) = message.data() {
+                    if message.destination() == self.curve_editor {
+                        let mut keys = selection
+                            .entities
+                            .iter()
+                            .filter_map(|e| {
+                                if let SelectedEntity::Keyframe { curve, key } = e {
+                                    Some((*curve, *key))
+                                } else {
+                                    None
+                                }
+                            })
+                            .collect::<Vec<_>>();
+
+                        if let Some(animation) =
+                            animation_player.animations().try_get(selection.animation)
+                        {
+                            let count = keys.len();
+                            keys.retain(|(curve, _)| {
+                                !self.track_list.is_curve_locked(animation, *curve)
+                            });
+                            if keys.len() != count {
+                                report_locked_edit(sender);
+                            }
+                        }
+
+                        if !keys.is_empty() {
+                            let group = vec![
+                                SceneCommand::new(ChangeSelectionCommand::new(
+                                    Selection::Animation(AnimationSelection {
+                                        animation_player: selection.animation_player,
+                                        animation: selection.animation,
+                                        entities: selection
+                                            .entities
+                                            .iter()
+                                            .filter(|e| {
+                                                !matches!(e, SelectedEntity::Keyframe { .. })
+                                            })
+                                            .cloned()
+                                            .collect(),
+                                    }),
+                                    editor_scene.selection.clone(),
+                                )),
+                                SceneCommand::new(RemoveKeyframesCommand::new(
+                                    selection.animation_player,
+                                    selection.animation,
+                                    keys,
+                                )),
+                            ];
+
+                            sender.do_scene_command(CommandGroup::from(group));
+                        }
+                    }
+                } else if let Some(WidgetMessage::KeyDown(key)) = message.data() {
+                    if message.destination() == self.curve_editor {
+                        let control = engine.user_interface.keyboard_modifiers().control;
+                        if let Some(animation) =
+                            animation_player.animations().try_get(selection.animation)
+                        {
+                            match key {
+                                KeyCode::H if !control => {
+                                    editor_settings.show_tangent_handles =
+                                        !editor_settings.show_tangent_handles;
+                                    self.toolbar.sync_tangent_visibility(
+                                        editor_settings,
+                                        &engine.user_interface,
+                                    );
+                                    engine.user_interface.send_message(
+                                        CurveEditorMessage::show_tangents(
+                                            self.curve_editor,
+                                            MessageDirection::ToWidget,
+                                            editor_settings.show_tangent_handles,
+                                        ),
+                                    );
+                                }
+                                KeyCode::F if !control => {
+                                    self.zoom_to_selection(
+                                        &selection,
+                                        animation,
+                                        &engine.user_interface,
+                                    );
+                                }
+                                KeyCode::A if control => {
+                                    if let Some(new_selection) =
+                                        select_all_track_keys(animation, &selection)
+                                    {
+                                        sender.do_scene_command(ChangeSelectionCommand::new(
+                                            Selection::Animation(new_selection),
+                                            editor_scene.selection.clone(),
+                                        ));
+                                    }
+                                }
+                                KeyCode::C if control => {
+                                    let mut copied = selection
+                                        .entities
+                                        .iter()
+                                        .filter_map(|e| {
+                                            if let SelectedEntity::Keyframe { curve, key } = e {
+                                                animation
+                                                    .tracks()
+                                                    .iter()
+                                                    .flat_map(|t| t.data_container().curves_ref())
+                                                    .find(|c| c.id() == *curve)
+                                                    .and_then(|c| {
+                                                        c.keys().iter().find(|k| k.id == *key)
+                                                    })
+                                                    .map(|k| CopiedKeyframe {
+                                                        curve: *curve,
+                                                        time: k.location(),
+                                                        value: k.value,
+                                                        kind: k.kind.clone(),
+                                                        tag: k.tag,
+                                                    })
+                                            } else {
+                                                None
+                                            }
+                                        })
+                                        .collect::<Vec<_>>();
+
+                                    if !copied.is_empty() {
+                                        let earliest =
+                                            copied.iter().map(|k| k.time).fold(f32::MAX, f32::min);
+                                        for key in copied.iter_mut() {
+                                            key.time -= earliest;
+                                        }
+                                        self.clipboard = copied;
+                                    }
+                                }
+                                KeyCode::V if control => {
+                                    let mut sources = Vec::<Uuid>::new();
+                                    for key in self.clipboard.iter() {
+                                        if !sources.contains(&key.curve) {
+                                            sources.push(key.curve);
+                                        }
+                                    }
+
+                                    // Keys of a single curve are pasted on the first selected
+                                    // curve, or, if there's no such, on the curve they were
+                                    // copied from. Keys of many curves are pasted on the curves
+                                    // they were copied from.
+                                    let selected_target = selection.curves().next();
+
+                                    let time = animation.time_position();
+                                    let mut locked_edit = false;
+                                    let commands = sources
+                                        .iter()
+                                        .filter_map(|source| {
+                                            let target = match selected_target {
+                                                Some(target) if sources.len() == 1 => target,
+                                                _ => *source,
+                                            };
+                                            if self.track_list.is_curve_locked(animation, target) {
+                                                locked_edit = true;
+                                                return None;
+                                            }
+                                            let keys = self
+                                                .clipboard
+                                                .iter()
+                                                .filter(|k| k.curve == *source)
+                                                .map(|k| {
+                                                    let mut key = CurveKey::new(
+                                                        time + k.time,
+                                                        k.value,
+                                                        k.kind.clone(),
+                                                    );
+                                                    key.tag = k.tag;
+                                                    key
+                                                })
+                                                .collect::<Vec<_>>();
+                                            Some(SceneCommand::new(PasteKeyframesCommand::new(
+                                                selection.animation_player,
+                                                selection.animation,
+                                                target,
+                                                keys,
+                                            )))
+                                        })
+                                        .collect::<Vec<_>>();
+
+                                    if locked_edit {
+                                        report_locked_edit(sender);
+                                    }
+                                    if !commands.is_empty() {
+                                        sender.do_scene_command(CommandGroup::from(commands));
+                                    }
+                                }
+                                _ => (),
+                            }
                         }
                     }
                 } else if let Some(msg) = message.data::<RulerMessage>() {
@@ -262,6 +1827,16 @@ impl AnimationEditor {
                                     animation.set_time_position(*value);
                                 }
                             }
+                            RulerMessage::CursorMoved(time) => {
+                                // Scrubbing moves the thumb of the curve editor and the paused
+                                // preview pose together with the cursor.
+                                if let Some(animation) = animation_player
+                                    .animations_mut()
+                                    .try_get_mut(selection.animation)
+                                {
+                                    self.move_time_cursor(animation, *time, &engine.user_interface);
+                                }
+                            }
                             RulerMessage::AddSignal(time) => {
                                 sender.do_scene_command(AddAnimationSignal {
                                     animation_player_handle: selection.animation_player,
@@ -308,6 +1883,53 @@ impl AnimationEditor {
                                     editor_scene.selection.clone(),
                                 ));
                             }
+                            RulerMessage::AddMarker { time, name } => {
+                                sender.do_scene_command(AddAnimationMarker {
+                                    animation_player_handle: selection.animation_player,
+                                    animation_handle: selection.animation,
+                                    marker: Some(AnimationMarker::new(Uuid::new_v4(), name, *time)),
+                                });
+                            }
+                            RulerMessage::RemoveMarker(id) => {
+                                if let Some(animation) =
+                                    animation_player.animations().try_get(selection.animation)
+                                {
+                                    if let Some(marker_index) =
+                                        animation.markers().iter().position(|m| m.id == *id)
+                                    {
+                                        sender.do_scene_command(RemoveAnimationMarker {
+                                            animation_player_handle: selection.animation_player,
+                                            animation_handle: selection.animation,
+                                            marker_index,
+                                            marker: None,
+                                        })
+                                    }
+                                }
+                            }
+                            RulerMessage::MoveMarker { id, new_position } => {
+                                sender.do_scene_command(MoveAnimationMarker {
+                                    animation_player_handle: selection.animation_player,
+                                    animation_handle: selection.animation,
+                                    marker: *id,
+                                    time: *new_position,
+                                });
+                            }
+                            RulerMessage::SelectMarker(id) => {
+                                sender.do_scene_command(ChangeSelectionCommand::new(
+                                    Selection::Animation(AnimationSelection {
+                                        animation_player: selection.animation_player,
+                                        animation: selection.animation,
+                                        entities: vec![SelectedEntity::Marker(*id)],
+                                    }),
+                                    editor_scene.selection.clone(),
+                                ));
+                            }
+                            RulerMessage::LoopStart(start) => {
+                                self.loop_start = *start;
+                            }
+                            RulerMessage::LoopEnd(end) => {
+                                self.loop_end = *end;
+                            }
                             _ => (),
                         }
                     }
@@ -345,6 +1967,12 @@ impl AnimationEditor {
                         if let Some(animation) = animations.try_get_mut(selection.animation) {
                             animation.rewind();
 
+                            for track in animation.tracks_mut() {
+                                if !self.track_list.is_track_previewed(&track.id()) {
+                                    track.set_enabled(false);
+                                }
+                            }
+
                             let animation_targets = animation
                                 .tracks()
                                 .iter()
@@ -373,28 +2001,23 @@ impl AnimationEditor {
                     ToolbarAction::SelectAnimation(animation) => {
                         let animation_ref = &animation_player.animations()[animation];
 
-                        let size = engine
-                            .user_interface
-                            .node(self.curve_editor)
-                            .actual_local_size();
-                        let zoom = size.x / animation_ref.length().max(f32::EPSILON);
-
-                        engine.user_interface.send_message(CurveEditorMessage::zoom(
-                            self.curve_editor,
-                            MessageDirection::ToWidget,
-                            Vector2::new(zoom, zoom),
-                        ));
-
-                        engine
-                            .user_interface
-                            .send_message(CurveEditorMessage::view_position(
-                                self.curve_editor,
+                        // Loop range belongs to previous animation.
+                        for message in [
+                            RulerMessage::set_loop_start(
+                                self.ruler,
                                 MessageDirection::ToWidget,
-                                Vector2::new(
-                                    0.5 * (size.x - animation_ref.length()),
-                                    -0.5 * size.y,
-                                ),
-                            ));
+                                None,
+                            ),
+                            RulerMessage::set_loop_end(
+                                self.ruler,
+                                MessageDirection::ToWidget,
+                                None,
+                            ),
+                        ] {
+                            engine.user_interface.send_message(message);
+                        }
+
+                        self.fit_view_to_length(animation_ref.length(), &engine.user_interface);
                     }
                     ToolbarAction::PlayPause => {
                         if self.preview_mode_data.is_some() {
@@ -406,6 +2029,367 @@ impl AnimationEditor {
                             }
                         }
                     }
+                    ToolbarAction::PreviousKey | ToolbarAction::NextKey => {
+                        if let Some(animation) = animation_player
+                            .animations_mut()
+                            .try_get_mut(selection.animation)
+                        {
+                            // Union of the keys of all the curves shown in the curve editor.
+                            let time = animation.time_position();
+                            let new_time = neighbour_key_location(
+                                animation,
+                                time,
+                                matches!(toolbar_action, ToolbarAction::NextKey),
+                                |c| self.is_curve_shown(&selection, c),
+                            )
+                            .unwrap_or(time);
+                            self.move_time_cursor(animation, new_time, &engine.user_interface);
+                        }
+                    }
+                    ToolbarAction::GoToTime(time) => {
+                        if let Some(animation) = animation_player
+                            .animations_mut()
+                            .try_get_mut(selection.animation)
+                        {
+                            let max_distance = GO_TO_TIME_KEY_DISTANCE / self.toolbar.fps.max(1.0);
+
+                            // The nearest key of the curves shown in the curve editor.
+                            let nearest_key = animation
+                                .tracks()
+                                .iter()
+                                .flat_map(|t| t.data_container().curves_ref())
+                                .filter(|c| self.is_curve_shown(&selection, c))
+                                .flat_map(|c| {
+                                    let view = self.curve_view(animation, c);
+                                    view.keys()
+                                        .iter()
+                                        .map(|k| {
+                                            (c.id(), k.id, Vector2::new(k.location(), k.value))
+                                        })
+                                        .collect::<Vec<_>>()
+                                })
+                                .filter(|(_, _, position)| {
+                                    (position.x - time).abs() <= max_distance
+                                })
+                                .min_by(|(_, _, a), (_, _, b)| {
+                                    (a.x - time)
+                                        .abs()
+                                        .partial_cmp(&(b.x - time).abs())
+                                        .unwrap_or(Ordering::Equal)
+                                });
+
+                            let ui = &engine.user_interface;
+                            match nearest_key {
+                                Some((curve, key, position)) => {
+                                    self.move_time_cursor(animation, position.x, ui);
+                                    self.zoom_to_area(position, position, ui);
+
+                                    let mut entities = selection
+                                        .entities
+                                        .iter()
+                                        .filter(|e| !matches!(e, SelectedEntity::Keyframe { .. }))
+                                        .cloned()
+                                        .collect::<Vec<_>>();
+                                    entities.push(SelectedEntity::Keyframe { curve, key });
+                                    sender.do_scene_command(ChangeSelectionCommand::new(
+                                        Selection::Animation(AnimationSelection {
+                                            animation_player: selection.animation_player,
+                                            animation: selection.animation,
+                                            entities,
+                                        }),
+                                        editor_scene.selection.clone(),
+                                    ));
+                                }
+                                None => self.move_time_cursor(animation, time, ui),
+                            }
+                        }
+                    }
+                    ToolbarAction::ZoomToSelection => {
+                        if let Some(animation) =
+                            animation_player.animations().try_get(selection.animation)
+                        {
+                            self.zoom_to_selection(&selection, animation, &engine.user_interface);
+                        }
+                    }
+                    ToolbarAction::ResetView => {
+                        self.reset_view(&engine.user_interface);
+                    }
+                    ToolbarAction::DeleteKeysInRange => {
+                        if let (Some(start), Some(end), Some(animation)) = (
+                            self.loop_start,
+                            self.loop_end,
+                            animation_player.animations().try_get(selection.animation),
+                        ) {
+                            let mut locked_edit = false;
+                            let curves = selection
+                                .curves()
+                                .filter(|id| {
+                                    let locked = self.track_list.is_curve_locked(animation, *id);
+                                    locked_edit |= locked;
+                                    !locked
+                                })
+                                .collect::<Vec<_>>();
+
+                            if locked_edit {
+                                report_locked_edit(sender);
+                            }
+                            if !curves.is_empty() {
+                                sender.do_scene_command(DeleteKeyframesInRangeCommand::new(
+                                    selection.animation_player,
+                                    selection.animation,
+                                    curves,
+                                    start..end,
+                                    editor_settings.preserve_shape_on_range_delete,
+                                ));
+                            }
+                        } else {
+                            Log::warn("Set a loop range on the ruler to delete keys within it!");
+                        }
+                    }
+                    ToolbarAction::PinReference => {
+                        if let Some(animation) =
+                            animation_player.animations().try_get(selection.animation)
+                        {
+                            for curve in selected_curves(animation, &selection) {
+                                // Pinning the same curve again updates its reference.
+                                let curve = self.curve_view(animation, curve);
+                                self.reference_curves.retain(|c| c.id() != curve.id());
+                                self.reference_curves.push(curve);
+                            }
+
+                            engine.user_interface.send_message(
+                                CurveEditorMessage::reference_curves(
+                                    self.curve_editor,
+                                    MessageDirection::ToWidget,
+                                    self.reference_curves.clone(),
+                                ),
+                            );
+                        }
+                    }
+                    ToolbarAction::ClearReferences => {
+                        self.reference_curves.clear();
+                        engine
+                            .user_interface
+                            .send_message(CurveEditorMessage::reference_curves(
+                                self.curve_editor,
+                                MessageDirection::ToWidget,
+                                Default::default(),
+                            ));
+                    }
+                    ToolbarAction::SetAudioReference(path) => {
+                        if animation_player
+                            .animations()
+                            .try_get(selection.animation)
+                            .is_some()
+                        {
+                            match AudioReference::load(&path) {
+                                Ok(reference) => {
+                                    self.audio_references.insert(
+                                        (selection.animation_player, selection.animation),
+                                        reference,
+                                    );
+                                    sender.send(Message::ForceSync);
+                                }
+                                Err(err) => Log::err(format!(
+                                    "Failed to load reference audio from {}! Reason: {}",
+                                    path.display(),
+                                    err
+                                )),
+                            }
+                        } else {
+                            Log::warn("Select an animation to attach the reference audio to!");
+                        }
+                    }
+                    ToolbarAction::ClearAudioReference => {
+                        if self
+                            .audio_references
+                            .remove(&(selection.animation_player, selection.animation))
+                            .is_some()
+                        {
+                            sender.send(Message::ForceSync);
+                        }
+                    }
+                    ToolbarAction::KeyTagFilterChanged(filter) => {
+                        engine
+                            .user_interface
+                            .send_message(CurveEditorMessage::key_tag_filter(
+                                self.curve_editor,
+                                MessageDirection::ToWidget,
+                                filter,
+                            ));
+                    }
+                    ToolbarAction::SetTangentMode(mode) => {
+                        engine
+                            .user_interface
+                            .send_message(CurveEditorMessage::set_tangent_mode(
+                                self.curve_editor,
+                                MessageDirection::ToWidget,
+                                mode,
+                            ));
+                    }
+                    ToolbarAction::ShowTangents(show) => {
+                        engine
+                            .user_interface
+                            .send_message(CurveEditorMessage::show_tangents(
+                                self.curve_editor,
+                                MessageDirection::ToWidget,
+                                show,
+                            ));
+                    }
+                    ToolbarAction::SetNewKeyKind(kind) => {
+                        engine
+                            .user_interface
+                            .send_message(CurveEditorMessage::new_key_kind(
+                                self.curve_editor,
+                                MessageDirection::ToWidget,
+                                kind,
+                            ));
+                    }
+                    ToolbarAction::FlattenTangents
+                    | ToolbarAction::AutoSmoothTangents
+                    | ToolbarAction::SetKeyTag(_)
+                    | ToolbarAction::AlignKeys(_)
+                    | ToolbarAction::DistributeKeys
+                    | ToolbarAction::RoundKeyValues(_) => {
+                        if let Some(animation) =
+                            animation_player.animations().try_get(selection.animation)
+                        {
+                            let mut keys = selection
+                                .entities
+                                .iter()
+                                .filter_map(|e| {
+                                    if let SelectedEntity::Keyframe { curve, key } = e {
+                                        Some((*curve, *key))
+                                    } else {
+                                        None
+                                    }
+                                })
+                                .collect::<Vec<_>>();
+
+                            let count = keys.len();
+                            keys.retain(|(curve, _)| {
+                                !self.track_list.is_curve_locked(animation, *curve)
+                            });
+                            if keys.len() != count {
+                                report_locked_edit(sender);
+                            }
+
+                            if !keys.is_empty() {
+                                match toolbar_action {
+                                    ToolbarAction::FlattenTangents => {
+                                        sender.do_scene_command(FlattenTangentsCommand::new(
+                                            selection.animation_player,
+                                            selection.animation,
+                                            keys,
+                                        ));
+                                    }
+                                    ToolbarAction::AlignKeys(_) | ToolbarAction::DistributeKeys => {
+                                        let collision = key_collision(editor_settings);
+                                        let command = if let ToolbarAction::AlignKeys(target) =
+                                            toolbar_action
+                                        {
+                                            AlignKeyframesCommand::new(
+                                                selection.animation_player,
+                                                selection.animation,
+                                                animation,
+                                                &keys,
+                                                target,
+                                                collision,
+                                            )
+                                            .map(SceneCommand::new)
+                                        } else {
+                                            DistributeKeyframesCommand::new(
+                                                selection.animation_player,
+                                                selection.animation,
+                                                animation,
+                                                &keys,
+                                                collision,
+                                            )
+                                            .map(SceneCommand::new)
+                                        };
+                                        match command {
+                                            Ok(command) => {
+                                                sender.send(Message::DoSceneCommand(command))
+                                            }
+                                            Err(err) => Log::warn(format!(
+                                                "Unable to move the keys. {} Enable merging \
+                                                of aligned keys in the settings to replace \
+                                                other keys.",
+                                                err
+                                            )),
+                                        }
+                                    }
+                                    ToolbarAction::RoundKeyValues(decimals) => {
+                                        sender.do_scene_command(RoundKeyframeValuesCommand::new(
+                                            selection.animation_player,
+                                            selection.animation,
+                                            keys,
+                                            decimals,
+                                        ));
+                                    }
+                                    ToolbarAction::SetKeyTag(tag) => {
+                                        sender.do_scene_command(SetKeyTagsCommand::new(
+                                            selection.animation_player,
+                                            selection.animation,
+                                            keys,
+                                            tag,
+                                        ));
+                                    }
+                                    _ => {
+                                        sender.do_scene_command(AutoSmoothTangentsCommand::new(
+                                            selection.animation_player,
+                                            selection.animation,
+                                            keys,
+                                        ));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    ToolbarAction::RotationViewChanged => {
+                        self.synced_curves = None;
+                        sender.send(Message::ForceSync);
+                    }
+                    ToolbarAction::RulerDensityChanged => {
+                        self.set_ruler_compact(
+                            self.toolbar.is_ruler_compact,
+                            &engine.user_interface,
+                        );
+                    }
+                    ToolbarAction::TimeGridChanged => {
+                        let ui = &engine.user_interface;
+                        ui.send_message(RulerMessage::frame_rate(
+                            self.ruler,
+                            MessageDirection::ToWidget,
+                            self.toolbar.fps,
+                        ));
+                        ui.send_message(RulerMessage::show_frame_ticks(
+                            self.ruler,
+                            MessageDirection::ToWidget,
+                            self.toolbar.snapping,
+                        ));
+                        ui.send_message(RulerMessage::time_format(
+                            self.ruler,
+                            MessageDirection::ToWidget,
+                            self.toolbar.time_format,
+                        ));
+                        ui.send_message(RulerMessage::snap_to_frames(
+                            self.ruler,
+                            MessageDirection::ToWidget,
+                            self.toolbar.snapping,
+                        ));
+                        ui.send_message(RulerMessage::snap_to_keys(
+                            self.ruler,
+                            MessageDirection::ToWidget,
+                            self.toolbar.snap_to_keys,
+                        ));
+                        // Vertical lines of the grid show where snapped keys land.
+                        ui.send_message(CurveEditorMessage::time_grid_step(
+                            self.curve_editor,
+                            MessageDirection::ToWidget,
+                            self.toolbar.snap_step(),
+                        ));
+                    }
                     ToolbarAction::Stop => {
                         if self.preview_mode_data.is_some() {
                             if let Some(animation) = animation_player
@@ -414,12 +2398,17 @@ impl AnimationEditor {
                             {
                                 animation.rewind();
                                 animation.set_enabled(false);
+
+                                // Same path as moving the cursor of the ruler, so the thumb of
+                                // the curve editor follows the rewound time.
+                                let start = animation.time_slice().start;
+                                self.move_time_cursor(animation, start, &engine.user_interface);
                             }
                         }
                     }
                 }
 
-                self.track_list.handle_ui_message(
+                let track_list_action = self.track_list.handle_ui_message(
                     message,
                     editor_scene,
                     sender,
@@ -428,6 +2417,131 @@ impl AnimationEditor {
                     &mut engine.user_interface,
                     scene,
                 );
+
+                match track_list_action {
+                    TrackListAction::None => {}
+                    TrackListAction::PreviewTracksChanged => {
+                        self.sync_preview_tracks(&selection, scene);
+                    }
+                    TrackListAction::CurveVisibilityChanged => {
+                        engine
+                            .user_interface
+                            .send_message(CurveEditorMessage::hidden_curves(
+                                self.curve_editor,
+                                MessageDirection::ToWidget,
+                                self.track_list.hidden_curves(),
+                            ));
+                    }
+                    TrackListAction::OffsetSelectedTracks => {
+                        self.track_list.open_offset_dialog(
+                            self.toolbar.time_format,
+                            self.toolbar.fps,
+                            &engine.user_interface,
+                        );
+                    }
+                    TrackListAction::CopySelectedTracks => {
+                        if let Some(animation) = scene
+                            .graph
+                            .try_get_of_type::<AnimationPlayer>(selection.animation_player)
+                            .and_then(|p| p.animations().try_get(selection.animation))
+                        {
+                            let copied = animation
+                                .tracks()
+                                .iter()
+                                .filter(|t| {
+                                    selection.entities.contains(&SelectedEntity::Track(t.id()))
+                                })
+                                .map(|t| CopiedTrack {
+                                    track: t.clone(),
+                                    target_name: scene
+                                        .graph
+                                        .try_get(t.target())
+                                        .map(|n| n.name_owned()),
+                                })
+                                .collect::<Vec<_>>();
+
+                            if !copied.is_empty() {
+                                self.track_clipboard = copied;
+                            }
+                        }
+                    }
+                    TrackListAction::ToggleIsolation { entity, previous } => {
+                        if let Some(animation) = scene
+                            .graph
+                            .try_get_of_type::<AnimationPlayer>(selection.animation_player)
+                            .and_then(|p| p.animations().try_get(selection.animation))
+                        {
+                            let is_isolated = selection.entities == [entity.clone()];
+                            let entities = match self.isolation.take() {
+                                Some((isolated, previous)) if isolated == entity && is_isolated => {
+                                    // Entities of the previous selection could be deleted while
+                                    // the entity was isolated.
+                                    previous
+                                        .into_iter()
+                                        .filter(|e| entity_exists(animation, e))
+                                        .collect()
+                                }
+                                _ => {
+                                    self.isolation = Some((entity.clone(), previous));
+                                    vec![entity]
+                                }
+                            };
+
+                            sender.do_scene_command(ChangeSelectionCommand::new(
+                                Selection::Animation(AnimationSelection {
+                                    animation_player: selection.animation_player,
+                                    animation: selection.animation,
+                                    entities,
+                                }),
+                                editor_scene.selection.clone(),
+                            ));
+                            self.zoom_to_fit_on_sync = true;
+                        }
+                    }
+                    TrackListAction::PasteTracks => {
+                        let mut commands = Vec::new();
+                        let mut entities = Vec::new();
+                        let mut orphaned = Vec::new();
+                        for copied in self.track_clipboard.iter() {
+                            let mut track = copy_track(&copied.track);
+                            track.set_target(pasted_track_target(
+                                &scene.graph,
+                                editor_scene.scene_content_root,
+                                copied,
+                            ));
+                            if track.target().is_none() {
+                                orphaned.push(format!("{}", track.binding()));
+                            }
+
+                            entities.push(SelectedEntity::Track(track.id()));
+                            commands.push(SceneCommand::new(AddTrackCommand::new(
+                                selection.animation_player,
+                                selection.animation,
+                                track,
+                            )));
+                        }
+
+                        if !commands.is_empty() {
+                            commands.push(SceneCommand::new(ChangeSelectionCommand::new(
+                                Selection::Animation(AnimationSelection {
+                                    animation_player: selection.animation_player,
+                                    animation: selection.animation,
+                                    entities,
+                                }),
+                                editor_scene.selection.clone(),
+                            )));
+                            sender.do_scene_command(CommandGroup::from(commands));
+                        }
+
+                        if !orphaned.is_empty() {
+                            Log::warn(format!(
+                                "Target nodes of pasted tracks were not found: {}. Use \
+                                \"Set Target...\" to bind the tracks.",
+                                orphaned.join(", ")
+                            ));
+                        }
+                    }
+                }
             }
 
             self.toolbar.post_handle_ui_message(
@@ -459,18 +2573,259 @@ impl AnimationEditor {
             assert!(node_overrides.insert(target));
         }
 
-        let mut data = PreviewModeData {
-            nodes: animation_targets
-                .into_iter()
-                .map(|t| (t, scene.graph[t].clone_box()))
-                .collect(),
-        };
+        let mut data = PreviewModeData {
+            nodes: animation_targets
+                .into_iter()
+                .map(|t| (t, scene.graph[t].clone_box()))
+                .collect(),
+        };
+
+        data.nodes
+            .push((initial_animation_player_handle, initial_animation_player));
+
+        // Save state of affected nodes.
+        self.preview_mode_data = Some(data);
+        // Isolation is applied on next update.
+        self.isolated_curve = None;
+    }
+
+    /// Changes zoom of the curve editor so the whole length of an animation is visible.
+    fn fit_view_to_length(&self, length: f32, ui: &UserInterface) {
+        let size = ui.node(self.curve_editor).actual_local_size();
+        let zoom = size.x / length.max(f32::EPSILON);
+
+        ui.send_message(CurveEditorMessage::zoom(
+            self.curve_editor,
+            MessageDirection::ToWidget,
+            Vector2::new(zoom, zoom),
+        ));
+
+        ui.send_message(CurveEditorMessage::view_position(
+            self.curve_editor,
+            MessageDirection::ToWidget,
+            Vector2::new(0.5 * (size.x - length), -0.5 * size.y),
+        ));
+    }
+
+    /// Moves the view of the curve editor to the origin and then frames every visible curve. If
+    /// there's nothing to frame, the view just stays at the origin with default zoom. The ruler
+    /// and the thumb follow the view by the reports of the curve editor.
+    fn reset_view(&self, ui: &UserInterface) {
+        ui.send_message(CurveEditorMessage::zoom(
+            self.curve_editor,
+            MessageDirection::ToWidget,
+            Vector2::repeat(1.0),
+        ));
+        ui.send_message(CurveEditorMessage::view_position(
+            self.curve_editor,
+            MessageDirection::ToWidget,
+            Vector2::default(),
+        ));
+        ui.send_message(CurveEditorMessage::zoom_to_fit(
+            self.curve_editor,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    /// Moves the time cursor of the animation to the given time. The time is clamped instead of
+    /// wrapping, even for looping animations.
+    fn move_time_cursor(&self, animation: &mut Animation, time: f32, ui: &UserInterface) {
+        let time_slice = animation.time_slice();
+        animation.set_time_position(time.clamp(time_slice.start, time_slice.end));
+
+        send_sync_message(
+            ui,
+            RulerMessage::value(
+                self.ruler,
+                MessageDirection::ToWidget,
+                animation.time_position(),
+            ),
+        );
+        ui.send_message(ThumbMessage::position(
+            self.thumb,
+            MessageDirection::ToWidget,
+            animation.time_position(),
+        ));
+    }
+
+    /// Returns true if the curve is shown in the curve editor, that is when it is visible and
+    /// selected.
+    fn is_curve_shown(&self, selection: &AnimationSelection, curve: &Curve) -> bool {
+        self.track_list.is_curve_visible(&curve.id())
+            && selection
+                .entities
+                .contains(&SelectedEntity::Curve(curve.id()))
+    }
+
+    /// Returns the curve as it is shown in the curve editor.
+    fn curve_view(&self, animation: &Animation, curve: &Curve) -> Curve {
+        if self.toolbar.show_euler_angles && euler::is_rotation_curve(animation, curve) {
+            euler::to_euler_view(curve)
+        } else {
+            curve.clone()
+        }
+    }
+
+    /// Frames selected keys in the curve editor, or every curve if there are no selected keys.
+    fn zoom_to_selection(
+        &self,
+        selection: &AnimationSelection,
+        animation: &Animation,
+        ui: &UserInterface,
+    ) {
+        let mut min = Vector2::repeat(f32::MAX);
+        let mut max = Vector2::repeat(-f32::MAX);
+        let mut any_selected = false;
+
+        for entity in selection.entities.iter() {
+            if let SelectedEntity::Keyframe { curve, key } = entity {
+                if let Some(position) = animation
+                    .tracks()
+                    .iter()
+                    .flat_map(|t| t.data_container().curves_ref())
+                    .find(|c| c.id() == *curve)
+                    .map(|c| self.curve_view(animation, c))
+                    .and_then(|c| {
+                        c.keys()
+                            .iter()
+                            .find(|k| k.id == *key)
+                            .map(|k| Vector2::new(k.location(), k.value))
+                    })
+                {
+                    min = min.inf(&position);
+                    max = max.sup(&position);
+                    any_selected = true;
+                }
+            }
+        }
+
+        if any_selected {
+            self.zoom_to_area(min, max, ui);
+        } else {
+            ui.send_message(CurveEditorMessage::zoom_to_fit(
+                self.curve_editor,
+                MessageDirection::ToWidget,
+            ));
+        }
+    }
+
+    /// Zooms the curve editor to the area between the given points.
+    fn zoom_to_area(&self, min: Vector2<f32>, max: Vector2<f32>, ui: &UserInterface) {
+        // Leave some space around the keys, otherwise a single key would occupy the whole area
+        // of the editor.
+        let size = (max - min).map(|v| v.max(0.1));
+        let rect = Rect::new(
+            0.5 * (min.x + max.x - size.x),
+            0.5 * (min.y + max.y - size.y),
+            size.x,
+            size.y,
+        )
+        .inflate(0.1 * size.x, 0.1 * size.y);
+
+        ui.send_message(CurveEditorMessage::zoom_to_rect(
+            self.curve_editor,
+            MessageDirection::ToWidget,
+            rect,
+        ));
+    }
+
+    /// Returns the selected animation as it was before entering preview mode.
+    fn saved_animation(&self, selection: &AnimationSelection) -> Option<&Animation> {
+        self.preview_mode_data
+            .as_ref()
+            .and_then(|preview_data| {
+                preview_data
+                    .nodes
+                    .iter()
+                    .find(|(handle, _)| *handle == selection.animation_player)
+            })
+            .and_then(|(_, node)| node.query_component_ref::<AnimationPlayer>())
+            .and_then(|animation_player| animation_player.animations().try_get(selection.animation))
+    }
+
+    /// Returns enabled state of the tracks of the animation as it was before entering preview
+    /// mode. The map is empty if the editor is not in preview mode.
+    fn saved_track_states(&self, selection: &AnimationSelection) -> FxHashMap<Uuid, bool> {
+        self.saved_animation(selection)
+            .map(|animation| {
+                animation
+                    .tracks()
+                    .iter()
+                    .map(|track| (track.id(), track.is_enabled()))
+                    .collect::<FxHashMap<_, _>>()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Enables or disables tracks of the previewed animation according to their mute and solo
+    /// state. Tracks that were disabled before entering preview mode are left disabled. When a
+    /// curve is isolated, only its track is enabled and the other curves of the track are held
+    /// at rest values. Animated nodes are reset to the rest pose, so disabled tracks do not
+    /// leave their last previewed values.
+    fn sync_preview_tracks(&self, selection: &AnimationSelection, scene: &mut Scene) {
+        let preview_data = match self.preview_mode_data.as_ref() {
+            Some(preview_data) => preview_data,
+            None => return,
+        };
+
+        for (handle, node) in preview_data.nodes.iter() {
+            if *handle != selection.animation_player {
+                if let Some(scene_node) = scene.graph.try_get_mut(*handle) {
+                    *scene_node = node.clone_box();
+                }
+            }
+        }
+
+        let saved_animation = self.saved_animation(selection);
+
+        if let Some(animation) = scene
+            .graph
+            .try_get_mut_of_type::<AnimationPlayer>(selection.animation_player)
+            .and_then(|animation_player| {
+                animation_player
+                    .animations_mut()
+                    .try_get_mut(selection.animation)
+            })
+        {
+            for track in animation.tracks_mut() {
+                let saved_track =
+                    saved_animation.and_then(|a| a.tracks().iter().find(|t| t.id() == track.id()));
+
+                // Curves of the track could be replaced by previous isolation.
+                if let Some(saved_track) = saved_track {
+                    track.set_data_container(saved_track.data_container().clone());
+                }
+
+                let mut enabled = saved_track.map_or(true, |t| t.is_enabled())
+                    && self.track_list.is_track_previewed(&track.id());
 
-        data.nodes
-            .push((initial_animation_player_handle, initial_animation_player));
+                let rest_node = preview_data
+                    .nodes
+                    .iter()
+                    .find(|(handle, _)| *handle == track.target())
+                    .map(|(_, node)| node);
 
-        // Save state of affected nodes.
-        self.preview_mode_data = Some(data);
+                if let Some(isolated) = self.isolated_curve {
+                    if track
+                        .data_container()
+                        .curves_ref()
+                        .iter()
+                        .any(|c| c.id() == isolated)
+                    {
+                        isolate::isolate_curve(track, isolated, rest_node);
+                    } else {
+                        enabled = false;
+                    }
+                }
+
+                let weight = self.track_list.track_weight(&track.id());
+                if weight != 1.0 {
+                    isolate::weight_track(track, weight, rest_node);
+                }
+
+                track.set_enabled(enabled);
+            }
+        }
     }
 
     fn leave_preview_mode(
@@ -480,16 +2835,21 @@ impl AnimationEditor {
         node_overrides: &mut FxHashSet<Handle<Node>>,
     ) {
         self.toolbar.on_preview_mode_changed(ui, false);
+        self.isolated_curve = None;
+        self.remove_audio_player(&mut scene.graph);
 
         let preview_data = self
             .preview_mode_data
             .take()
             .expect("Unable to leave animation preview mode!");
 
-        // Revert state of nodes.
+        // Revert state of nodes. Nodes that were removed from the scene somehow are just skipped,
+        // the rest of the scene must be restored anyway.
         for (handle, node) in preview_data.nodes {
-            assert!(node_overrides.remove(&handle));
-            scene.graph[handle] = node;
+            node_overrides.remove(&handle);
+            if let Some(scene_node) = scene.graph.try_get_mut(handle) {
+                *scene_node = node;
+            }
         }
     }
 
@@ -517,33 +2877,435 @@ impl AnimationEditor {
         {
             self.try_leave_preview_mode(editor_scene, engine);
         }
+
+        // Undone and redone changes are not keyed, they are keyed already if they need to be.
+        if let Message::DoSceneCommand(_) = message {
+            self.auto_key_pending |= self.toolbar.auto_key_enabled;
+        }
+
+        // The scene is saved right after this message, so its current state becomes the saved
+        // one.
+        if let Message::SaveScene(_) = message {
+            self.try_leave_preview_mode(editor_scene, engine);
+            self.store_saved_animations(&engine.scenes[editor_scene.scene]);
+
+            let selection = fetch_selection(&editor_scene.selection);
+            if self
+                .saved_animations
+                .contains_key(&(selection.animation_player, selection.animation))
+            {
+                self.diff
+                    .sync_to_model(Some(Default::default()), &mut engine.user_interface);
+            }
+        }
+    }
+
+    /// Must be called when a scene is loaded or created, animations of the scene become the saved
+    /// ones and the editor-only state of the previous scene is discarded.
+    pub fn on_scene_loaded(&mut self, editor_scene: &EditorScene, engine: &Engine) {
+        self.store_saved_animations(&engine.scenes[editor_scene.scene]);
+        // Preview mode is left before the scene is changed, so there's no sound to remove.
+        self.audio_references.clear();
+        self.audio_player = None;
+    }
+
+    fn store_saved_animations(&mut self, scene: &Scene) {
+        self.saved_animations = scene
+            .graph
+            .pair_iter()
+            .filter_map(|(handle, node)| {
+                node.query_component_ref::<AnimationPlayer>()
+                    .map(|animation_player| (handle, animation_player))
+            })
+            .flat_map(|(handle, animation_player)| {
+                animation_player.animations().pair_iter().map(
+                    move |(animation_handle, animation)| {
+                        ((handle, animation_handle), animation.clone())
+                    },
+                )
+            })
+            .collect();
     }
 
-    pub fn update(&mut self, editor_scene: &EditorScene, engine: &Engine) {
+    pub fn update(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
         let selection = fetch_selection(&editor_scene.selection);
 
-        let scene = &engine.scenes[editor_scene.scene];
+        let scene = &mut engine.scenes[editor_scene.scene];
+
+        let isolated_curve =
+            if self.preview_mode_data.is_some() && self.toolbar.isolate_selected_curve {
+                selection.curves().next()
+            } else {
+                None
+            };
+        if isolated_curve != self.isolated_curve {
+            self.isolated_curve = isolated_curve;
+            self.sync_preview_tracks(&selection, scene);
+        }
+
+        let playback = scene
+            .graph
+            .try_get_of_type::<AnimationPlayer>(selection.animation_player)
+            .and_then(|animation_player| animation_player.animations().try_get(selection.animation))
+            .map(|animation| (animation.time_position(), animation.is_enabled()));
+        self.sync_audio_player(&selection, editor_scene, scene, playback);
 
         if let Some(animation_player) = scene
             .graph
-            .try_get(selection.animation_player)
-            .and_then(|n| n.query_component_ref::<AnimationPlayer>())
+            .try_get_mut_of_type::<AnimationPlayer>(selection.animation_player)
         {
-            if let Some(animation) = animation_player.animations().try_get(selection.animation) {
+            if let Some(animation) = animation_player
+                .animations_mut()
+                .try_get_mut(selection.animation)
+            {
+                // Preview changes only a copy of the animation player, so the loop flag of the
+                // copy is overridden by the preview toggle without touching the saved one.
+                if self.preview_mode_data.is_some()
+                    && animation.is_loop() != self.toolbar.loop_preview
+                {
+                    animation.set_loop(self.toolbar.loop_preview);
+                }
+
+                // Root motion of the copy is disabled when the extraction is turned off, so the
+                // root node moves in the scene as it was authored.
+                if let Some(preview_mode_data) = self.preview_mode_data.as_ref() {
+                    let settings = if self.toolbar.extract_root_motion_in_preview() {
+                        preview_mode_data
+                            .saved_animation(selection.animation_player, selection.animation)
+                            .and_then(|a| a.root_motion_settings_ref().cloned())
+                    } else {
+                        None
+                    };
+                    if animation.root_motion_settings_ref() != settings.as_ref() {
+                        animation.set_root_motion_settings(settings);
+                    }
+                }
+
+                if let (Some(loop_start), Some(loop_end), Some(_)) = (
+                    self.loop_start,
+                    self.loop_end,
+                    self.preview_mode_data.as_ref(),
+                ) {
+                    let time = animation.time_position();
+                    if self.toolbar.loop_preview {
+                        // Wrap playback within the loop range.
+                        if loop_end > loop_start && (time < loop_start || time > loop_end) {
+                            animation.set_time_position(
+                                loop_start + (time - loop_start).rem_euclid(loop_end - loop_start),
+                            );
+                        }
+                    } else if time > loop_end {
+                        // Playback without looping stops at the end of the loop range.
+                        animation.set_time_position(loop_end);
+                    }
+                }
+
+                // Paused animation is not applied by the animation player, so the pose at the
+                // time cursor is applied here to show scrubbing in the scene.
+                let paused_pose = if self.preview_mode_data.is_some() && !animation.is_enabled() {
+                    Some(sample_pose(animation))
+                } else {
+                    None
+                };
+
+                let lines = value_readout_lines(animation, &selection);
+                self.sync_value_readout(lines, &mut engine.user_interface);
+
+                // Cursor time changes during playback and scrubbing.
+                let status = status_bar_text(
+                    animation,
+                    &selection,
+                    self.toolbar.time_format,
+                    self.toolbar.fps,
+                );
+                self.sync_status_bar(status, &engine.user_interface);
+
                 engine.user_interface.send_message(ThumbMessage::position(
                     self.thumb,
                     MessageDirection::ToWidget,
                     animation.time_position(),
                 ));
+
+                // Move time cursor of the ruler together with the playing animation. Sync message
+                // is used here, because otherwise it will be treated as user input.
+                send_sync_message(
+                    &engine.user_interface,
+                    RulerMessage::value(
+                        self.ruler,
+                        MessageDirection::ToWidget,
+                        animation.time_position(),
+                    ),
+                );
+
+                // Animated nodes are saved on entering preview mode, so they will be restored
+                // when preview mode is left.
+                for (target, values) in paused_pose.into_iter().flatten() {
+                    if let Some(node) = scene.graph.try_get_mut(target) {
+                        values.apply(node);
+                    }
+                }
+
+                return;
+            }
+        }
+
+        self.sync_value_readout(Vec::new(), &mut engine.user_interface);
+        self.sync_status_bar("No selection".to_string(), &engine.user_interface);
+    }
+
+    /// Plays the reference audio of the selected animation in preview mode. The sound node is
+    /// re-created when the reference is changed and removed when it is not needed anymore.
+    fn sync_audio_player(
+        &mut self,
+        selection: &AnimationSelection,
+        editor_scene: &EditorScene,
+        scene: &mut Scene,
+        playback: Option<(f32, bool)>,
+    ) {
+        let key = (selection.animation_player, selection.animation);
+        let path = if self.preview_mode_data.is_some() && self.toolbar.play_audio_in_preview {
+            self.audio_references.get(&key).map(|r| r.path.clone())
+        } else {
+            None
+        };
+
+        if self.audio_player.as_ref().map(|(path, _)| path) != path.as_ref() {
+            self.remove_audio_player(&mut scene.graph);
+            if let (Some(path), Some(reference)) = (path, self.audio_references.get(&key)) {
+                let player =
+                    reference.create_player(&mut scene.graph, editor_scene.editor_objects_root);
+                self.audio_player = Some((path, player));
+            }
+        }
+
+        if let (Some((_, player)), Some((time, is_playing))) =
+            (self.audio_player.as_ref(), playback)
+        {
+            audio::sync_player(&mut scene.graph, *player, time, is_playing);
+        }
+    }
+
+    fn remove_audio_player(&mut self, graph: &mut Graph) {
+        if let Some((_, player)) = self.audio_player.take() {
+            if graph.is_valid_handle(player) {
+                graph.remove_node(player);
+            }
+        }
+    }
+
+    fn sync_status_bar(&mut self, text: String, ui: &UserInterface) {
+        if text != self.status_text {
+            ui.send_message(TextMessage::text(
+                self.status_bar,
+                MessageDirection::ToWidget,
+                text.clone(),
+            ));
+            self.status_text = text;
+        }
+    }
+
+    /// Draws ghosts of the previewed animation at `t - delta` and `t + delta`. Ghosts are just
+    /// auxiliary geometry, they are never a part of the scene.
+    pub fn draw_onion_skin(&self, editor_scene: &EditorScene, engine: &mut Engine) {
+        if self.preview_mode_data.is_none() || !self.toolbar.onion_skinning {
+            return;
+        }
+
+        let selection = fetch_selection(&editor_scene.selection);
+
+        let scene = &mut engine.scenes[editor_scene.scene];
+
+        if let Some(animation) = scene
+            .graph
+            .try_get_of_type::<AnimationPlayer>(selection.animation_player)
+            .and_then(|animation_player| animation_player.animations().try_get(selection.animation))
+        {
+            let time = animation.time_position();
+            let delta = self.toolbar.onion_skin_offset;
+
+            for (offset, color) in [(-delta, PREVIOUS_GHOST_COLOR), (delta, NEXT_GHOST_COLOR)] {
+                draw_ghost(
+                    &scene.graph,
+                    animation,
+                    time + offset,
+                    color,
+                    &mut scene.drawing_context,
+                );
+            }
+        }
+    }
+
+    fn sync_value_readout(&mut self, lines: Vec<(Uuid, Color, String)>, ui: &mut UserInterface) {
+        let same_curves = lines.len() == self.value_readout_lines.len()
+            && lines
+                .iter()
+                .zip(self.value_readout_lines.iter())
+                .all(|((a, _, _), (b, _, _))| a == b);
+
+        if same_curves {
+            for ((_, _, text), (_, text_widget, shown_text)) in
+                lines.into_iter().zip(self.value_readout_lines.iter_mut())
+            {
+                if text != *shown_text {
+                    ui.send_message(TextMessage::text(
+                        *text_widget,
+                        MessageDirection::ToWidget,
+                        text.clone(),
+                    ));
+                    *shown_text = text;
+                }
+            }
+        } else {
+            for &row in ui.node(self.value_readout).children() {
+                ui.send_message(WidgetMessage::remove(row, MessageDirection::ToWidget));
+            }
+            self.value_readout_lines.clear();
+
+            for (id, color, text) in lines {
+                let ctx = &mut ui.build_ctx();
+                let text_widget = TextBuilder::new(WidgetBuilder::new())
+                    .with_text(&text)
+                    .build(ctx);
+                let row = StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            BorderBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_width(10.0)
+                                    .with_height(10.0)
+                                    .with_margin(Thickness::right(4.0))
+                                    .with_vertical_alignment(VerticalAlignment::Center)
+                                    .with_background(Brush::Solid(color)),
+                            )
+                            .build(ctx),
+                        )
+                        .with_child(text_widget),
+                )
+                .with_orientation(Orientation::Horizontal)
+                .build(ctx);
+                ui.send_message(WidgetMessage::link(
+                    row,
+                    MessageDirection::ToWidget,
+                    self.value_readout,
+                ));
+                self.value_readout_lines.push((id, text_widget, text));
+            }
+        }
+    }
+
+    /// Keys changes of the animated properties made by scene commands since last sync at the
+    /// current time of the animation. The values are remembered on every sync, so only changes
+    /// made while auto key is on are keyed.
+    fn sync_auto_key(
+        &mut self,
+        selection: &AnimationSelection,
+        scene: &Scene,
+        ui: &UserInterface,
+        editor_settings: &AnimationEditorSettings,
+        sender: &MessageSender,
+    ) {
+        let pending = std::mem::take(&mut self.auto_key_pending);
+
+        // Preview mode changes the properties on its own.
+        if self.preview_mode_data.is_some() {
+            return;
+        }
+
+        let animation = match scene
+            .graph
+            .try_get_of_type::<AnimationPlayer>(selection.animation_player)
+            .and_then(|animation_player| animation_player.animations().try_get(selection.animation))
+        {
+            Some(animation) => animation,
+            None => {
+                self.auto_key_values.clear();
+                return;
+            }
+        };
+
+        let values = animation
+            .tracks()
+            .iter()
+            .filter_map(|track| {
+                scene
+                    .graph
+                    .try_get(track.target())
+                    .and_then(|node| bound_values(node, track.binding()))
+                    .map(|values| (track.id(), values))
+            })
+            .collect::<FxHashMap<_, _>>();
+
+        if pending && self.toolbar.auto_key_enabled && ui.node(self.window).visibility() {
+            let time = animation.time_position();
+
+            let mut keys = Vec::new();
+            for track in animation.tracks() {
+                if self.track_list.is_track_locked(track.id()) {
+                    continue;
+                }
+
+                let changed = match (
+                    self.auto_key_values.get(&track.id()),
+                    values.get(&track.id()),
+                ) {
+                    (Some(old), Some(new)) => old != new,
+                    _ => false,
+                };
+                if changed {
+                    if let Some(node) = scene.graph.try_get(track.target()) {
+                        keys.extend(current_key_values(track, node, time));
+                    }
+                }
+            }
+
+            if !keys.is_empty() {
+                sender.do_scene_command(InsertKeyframesCommand::new(
+                    selection.animation_player,
+                    selection.animation,
+                    keys,
+                    time,
+                    editor_settings.new_key_interpolation.key_kind(),
+                ));
             }
         }
+
+        self.auto_key_values = values;
     }
 
-    pub fn sync_to_model(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
+    pub fn sync_to_model(
+        &mut self,
+        editor_scene: &EditorScene,
+        engine: &mut Engine,
+        editor_settings: &AnimationEditorSettings,
+        sender: &MessageSender,
+    ) {
         let selection = fetch_selection(&editor_scene.selection);
 
         let scene = &engine.scenes[editor_scene.scene];
 
+        self.sync_auto_key(
+            &selection,
+            scene,
+            &engine.user_interface,
+            editor_settings,
+            sender,
+        );
+
+        let audio_key = (selection.animation_player, selection.animation);
+        let audio_reference = self.audio_references.get(&audio_key);
+        let synced_audio = (audio_key, audio_reference.map(|r| r.path.clone()));
+        if self.synced_audio.as_ref() != Some(&synced_audio) {
+            send_sync_message(
+                &engine.user_interface,
+                RulerMessage::waveform(
+                    self.ruler,
+                    MessageDirection::ToWidget,
+                    audio_reference.map(|r| r.waveform.clone()),
+                ),
+            );
+            self.synced_audio = Some(synced_audio);
+        }
+
         let mut is_animation_player_selected = false;
         let mut is_animation_selected = false;
         let mut is_curve_selected = false;
@@ -559,16 +3321,61 @@ impl AnimationEditor {
                 scene,
                 &mut engine.user_interface,
                 self.preview_mode_data.is_some(),
+                self.preview_mode_data.as_ref().and_then(|d| {
+                    d.saved_animation(selection.animation_player, selection.animation)
+                }),
+            );
+            self.overview.sync_to_model(
+                animation_player,
+                selection.animation,
+                &mut engine.user_interface,
             );
 
             if let Some(animation) = animation_player.animations().try_get(selection.animation) {
+                // Keep the whole animation visible when its length was changed.
+                if let Some((animation_handle, length)) = self.synced_length {
+                    if animation_handle == selection.animation && length != animation.length() {
+                        self.fit_view_to_length(animation.length(), &engine.user_interface);
+                    }
+                }
+                self.synced_length = Some((selection.animation, animation.length()));
+
+                send_sync_message(
+                    &engine.user_interface,
+                    RulerMessage::looping(
+                        self.ruler,
+                        MessageDirection::ToWidget,
+                        if animation.is_loop() {
+                            Some(animation.time_slice())
+                        } else {
+                            None
+                        },
+                    ),
+                );
+
                 self.track_list.sync_to_model(
                     animation,
                     &scene.graph,
                     editor_scene,
+                    &self.saved_track_states(&selection),
+                    editor_settings.key_density_threshold,
                     &mut engine.user_interface,
                 );
 
+                self.keyframe_inspector.sync_to_model(
+                    animation,
+                    &selection,
+                    &engine.user_interface,
+                );
+
+                let status = status_bar_text(
+                    animation,
+                    &selection,
+                    self.toolbar.time_format,
+                    self.toolbar.fps,
+                );
+                self.sync_status_bar(status, &engine.user_interface);
+
                 send_sync_message(
                     &engine.user_interface,
                     CurveEditorMessage::hightlight_zones(
@@ -586,6 +3393,15 @@ impl AnimationEditor {
                     ),
                 );
 
+                send_sync_message(
+                    &engine.user_interface,
+                    CurveEditorMessage::key_location_limits(
+                        self.curve_editor,
+                        MessageDirection::ToWidget,
+                        Some(animation.time_slice()),
+                    ),
+                );
+
                 send_sync_message(
                     &engine.user_interface,
                     RulerMessage::sync_signals(
@@ -603,25 +3419,153 @@ impl AnimationEditor {
                     ),
                 );
 
-                // TODO: Support multi-selection.
-                if let Some(SelectedEntity::Curve(selected_curve_id)) = selection.entities.first() {
-                    if let Some(selected_curve) = animation.tracks().iter().find_map(|t| {
-                        t.data_container()
-                            .curves_ref()
+                send_sync_message(
+                    &engine.user_interface,
+                    RulerMessage::sync_markers(
+                        self.ruler,
+                        MessageDirection::ToWidget,
+                        animation
+                            .markers()
+                            .iter()
+                            .map(|m| MarkerView {
+                                id: m.id,
+                                time: m.time,
+                                name: m.name.clone(),
+                            })
+                            .collect(),
+                    ),
+                );
+
+                let selected_curves = selected_curves(animation, &selection)
+                    .map(|c| self.curve_view(animation, c))
+                    .collect::<Vec<_>>();
+
+                // Time cursor of the ruler could be snapped to any key of the visible curves.
+                send_sync_message(
+                    &engine.user_interface,
+                    RulerMessage::sync_key_times(
+                        self.ruler,
+                        MessageDirection::ToWidget,
+                        selected_curves
+                            .iter()
+                            .filter(|c| self.track_list.is_curve_visible(&c.id()))
+                            .flat_map(|c| c.keys().iter().map(|k| k.location()))
+                            .collect(),
+                    ),
+                );
+
+                send_sync_message(
+                    &engine.user_interface,
+                    MinimapMessage::length(
+                        self.minimap,
+                        MessageDirection::ToWidget,
+                        animation.length(),
+                    ),
+                );
+                send_sync_message(
+                    &engine.user_interface,
+                    MinimapMessage::sync_curves(
+                        self.minimap,
+                        MessageDirection::ToWidget,
+                        selected_curves
+                            .iter()
+                            .filter(|c| self.track_list.is_curve_visible(&c.id()))
+                            .cloned()
+                            .collect(),
+                    ),
+                );
+
+                if !selected_curves.is_empty() {
+                    // Re-sync only changed curves, otherwise the curve editor would flicker on
+                    // every unrelated change of the scene.
+                    if self.synced_curves.as_ref() != Some(&selected_curves) {
+                        // Color each curve by its role within its parent track.
+                        let curve_brushes = animation
+                            .tracks()
                             .iter()
-                            .find(|c| &c.id() == selected_curve_id)
-                    }) {
+                            .flat_map(|t| {
+                                let kind = t.data_container().value_kind();
+                                t.data_container()
+                                    .curves_ref()
+                                    .iter()
+                                    .enumerate()
+                                    .filter(|(_, c)| {
+                                        selected_curves.iter().any(|s| s.id() == c.id())
+                                    })
+                                    .map(move |(i, c)| (c.id(), Brush::Solid(curve_color(kind, i))))
+                            })
+                            .collect::<Vec<_>>();
+                        send_sync_message(
+                            &engine.user_interface,
+                            CurveEditorMessage::curve_brushes(
+                                self.curve_editor,
+                                MessageDirection::ToWidget,
+                                curve_brushes,
+                            ),
+                        );
                         send_sync_message(
                             &engine.user_interface,
                             CurveEditorMessage::sync(
                                 self.curve_editor,
                                 MessageDirection::ToWidget,
-                                selected_curve.clone(),
+                                selected_curves.clone(),
                             ),
                         );
+                        self.synced_curves = Some(selected_curves);
+                    }
+                    send_sync_message(
+                        &engine.user_interface,
+                        CurveEditorMessage::hidden_curves(
+                            self.curve_editor,
+                            MessageDirection::ToWidget,
+                            self.track_list.hidden_curves(),
+                        ),
+                    );
+                    if self.zoom_to_fit_on_sync {
+                        self.zoom_to_fit_on_sync = false;
+                        engine
+                            .user_interface
+                            .send_message(CurveEditorMessage::zoom_to_fit(
+                                self.curve_editor,
+                                MessageDirection::ToWidget,
+                            ));
                     }
+                    send_sync_message(
+                        &engine.user_interface,
+                        CurveEditorMessage::selection_changed(
+                            self.curve_editor,
+                            MessageDirection::ToWidget,
+                            selection
+                                .entities
+                                .iter()
+                                .filter_map(|e| {
+                                    if let SelectedEntity::Keyframe { key, .. } = e {
+                                        Some(*key)
+                                    } else {
+                                        None
+                                    }
+                                })
+                                .collect(),
+                        ),
+                    );
                     is_curve_selected = true;
                 }
+
+                // Preview changes only a copy of the animation, so the saved one is compared.
+                let current = self
+                    .preview_mode_data
+                    .as_ref()
+                    .and_then(|d| {
+                        d.saved_animation(selection.animation_player, selection.animation)
+                    })
+                    .unwrap_or(animation);
+                self.diff.sync_to_model(
+                    self.saved_animations
+                        .get(&(selection.animation_player, selection.animation))
+                        .map(|saved| diff_animations(saved, current, &scene.graph)),
+                    &mut engine.user_interface,
+                );
+
                 is_animation_selected = true;
             }
             is_animation_player_selected = true;
@@ -631,6 +3575,8 @@ impl AnimationEditor {
 
         if !is_animation_selected || !is_animation_player_selected {
             self.track_list.clear(ui);
+            self.keyframe_inspector.clear(ui);
+            self.diff.clear(ui);
 
             send_sync_message(
                 ui,
@@ -650,7 +3596,11 @@ impl AnimationEditor {
             );
         }
 
-        if !is_animation_selected || !is_animation_player_selected || !is_curve_selected {
+        // Selected curves could be removed from the animation (for example by undo), the curve
+        // editor must not show them anymore.
+        if (!is_animation_selected || !is_animation_player_selected || !is_curve_selected)
+            && self.synced_curves.as_ref().map_or(true, |c| !c.is_empty())
+        {
             send_sync_message(
                 ui,
                 CurveEditorMessage::sync(
@@ -659,10 +3609,20 @@ impl AnimationEditor {
                     Default::default(),
                 ),
             );
+            self.synced_curves = Some(Default::default());
+            send_sync_message(
+                ui,
+                MinimapMessage::sync_curves(
+                    self.minimap,
+                    MessageDirection::ToWidget,
+                    Default::default(),
+                ),
+            );
         }
 
         if !is_animation_player_selected {
             self.toolbar.clear(ui);
+            self.overview.clear(ui);
         }
 
         send_sync_message(
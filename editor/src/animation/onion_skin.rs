@@ -0,0 +1,172 @@
+//! Onion skinning draws translucent "ghosts" of the animated nodes at the neighbour frames, so
+//! motion arcs and spacing could be judged while scrubbing the animation.
+
+use fyrox::{
+    animation::{
+        value::{TrackValue, ValueBinding},
+        Animation,
+    },
+    core::{
+        algebra::{Matrix4, Vector3},
+        color::Color,
+        math::wrapf,
+        pool::Handle,
+    },
+    fxhash::FxHashMap,
+    scene::{
+        debug::{Line, SceneDrawingContext},
+        graph::Graph,
+        node::Node,
+        transform::Transform,
+    },
+};
+
+pub const PREVIOUS_GHOST_COLOR: Color = Color::from_rgba(80, 130, 230, 120);
+pub const NEXT_GHOST_COLOR: Color = Color::from_rgba(230, 150, 50, 120);
+
+const GHOST_NODE_RADIUS: f32 = 0.01;
+
+/// Converts a time to the time slice of the animation, the same way as the animation itself
+/// does it when playing.
+fn ghost_time(animation: &Animation, time: f32) -> f32 {
+    let time_slice = animation.time_slice();
+    if animation.is_loop() {
+        wrapf(time, time_slice.start, time_slice.end)
+    } else {
+        time.clamp(time_slice.start, time_slice.end)
+    }
+}
+
+/// Samples local transforms of the animated nodes at the given time. Tracks of the nodes are
+/// applied to copies of their current local transforms, so the scene itself is left untouched.
+fn sample_local_transforms(
+    graph: &Graph,
+    animation: &Animation,
+    time: f32,
+) -> FxHashMap<Handle<Node>, Transform> {
+    let mut transforms = FxHashMap::default();
+
+    for track in animation.tracks() {
+        if !track.is_enabled() {
+            continue;
+        }
+
+        let node = match graph.try_get(track.target()) {
+            Some(node) => node,
+            None => continue,
+        };
+
+        if let Some(bound_value) = track.fetch(time) {
+            let transform = transforms
+                .entry(track.target())
+                .or_insert_with(|| node.local_transform().clone());
+
+            match (bound_value.binding, bound_value.value) {
+                (ValueBinding::Position, TrackValue::Vector3(position)) => {
+                    transform.set_position(position);
+                }
+                (ValueBinding::Scale, TrackValue::Vector3(scale)) => {
+                    transform.set_scale(scale);
+                }
+                (ValueBinding::Rotation, TrackValue::UnitQuaternion(rotation)) => {
+                    transform.set_rotation(rotation);
+                }
+                // Only transform of a node is visible in a ghost.
+                _ => (),
+            }
+        }
+    }
+
+    transforms
+}
+
+fn ghost_global_transform(
+    graph: &Graph,
+    handle: Handle<Node>,
+    local_transforms: &FxHashMap<Handle<Node>, Transform>,
+    cache: &mut FxHashMap<Handle<Node>, Matrix4<f32>>,
+) -> Matrix4<f32> {
+    if let Some(matrix) = cache.get(&handle) {
+        return *matrix;
+    }
+
+    let node = &graph[handle];
+    let matrix = match local_transforms.get(&handle) {
+        Some(local_transform) => {
+            let parent_transform = if node.parent().is_some() {
+                ghost_global_transform(graph, node.parent(), local_transforms, cache)
+            } else {
+                Matrix4::identity()
+            };
+            parent_transform * local_transform.matrix()
+        }
+        None => {
+            // Nodes above the animated ones are not affected by the animation, but the ghost
+            // still must follow animated ancestors.
+            let mut ancestor = node.parent();
+            let mut animated_ancestor = false;
+            while let Some(ancestor_ref) = graph.try_get(ancestor) {
+                if local_transforms.contains_key(&ancestor) {
+                    animated_ancestor = true;
+                    break;
+                }
+                ancestor = ancestor_ref.parent();
+            }
+
+            if animated_ancestor && node.parent().is_some() {
+                ghost_global_transform(graph, node.parent(), local_transforms, cache)
+                    * node.local_transform().matrix()
+            } else {
+                node.global_transform()
+            }
+        }
+    };
+
+    cache.insert(handle, matrix);
+
+    matrix
+}
+
+fn position(matrix: &Matrix4<f32>) -> Vector3<f32> {
+    Vector3::new(matrix[12], matrix[13], matrix[14])
+}
+
+/// Draws a ghost of the nodes animated by the animation at the given time. Each animated node is
+/// drawn as a small sphere connected with its animated parent by a line.
+pub fn draw_ghost(
+    graph: &Graph,
+    animation: &Animation,
+    time: f32,
+    color: Color,
+    ctx: &mut SceneDrawingContext,
+) {
+    let local_transforms = sample_local_transforms(graph, animation, ghost_time(animation, time));
+    let mut cache = FxHashMap::default();
+
+    for &handle in local_transforms.keys() {
+        let global_position = position(&ghost_global_transform(
+            graph,
+            handle,
+            &local_transforms,
+            &mut cache,
+        ));
+
+        ctx.draw_wire_sphere(global_position, GHOST_NODE_RADIUS, 6, color);
+
+        let parent = graph[handle].parent();
+        if local_transforms.contains_key(&parent) {
+            let parent_position = position(&ghost_global_transform(
+                graph,
+                parent,
+                &local_transforms,
+                &mut cache,
+            ));
+
+            ctx.add_line(Line {
+                begin: parent_position,
+                end: global_position,
+                color,
+            });
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! Overview lists every animation of the selected animation player with a bar proportional to
+//! the length of the animation, a click on a row selects the animation.
+
+use fyrox::{
+    animation::Animation,
+    core::{algebra::Vector2, pool::Handle},
+    gui::{
+        border::BorderBuilder,
+        button::{ButtonBuilder, ButtonMessage},
+        expander::ExpanderBuilder,
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+        BRUSH_BRIGHT_BLUE, BRUSH_PRIMARY,
+    },
+    scene::animation::AnimationPlayer,
+};
+
+/// Width of the bar of the longest animation.
+const MAX_BAR_WIDTH: f32 = 200.0;
+const MAX_OVERVIEW_HEIGHT: f32 = 120.0;
+
+pub struct AnimationOverview {
+    pub panel: Handle<UiNode>,
+    rows_panel: Handle<UiNode>,
+    rows: Vec<(Handle<UiNode>, Handle<Animation>)>,
+    // Handle, name and length of each animation at the moment of last sync.
+    synced: Vec<(Handle<Animation>, String, f32)>,
+    selected: Handle<Animation>,
+}
+
+fn make_row(
+    name: &str,
+    length: f32,
+    max_length: f32,
+    selected: bool,
+    ctx: &mut BuildContext,
+) -> Handle<UiNode> {
+    let bar_width = if max_length > 0.0 {
+        MAX_BAR_WIDTH * length / max_length
+    } else {
+        0.0
+    };
+
+    ButtonBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        TextBuilder::new(
+                            WidgetBuilder::new()
+                                .on_column(0)
+                                .with_margin(Thickness::left_right(4.0)),
+                        )
+                        .with_text(name)
+                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                        .build(ctx),
+                    )
+                    .with_child(
+                        BorderBuilder::new(
+                            WidgetBuilder::new()
+                                .on_column(1)
+                                .with_width(bar_width.max(1.0))
+                                .with_height(10.0)
+                                .with_horizontal_alignment(HorizontalAlignment::Left)
+                                .with_vertical_alignment(VerticalAlignment::Center)
+                                .with_background(if selected {
+                                    BRUSH_BRIGHT_BLUE
+                                } else {
+                                    BRUSH_PRIMARY
+                                }),
+                        )
+                        .build(ctx),
+                    )
+                    .with_child(
+                        TextBuilder::new(
+                            WidgetBuilder::new()
+                                .on_column(2)
+                                .with_margin(Thickness::left_right(4.0)),
+                        )
+                        .with_text(format!("{:.2} s", length))
+                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::strict(20.0))
+            .add_column(Column::strict(120.0))
+            .add_column(Column::strict(MAX_BAR_WIDTH))
+            .add_column(Column::auto())
+            .build(ctx),
+        )
+        .build(ctx)
+}
+
+impl AnimationOverview {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let rows_panel = StackPanelBuilder::new(WidgetBuilder::new()).build(ctx);
+
+        let panel = ExpanderBuilder::new(WidgetBuilder::new().on_row(1))
+            .with_header(
+                TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(2.0)))
+                    .with_text("Animations")
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .build(ctx),
+            )
+            .with_content(
+                ScrollViewerBuilder::new(
+                    WidgetBuilder::new()
+                        .with_max_size(Vector2::new(f32::INFINITY, MAX_OVERVIEW_HEIGHT)),
+                )
+                .with_content(rows_panel)
+                .build(ctx),
+            )
+            .with_expanded(false)
+            .build(ctx);
+
+        Self {
+            panel,
+            rows_panel,
+            rows: Default::default(),
+            synced: Default::default(),
+            selected: Default::default(),
+        }
+    }
+
+    /// Rebuilds the rows when the animations of the player or the selected animation were
+    /// changed since last sync.
+    pub fn sync_to_model(
+        &mut self,
+        animation_player: &AnimationPlayer,
+        selected: Handle<Animation>,
+        ui: &mut UserInterface,
+    ) {
+        let animations = animation_player
+            .animations()
+            .pair_iter()
+            .map(|(h, a)| (h, a.name().to_owned(), a.length()))
+            .collect::<Vec<_>>();
+
+        if animations == self.synced && selected == self.selected {
+            return;
+        }
+
+        self.clear(ui);
+
+        let max_length = animations
+            .iter()
+            .map(|(_, _, length)| *length)
+            .fold(0.0, f32::max);
+
+        for (handle, name, length) in animations.iter() {
+            let row = make_row(
+                name,
+                *length,
+                max_length,
+                *handle == selected,
+                &mut ui.build_ctx(),
+            );
+            ui.send_message(WidgetMessage::link(
+                row,
+                MessageDirection::ToWidget,
+                self.rows_panel,
+            ));
+            self.rows.push((row, *handle));
+        }
+
+        self.synced = animations;
+        self.selected = selected;
+    }
+
+    pub fn clear(&mut self, ui: &UserInterface) {
+        for (row, _) in self.rows.drain(..) {
+            ui.send_message(WidgetMessage::remove(row, MessageDirection::ToWidget));
+        }
+        self.synced.clear();
+        self.selected = Handle::NONE;
+    }
+
+    /// Returns an animation that was clicked in the overview.
+    pub fn handle_ui_message(&self, message: &UiMessage) -> Option<Handle<Animation>> {
+        if let Some(ButtonMessage::Click) = message.data() {
+            return self
+                .rows
+                .iter()
+                .find(|(row, _)| *row == message.destination())
+                .map(|(_, animation)| *animation);
+        }
+        None
+    }
+}
@@ -3,11 +3,13 @@ use fyrox::gui::RcUiNodeHandle;
 use fyrox::{
     core::{
         algebra::{Matrix3, Point2, Vector2},
+        color::Color,
         math::{round_to_step, Rect},
         pool::Handle,
         uuid::Uuid,
     },
     gui::{
+        brush::Brush,
         define_constructor, define_widget_deref,
         draw::{CommandTexture, Draw, DrawingContext},
         formatted_text::{FormattedText, FormattedTextBuilder},
@@ -24,9 +26,20 @@ use fyrox::{
 use std::{
     any::{Any, TypeId},
     cell::{Cell, RefCell},
-    ops::{Deref, DerefMut},
+    cmp::Ordering,
+    f32::consts::PI,
+    ops::{Deref, DerefMut, Range},
 };
 
+/// Defines how the ruler shows time values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeFormat {
+    #[default]
+    Seconds,
+    /// Integer frame numbers, calculated using a frame rate of the ruler.
+    Frames,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum RulerMessage {
     Zoom(f32),
@@ -35,8 +48,49 @@ pub enum RulerMessage {
     AddSignal(f32),
     RemoveSignal(Uuid),
     SyncSignals(Vec<SignalView>),
-    MoveSignal { id: Uuid, new_position: f32 },
+    MoveSignal {
+        id: Uuid,
+        new_position: f32,
+    },
     SelectSignal(Uuid),
+    FrameRate(f32),
+    ShowFrameTicks(bool),
+    TimeFormat(TimeFormat),
+    /// Defines whether loop markers should be snapped to frames or not.
+    SnapToFrames(bool),
+    /// Start of the loop range. Loop range is active only if both of its ends are set.
+    LoopStart(Option<f32>),
+    /// End of the loop range.
+    LoopEnd(Option<f32>),
+    /// Defines whether the time cursor should be snapped to the closest key or not. Snapping
+    /// could also be done by holding Shift while dragging the cursor.
+    SnapToKeys(bool),
+    /// Times of the keys of the visible curves, used for cursor snapping.
+    SyncKeyTimes(Vec<f32>),
+    /// Defines whether the ruler is shown as a thin strip with ticks only, without time labels.
+    Compact(bool),
+    /// A request to add a named marker at the given time. The ruler only emits it, markers are
+    /// set using [`RulerMessage::SyncMarkers`].
+    AddMarker {
+        time: f32,
+        name: String,
+    },
+    RemoveMarker(Uuid),
+    SyncMarkers(Vec<MarkerView>),
+    MoveMarker {
+        id: Uuid,
+        new_position: f32,
+    },
+    SelectMarker(Uuid),
+    /// Emitted when a user moves the time cursor by clicking or dragging on the ruler. Unlike
+    /// [`RulerMessage::Value`], it is never emitted when the value is set from code.
+    CursorMoved(f32),
+    /// Time slice of the animation if the animation is looping, `None` otherwise. A faded
+    /// repetition of the looped part is shown after its end.
+    Looping(Option<Range<f32>>),
+    /// Waveform of the reference audio of the animation, it is drawn behind the ticks. `None`
+    /// hides the waveform.
+    Waveform(Option<WaveformView>),
 }
 
 impl RulerMessage {
@@ -48,6 +102,23 @@ impl RulerMessage {
     define_constructor!(RulerMessage:SyncSignals => fn sync_signals(Vec<SignalView>), layout: false);
     define_constructor!(RulerMessage:MoveSignal => fn move_signal(id: Uuid, new_position: f32), layout: false);
     define_constructor!(RulerMessage:SelectSignal => fn select_signal(Uuid), layout: false);
+    define_constructor!(RulerMessage:FrameRate => fn frame_rate(f32), layout: false);
+    define_constructor!(RulerMessage:ShowFrameTicks => fn show_frame_ticks(bool), layout: false);
+    define_constructor!(RulerMessage:TimeFormat => fn time_format(TimeFormat), layout: false);
+    define_constructor!(RulerMessage:SnapToFrames => fn snap_to_frames(bool), layout: false);
+    define_constructor!(RulerMessage:LoopStart => fn set_loop_start(Option<f32>), layout: false);
+    define_constructor!(RulerMessage:LoopEnd => fn set_loop_end(Option<f32>), layout: false);
+    define_constructor!(RulerMessage:SnapToKeys => fn snap_to_keys(bool), layout: false);
+    define_constructor!(RulerMessage:SyncKeyTimes => fn sync_key_times(Vec<f32>), layout: false);
+    define_constructor!(RulerMessage:Compact => fn compact(bool), layout: false);
+    define_constructor!(RulerMessage:AddMarker => fn add_marker(time: f32, name: String), layout: false);
+    define_constructor!(RulerMessage:RemoveMarker => fn remove_marker(Uuid), layout: false);
+    define_constructor!(RulerMessage:SyncMarkers => fn sync_markers(Vec<MarkerView>), layout: false);
+    define_constructor!(RulerMessage:MoveMarker => fn move_marker(id: Uuid, new_position: f32), layout: false);
+    define_constructor!(RulerMessage:SelectMarker => fn select_marker(Uuid), layout: false);
+    define_constructor!(RulerMessage:Looping => fn looping(Option<Range<f32>>), layout: false);
+    define_constructor!(RulerMessage:CursorMoved => fn cursor_moved(f32), layout: false);
+    define_constructor!(RulerMessage:Waveform => fn waveform(Option<WaveformView>), layout: false);
 }
 
 #[derive(Clone)]
@@ -55,6 +126,11 @@ struct ContextMenu {
     menu: RcUiNodeHandle,
     add_signal: Handle<UiNode>,
     remove_signal: Handle<UiNode>,
+    set_loop_start: Handle<UiNode>,
+    set_loop_end: Handle<UiNode>,
+    clear_loop_range: Handle<UiNode>,
+    add_marker: Handle<UiNode>,
+    remove_marker: Handle<UiNode>,
     selected_position: Cell<f32>,
 }
 
@@ -62,6 +138,11 @@ impl ContextMenu {
     fn new(ctx: &mut BuildContext) -> Self {
         let add_signal;
         let remove_signal;
+        let set_loop_start;
+        let set_loop_end;
+        let clear_loop_range;
+        let add_marker;
+        let remove_marker;
         let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
             .with_content(
                 StackPanelBuilder::new(
@@ -73,6 +154,26 @@ impl ContextMenu {
                         .with_child({
                             remove_signal = create_menu_item("Remove Signal", vec![], ctx);
                             remove_signal
+                        })
+                        .with_child({
+                            set_loop_start = create_menu_item("Set Loop Start", vec![], ctx);
+                            set_loop_start
+                        })
+                        .with_child({
+                            set_loop_end = create_menu_item("Set Loop End", vec![], ctx);
+                            set_loop_end
+                        })
+                        .with_child({
+                            clear_loop_range = create_menu_item("Clear Loop Range", vec![], ctx);
+                            clear_loop_range
+                        })
+                        .with_child({
+                            add_marker = create_menu_item("Add Marker", vec![], ctx);
+                            add_marker
+                        })
+                        .with_child({
+                            remove_marker = create_menu_item("Remove Marker", vec![], ctx);
+                            remove_marker
                         }),
                 )
                 .build(ctx),
@@ -84,6 +185,11 @@ impl ContextMenu {
             menu,
             add_signal,
             remove_signal,
+            set_loop_start,
+            set_loop_end,
+            clear_loop_range,
+            add_marker,
+            remove_marker,
             selected_position: Cell::new(0.0),
         }
     }
@@ -121,10 +227,53 @@ impl SignalView {
     const SIZE: f32 = 10.0;
 }
 
+/// A named annotation on the ruler, shown as a labeled tick.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkerView {
+    pub id: Uuid,
+    pub time: f32,
+    pub name: String,
+}
+
+impl MarkerView {
+    /// Width of the area around the tick of a marker, that could be grabbed with the mouse.
+    const GRAB_WIDTH: f32 = 6.0;
+
+    fn screen_bounds(&self, ruler: &Ruler) -> Rect<f32> {
+        let view_x = ruler.local_to_view(self.time);
+
+        let min = ruler
+            .visual_transform()
+            .transform_point(&Point2::new(view_x - MarkerView::GRAB_WIDTH * 0.5, 0.0))
+            .coords;
+        let max = ruler
+            .visual_transform()
+            .transform_point(&Point2::new(
+                view_x + MarkerView::GRAB_WIDTH * 0.5,
+                ruler.bounding_rect().size.y,
+            ))
+            .coords;
+
+        Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+}
+
+/// Waveform of an audio clip that starts at zero time of the ruler.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct WaveformView {
+    /// Duration of the clip, in seconds.
+    pub duration: f32,
+    /// Peak amplitude of each of the equal slices of the clip, in `[0; 1]` range.
+    pub peaks: Vec<f32>,
+}
+
 #[derive(Clone)]
 enum DragEntity {
     TimePosition,
     Signal(Uuid),
+    Marker { id: Uuid, initial_time: f32 },
+    LoopStart,
+    LoopEnd,
 }
 
 #[derive(Clone)]
@@ -142,6 +291,18 @@ pub struct Ruler {
     drag_context: Option<DragContext>,
     signals: RefCell<Vec<SignalView>>,
     context_menu: ContextMenu,
+    frame_rate: f32,
+    show_frame_ticks: bool,
+    time_format: TimeFormat,
+    snap_to_frames: bool,
+    loop_start: Option<f32>,
+    loop_end: Option<f32>,
+    snap_to_keys: bool,
+    key_times: Vec<f32>,
+    compact: bool,
+    markers: RefCell<Vec<MarkerView>>,
+    looping: Option<Range<f32>>,
+    waveform: Option<WaveformView>,
 }
 
 define_widget_deref!(Ruler);
@@ -170,6 +331,94 @@ impl Ruler {
     fn screen_to_value_space(&self, x: f32) -> f32 {
         self.view_to_local(self.screen_to_local(Vector2::new(x, 0.0)).x)
     }
+
+    /// Returns distance between major ticks of the ruler in seconds.
+    fn major_step(&self) -> f32 {
+        let step = 50.0 / self.zoom.clamp(0.001, 1000.0);
+        match self.time_format {
+            TimeFormat::Seconds => step,
+            TimeFormat::Frames => {
+                // Make sure that every tick is at some "round" frame number.
+                let frame_rate = self.frame_rate.max(1.0);
+                let frames = (step * frame_rate).ceil();
+                let frames = if frames <= 5.0 {
+                    frames
+                } else {
+                    (frames / 5.0).ceil() * 5.0
+                };
+                frames / frame_rate
+            }
+        }
+    }
+
+    const LOOP_MARKER_SIZE: f32 = 10.0;
+
+    fn loop_marker_screen_bounds(&self, time: f32) -> Rect<f32> {
+        let view_x = self.local_to_view(time);
+
+        let min = self
+            .visual_transform()
+            .transform_point(&Point2::new(view_x - Self::LOOP_MARKER_SIZE * 0.5, 0.0))
+            .coords;
+        let max = self
+            .visual_transform()
+            .transform_point(&Point2::new(
+                view_x + Self::LOOP_MARKER_SIZE * 0.5,
+                Self::LOOP_MARKER_SIZE,
+            ))
+            .coords;
+
+        Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    fn snap_time(&self, time: f32) -> f32 {
+        if self.snap_to_frames {
+            round_to_step(time, 1.0 / self.frame_rate.max(1.0))
+        } else {
+            time
+        }
+    }
+
+    /// Moves the time cursor on user input and notifies about it with
+    /// [`RulerMessage::CursorMoved`]. The value is set directly, so the cursor follows the mouse
+    /// without a delay of a message round trip.
+    fn scrub(&mut self, time: f32, ui: &UserInterface) {
+        if time != self.value {
+            self.value = time;
+            ui.send_message(RulerMessage::cursor_moved(
+                self.handle,
+                MessageDirection::FromWidget,
+                time,
+            ));
+        }
+    }
+
+    /// Max distance (in pixels) between the cursor and a key at which the cursor snaps to the key.
+    const KEY_SNAP_DISTANCE: f32 = 8.0;
+
+    /// Returns time of the closest key, if it is close enough to the given time and key snapping
+    /// is active.
+    fn snap_to_key(&self, time: f32, ui: &UserInterface) -> f32 {
+        if !self.snap_to_keys && !ui.keyboard_modifiers().shift {
+            return time;
+        }
+
+        let max_distance = Self::KEY_SNAP_DISTANCE / self.zoom.max(f32::EPSILON);
+
+        self.key_times
+            .iter()
+            .map(|key_time| (*key_time, (key_time - time).abs()))
+            .filter(|(_, distance)| *distance <= max_distance)
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map_or(time, |(key_time, _)| key_time)
+    }
+
+    fn format_time(&self, time: f32) -> String {
+        match self.time_format {
+            TimeFormat::Seconds => format!("{:.1}s", time),
+            TimeFormat::Frames => format!("{}", (time * self.frame_rate).round() as i64),
+        }
+    }
 }
 
 impl Control for Ruler {
@@ -193,8 +442,42 @@ impl Control for Ruler {
             None,
         );
 
+        // Waveform is drawn behind the ticks, only the slices within the visible range.
+        if let Some(ref waveform) = self.waveform {
+            if !waveform.peaks.is_empty() && waveform.duration > 0.0 {
+                let center = local_bounds.h() * 0.5;
+                let slice = waveform.duration / waveform.peaks.len() as f32;
+                let first = (self.view_to_local(0.0) / slice).floor().max(0.0) as usize;
+                let last = (self.view_to_local(local_bounds.position.x + local_bounds.size.x)
+                    / slice)
+                    .ceil()
+                    .max(0.0) as usize;
+                for (i, peak) in waveform
+                    .peaks
+                    .iter()
+                    .enumerate()
+                    .take(last.saturating_add(1))
+                    .skip(first)
+                {
+                    let x = self.local_to_view(i as f32 * slice);
+                    let half_height = peak.clamp(0.0, 1.0) * center;
+                    ctx.push_line(
+                        Vector2::new(x, center - half_height),
+                        Vector2::new(x, center + half_height),
+                        1.0,
+                    );
+                }
+                ctx.commit(
+                    self.clip_bounds(),
+                    Brush::Solid(Color::from_rgba(90, 160, 220, 120)),
+                    CommandTexture::None,
+                    None,
+                );
+            }
+        }
+
         // Then draw the rest.
-        let step_size_x = 50.0 / self.zoom.clamp(0.001, 1000.0);
+        let step_size_x = self.major_step();
 
         let left_local_bound = round_to_step(self.view_to_local(0.0), step_size_x);
         let right_local_bound = round_to_step(
@@ -205,15 +488,45 @@ impl Control for Ruler {
         let range = right_local_bound - left_local_bound;
         let steps = ((range / step_size_x).ceil()) as usize;
 
+        // Compact ruler has no labels, so its ticks take the whole height.
+        let major_tick_top = if self.compact {
+            0.0
+        } else {
+            local_bounds.size.y * 0.5
+        };
+
         for nx in 0..=steps {
             let k = nx as f32 / steps as f32;
             let x = self.local_to_view(left_local_bound + k * range);
             ctx.push_line(
-                Vector2::new(x, local_bounds.size.y * 0.5),
+                Vector2::new(x, major_tick_top),
                 Vector2::new(x, local_bounds.size.y),
                 1.0,
             );
         }
+
+        if self.show_frame_ticks && !self.compact {
+            // Skip some frames if they're too close to each other.
+            let frame_step = 1.0 / self.frame_rate.max(1.0);
+            let min_spacing = 4.0;
+            let frames_per_tick = (min_spacing / (frame_step * self.zoom)).ceil().max(1.0);
+            let tick_step = frame_step * frames_per_tick;
+
+            let left = (self.view_to_local(0.0) / tick_step).floor() as i64;
+            let right = (self.view_to_local(local_bounds.position.x + local_bounds.size.x)
+                / tick_step)
+                .ceil() as i64;
+
+            for n in left..=right {
+                let x = self.local_to_view(n as f32 * tick_step);
+                ctx.push_line(
+                    Vector2::new(x, local_bounds.size.y * 0.8),
+                    Vector2::new(x, local_bounds.size.y),
+                    1.0,
+                );
+            }
+        }
+
         ctx.commit(
             self.clip_bounds(),
             self.foreground.clone(),
@@ -222,14 +535,16 @@ impl Control for Ruler {
         );
 
         // Draw values.
-        let mut text = self.text.borrow_mut();
+        if !self.compact {
+            let mut text = self.text.borrow_mut();
 
-        for nx in 0..=steps {
-            let k = nx as f32 / steps as f32;
-            let x = left_local_bound + k * range;
-            text.set_text(format!("{:.1}s", x)).build();
-            let vx = self.local_to_view(x);
-            ctx.draw_text(self.clip_bounds(), Vector2::new(vx + 1.0, 0.0), &text);
+            for nx in 0..=steps {
+                let k = nx as f32 / steps as f32;
+                let x = left_local_bound + k * range;
+                text.set_text(self.format_time(x)).build();
+                let vx = self.local_to_view(x);
+                ctx.draw_text(self.clip_bounds(), Vector2::new(vx + 1.0, 0.0), &text);
+            }
         }
 
         // Draw signals.
@@ -249,6 +564,97 @@ impl Control for Ruler {
             };
             ctx.commit(self.clip_bounds(), brush, CommandTexture::None, None);
         }
+
+        // Draw markers.
+        let marker_brush = Brush::Solid(Color::opaque(230, 150, 50));
+        for marker in self.markers.borrow().iter() {
+            let x = self.local_to_view(marker.time);
+            ctx.push_line(Vector2::new(x, 0.0), Vector2::new(x, local_bounds.h()), 1.0);
+        }
+        ctx.commit(self.clip_bounds(), marker_brush, CommandTexture::None, None);
+        if !self.compact {
+            let mut text = self.text.borrow_mut();
+            for marker in self.markers.borrow().iter() {
+                text.set_text(&marker.name).build();
+                let x = self.local_to_view(marker.time);
+                ctx.draw_text(
+                    self.clip_bounds(),
+                    Vector2::new(x + 2.0, local_bounds.h() * 0.5),
+                    &text,
+                );
+            }
+        }
+
+        // Draw looping. Preview repeats only the loop range if it is set, the whole time slice
+        // otherwise, so both cases are colored differently.
+        if let Some(ref time_slice) = self.looping {
+            let (looped, color) = match (self.loop_start, self.loop_end) {
+                (Some(loop_start), Some(loop_end)) => {
+                    (loop_start..loop_end, Color::opaque(80, 180, 80))
+                }
+                _ => (time_slice.clone(), Color::opaque(80, 150, 220)),
+            };
+
+            let start = self.local_to_view(looped.start);
+            let end = self.local_to_view(looped.end);
+            if end > start {
+                ctx.push_rect_filled(&Rect::new(end, 0.0, end - start, local_bounds.h()), None);
+                ctx.commit(
+                    self.clip_bounds(),
+                    Brush::Solid(color.with_new_alpha(30)),
+                    CommandTexture::None,
+                    None,
+                );
+
+                // A circular arrow at the end of the looped part.
+                let radius = (local_bounds.h() * 0.3).min(5.0);
+                let center = Vector2::new(end + radius + 3.0, local_bounds.h() * 0.5);
+                let angle = 0.8 * PI;
+                ctx.push_arc(center, radius, -angle..angle, 12, 1.5);
+                let tip = center + Vector2::new(angle.cos(), angle.sin()).scale(radius);
+                ctx.push_triangle_filled([
+                    tip + Vector2::new(-3.0, 0.0),
+                    tip + Vector2::new(3.0, 0.0),
+                    tip + Vector2::new(0.0, 3.0),
+                ]);
+                ctx.commit(
+                    self.clip_bounds(),
+                    Brush::Solid(color),
+                    CommandTexture::None,
+                    None,
+                );
+            }
+        }
+
+        // Draw loop range.
+        if let (Some(loop_start), Some(loop_end)) = (self.loop_start, self.loop_end) {
+            let size = Self::LOOP_MARKER_SIZE;
+            let start = self.local_to_view(loop_start);
+            let end = self.local_to_view(loop_end);
+
+            ctx.push_rect_filled(&Rect::new(start, 0.0, end - start, size * 0.5), None);
+            ctx.commit(
+                self.clip_bounds(),
+                Brush::Solid(Color::from_rgba(80, 180, 80, 100)),
+                CommandTexture::None,
+                None,
+            );
+
+            for x in [start, end] {
+                ctx.push_triangle_filled([
+                    Vector2::new(x - size * 0.5, 0.0),
+                    Vector2::new(x + size * 0.5, 0.0),
+                    Vector2::new(x, size),
+                ]);
+                ctx.push_line(Vector2::new(x, 0.0), Vector2::new(x, local_bounds.h()), 1.0);
+            }
+            ctx.commit(
+                self.clip_bounds(),
+                Brush::Solid(Color::opaque(80, 180, 80)),
+                CommandTexture::None,
+                None,
+            );
+        }
     }
 
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
@@ -262,6 +668,12 @@ impl Control for Ruler {
                     RulerMessage::Zoom(zoom) => {
                         self.zoom = *zoom;
                     }
+                    RulerMessage::Looping(looping) => {
+                        self.looping = looping.clone();
+                    }
+                    RulerMessage::Waveform(waveform) => {
+                        self.waveform = waveform.clone();
+                    }
                     RulerMessage::ViewPosition(position) => {
                         self.view_position = *position;
                     }
@@ -274,12 +686,72 @@ impl Control for Ruler {
                     RulerMessage::AddSignal(_)
                     | RulerMessage::RemoveSignal(_)
                     | RulerMessage::MoveSignal { .. }
-                    | RulerMessage::SelectSignal(_) => {
+                    | RulerMessage::SelectSignal(_)
+                    | RulerMessage::AddMarker { .. }
+                    | RulerMessage::RemoveMarker(_)
+                    | RulerMessage::MoveMarker { .. }
+                    | RulerMessage::SelectMarker(_)
+                    | RulerMessage::CursorMoved(_) => {
                         // Do nothing. These messages are only for output.
                     }
                     RulerMessage::SyncSignals(signals) => {
                         *self.signals.borrow_mut() = signals.clone();
                     }
+                    RulerMessage::FrameRate(frame_rate) => {
+                        self.frame_rate = *frame_rate;
+                    }
+                    RulerMessage::ShowFrameTicks(show) => {
+                        self.show_frame_ticks = *show;
+                    }
+                    RulerMessage::TimeFormat(time_format) => {
+                        self.time_format = *time_format;
+                    }
+                    RulerMessage::SnapToFrames(snap) => {
+                        self.snap_to_frames = *snap;
+                    }
+                    RulerMessage::SnapToKeys(snap) => {
+                        self.snap_to_keys = *snap;
+                    }
+                    RulerMessage::SyncKeyTimes(key_times) => {
+                        self.key_times = key_times.clone();
+                    }
+                    RulerMessage::Compact(compact) => {
+                        self.compact = *compact;
+                    }
+                    RulerMessage::SyncMarkers(markers) => {
+                        if self.drag_context.is_none() {
+                            *self.markers.borrow_mut() = markers.clone();
+                        }
+                    }
+                    RulerMessage::LoopStart(start) => {
+                        // Start marker cannot pass the end marker.
+                        let start = start.map(|start| {
+                            let start = self.snap_time(start).max(0.0);
+                            self.loop_end.map_or(start, |end| start.min(end))
+                        });
+                        if start != self.loop_start {
+                            self.loop_start = start;
+                            ui.send_message(RulerMessage::set_loop_start(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                start,
+                            ));
+                        }
+                    }
+                    RulerMessage::LoopEnd(end) => {
+                        let end = end.map(|end| {
+                            let end = self.snap_time(end).max(0.0);
+                            self.loop_start.map_or(end, |start| end.max(start))
+                        });
+                        if end != self.loop_end {
+                            self.loop_end = end;
+                            ui.send_message(RulerMessage::set_loop_end(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                end,
+                            ));
+                        }
+                    }
                 }
             }
         } else if let Some(msg) = message.data::<WidgetMessage>() {
@@ -289,6 +761,24 @@ impl Control for Ruler {
                         if *button == MouseButton::Left {
                             ui.capture_mouse(self.handle);
 
+                            if let Some(loop_start) = self.loop_start {
+                                if self.loop_marker_screen_bounds(loop_start).contains(*pos) {
+                                    self.drag_context = Some(DragContext {
+                                        entity: DragEntity::LoopStart,
+                                    });
+                                }
+                            }
+
+                            if let Some(loop_end) = self.loop_end {
+                                if self.drag_context.is_none()
+                                    && self.loop_marker_screen_bounds(loop_end).contains(*pos)
+                                {
+                                    self.drag_context = Some(DragContext {
+                                        entity: DragEntity::LoopEnd,
+                                    });
+                                }
+                            }
+
                             for signal in self.signals.borrow_mut().iter_mut() {
                                 signal.selected = false;
 
@@ -309,11 +799,37 @@ impl Control for Ruler {
                             }
 
                             if self.drag_context.is_none() {
-                                ui.send_message(RulerMessage::value(
-                                    self.handle,
-                                    MessageDirection::ToWidget,
-                                    self.screen_to_value_space(pos.x),
-                                ));
+                                if let Some(marker) = self
+                                    .markers
+                                    .borrow()
+                                    .iter()
+                                    .find(|m| m.screen_bounds(self).contains(*pos))
+                                {
+                                    // Clicking a marker snaps the cursor to it.
+                                    ui.send_message(RulerMessage::value(
+                                        self.handle,
+                                        MessageDirection::ToWidget,
+                                        marker.time,
+                                    ));
+                                    ui.send_message(RulerMessage::select_marker(
+                                        self.handle,
+                                        MessageDirection::FromWidget,
+                                        marker.id,
+                                    ));
+                                    self.drag_context = Some(DragContext {
+                                        entity: DragEntity::Marker {
+                                            id: marker.id,
+                                            initial_time: marker.time,
+                                        },
+                                    });
+                                }
+                            }
+
+                            if self.drag_context.is_none() {
+                                self.scrub(
+                                    self.snap_to_key(self.screen_to_value_space(pos.x), ui),
+                                    ui,
+                                );
 
                                 self.drag_context = Some(DragContext {
                                     entity: DragEntity::TimePosition,
@@ -326,32 +842,51 @@ impl Control for Ruler {
                             ui.release_mouse_capture();
 
                             if let Some(drag_context) = self.drag_context.take() {
-                                if let DragEntity::Signal(id) = drag_context.entity {
-                                    if let Some(signal) =
-                                        self.signals.borrow_mut().iter_mut().find(|s| s.id == id)
-                                    {
-                                        signal.selected = false;
+                                match drag_context.entity {
+                                    DragEntity::Signal(id) => {
+                                        if let Some(signal) = self
+                                            .signals
+                                            .borrow_mut()
+                                            .iter_mut()
+                                            .find(|s| s.id == id)
+                                        {
+                                            signal.selected = false;
 
-                                        ui.send_message(RulerMessage::move_signal(
-                                            self.handle,
-                                            MessageDirection::FromWidget,
-                                            id,
-                                            self.screen_to_value_space(pos.x),
-                                        ))
+                                            ui.send_message(RulerMessage::move_signal(
+                                                self.handle,
+                                                MessageDirection::FromWidget,
+                                                id,
+                                                self.screen_to_value_space(pos.x),
+                                            ))
+                                        }
+                                    }
+                                    DragEntity::Marker { id, initial_time } => {
+                                        if let Some(marker) =
+                                            self.markers.borrow().iter().find(|m| m.id == id)
+                                        {
+                                            if marker.time != initial_time {
+                                                ui.send_message(RulerMessage::move_marker(
+                                                    self.handle,
+                                                    MessageDirection::FromWidget,
+                                                    id,
+                                                    marker.time,
+                                                ))
+                                            }
+                                        }
                                     }
+                                    _ => (),
                                 }
                             }
                         }
                     }
                     WidgetMessage::MouseMove { pos, .. } => {
-                        if let Some(drag_context) = self.drag_context.as_ref() {
+                        if let Some(drag_context) = self.drag_context.clone() {
                             match drag_context.entity {
                                 DragEntity::TimePosition => {
-                                    ui.send_message(RulerMessage::value(
-                                        self.handle,
-                                        MessageDirection::ToWidget,
-                                        self.screen_to_value_space(pos.x),
-                                    ));
+                                    self.scrub(
+                                        self.snap_to_key(self.screen_to_value_space(pos.x), ui),
+                                        ui,
+                                    );
                                 }
 
                                 DragEntity::Signal(id) => {
@@ -361,6 +896,33 @@ impl Control for Ruler {
                                         signal.time = self.screen_to_value_space(pos.x);
                                     }
                                 }
+                                DragEntity::Marker { id, .. } => {
+                                    let time = self.snap_time(self.screen_to_value_space(pos.x));
+                                    if let Some(marker) =
+                                        self.markers.borrow_mut().iter_mut().find(|m| m.id == id)
+                                    {
+                                        marker.time = time.max(0.0);
+                                        ui.send_message(RulerMessage::value(
+                                            self.handle,
+                                            MessageDirection::ToWidget,
+                                            marker.time,
+                                        ));
+                                    }
+                                }
+                                DragEntity::LoopStart => {
+                                    ui.send_message(RulerMessage::set_loop_start(
+                                        self.handle,
+                                        MessageDirection::ToWidget,
+                                        Some(self.screen_to_value_space(pos.x)),
+                                    ));
+                                }
+                                DragEntity::LoopEnd => {
+                                    ui.send_message(RulerMessage::set_loop_end(
+                                        self.handle,
+                                        MessageDirection::ToWidget,
+                                        Some(self.screen_to_value_space(pos.x)),
+                                    ));
+                                }
                             }
                         }
                     }
@@ -392,6 +954,47 @@ impl Control for Ruler {
                         break; // No multi-selection
                     }
                 }
+            } else if message.destination() == self.context_menu.set_loop_start {
+                ui.send_message(RulerMessage::set_loop_start(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    Some(self.context_menu.selected_position.get()),
+                ));
+            } else if message.destination() == self.context_menu.set_loop_end {
+                ui.send_message(RulerMessage::set_loop_end(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    Some(self.context_menu.selected_position.get()),
+                ));
+            } else if message.destination() == self.context_menu.add_marker {
+                ui.send_message(RulerMessage::add_marker(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    self.context_menu.selected_position.get(),
+                    "Marker".to_string(),
+                ));
+            } else if message.destination() == self.context_menu.remove_marker {
+                if let Some(marker) = self.markers.borrow().iter().find(|m| {
+                    m.screen_bounds(self)
+                        .contains(ui.node(*self.context_menu.menu).screen_position())
+                }) {
+                    ui.send_message(RulerMessage::remove_marker(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        marker.id,
+                    ));
+                }
+            } else if message.destination() == self.context_menu.clear_loop_range {
+                ui.send_message(RulerMessage::set_loop_start(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    None,
+                ));
+                ui.send_message(RulerMessage::set_loop_end(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    None,
+                ));
             }
         } else if let Some(PopupMessage::Placement(Placement::Cursor(_))) = message.data() {
             self.context_menu
@@ -409,6 +1012,18 @@ impl Control for Ruler {
                 MessageDirection::ToWidget,
                 can_remove,
             ));
+
+            let can_remove_marker = self
+                .markers
+                .borrow()
+                .iter()
+                .any(|marker| marker.screen_bounds(self).contains(ui.cursor_position()));
+
+            ui.send_message(WidgetMessage::enabled(
+                self.context_menu.remove_marker,
+                MessageDirection::ToWidget,
+                can_remove_marker,
+            ));
         }
     }
 }
@@ -416,6 +1031,8 @@ impl Control for Ruler {
 pub struct RulerBuilder {
     widget_builder: WidgetBuilder,
     value: f32,
+    frame_rate: f32,
+    time_format: TimeFormat,
 }
 
 impl RulerBuilder {
@@ -423,6 +1040,8 @@ impl RulerBuilder {
         Self {
             widget_builder,
             value: 0.0,
+            frame_rate: 30.0,
+            time_format: Default::default(),
         }
     }
 
@@ -431,6 +1050,16 @@ impl RulerBuilder {
         self
     }
 
+    pub fn with_frame_rate(mut self, frame_rate: f32) -> Self {
+        self.frame_rate = frame_rate;
+        self
+    }
+
+    pub fn with_time_format(mut self, time_format: TimeFormat) -> Self {
+        self.time_format = time_format;
+        self
+    }
+
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let context_menu = ContextMenu::new(ctx);
 
@@ -449,6 +1078,18 @@ impl RulerBuilder {
             drag_context: None,
             signals: Default::default(),
             context_menu,
+            frame_rate: self.frame_rate,
+            show_frame_ticks: false,
+            time_format: self.time_format,
+            snap_to_frames: false,
+            loop_start: None,
+            loop_end: None,
+            snap_to_keys: false,
+            key_times: Default::default(),
+            compact: false,
+            markers: Default::default(),
+            looping: None,
+            waveform: None,
         };
 
         ctx.add_node(UiNode::new(ruler))
@@ -1,6 +1,6 @@
 use fyrox::{
     animation::Animation,
-    core::{pool::Handle, uuid::Uuid},
+    core::{curve::Curve, pool::Handle, uuid::Uuid},
     scene::node::Node,
 };
 
@@ -9,6 +9,8 @@ pub enum SelectedEntity {
     Track(Uuid),
     Curve(Uuid),
     Signal(Uuid),
+    Marker(Uuid),
+    Keyframe { curve: Uuid, key: Uuid },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,4 +28,29 @@ impl AnimationSelection {
     pub fn len(&self) -> usize {
         self.entities.len()
     }
+
+    /// Returns ids of the selected curves.
+    pub fn curves(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.entities.iter().filter_map(|e| {
+            if let SelectedEntity::Curve(id) = e {
+                Some(*id)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Returns curves of the animation that are selected.
+pub fn selected_curves<'a>(
+    animation: &'a Animation,
+    selection: &'a AnimationSelection,
+) -> impl Iterator<Item = &'a Curve> + 'a {
+    selection.curves().filter_map(move |id| {
+        animation
+            .tracks()
+            .iter()
+            .flat_map(|t| t.data_container().curves_ref())
+            .find(|c| c.id() == id)
+    })
 }
@@ -1,15 +1,27 @@
 use crate::message::MessageSender;
 use crate::{
     animation::{
+        animation_file::{load_animation, save_animation},
         command::{
-            AddAnimationCommand, RemoveAnimationCommand, ReplaceAnimationCommand,
-            SetAnimationEnabledCommand, SetAnimationLoopingCommand, SetAnimationNameCommand,
+            simplify_curve, AddAnimationCommand, AlignTarget, BakeCurveCommand,
+            DuplicateAnimationCommand, InsertKeyframeCommand, InsertKeyframesCommand,
+            NormalizeCurveCommand, RemapAnimationTimeCommand, RemoveAnimationCommand,
+            ReplaceAnimationCommand, ReplaceTrackCurveCommand, ReverseCurveCommand,
+            ScaleCurveValuesCommand, SetAnimationEnabledCommand, SetAnimationLengthCommand,
+            SetAnimationLoopingCommand, SetAnimationNameCommand,
             SetAnimationRootMotionSettingsCommand, SetAnimationSpeedCommand,
-            SetAnimationTimeSliceCommand,
+            SetAnimationTimeSliceCommand, SimplifyCurveCommand,
         },
-        selection::AnimationSelection,
+        curve_export::export_curves,
+        curve_import::CurveImportDialog,
+        curve_preset::{apply_preset, builtin_presets, make_preset},
+        key_collision, report_locked_edit,
+        ruler::TimeFormat,
+        selection::{selected_curves, AnimationSelection},
+        track::current_key_values,
+        unlocked_curves,
     },
-    gui::make_dropdown_list_option_universal,
+    gui::{make_dropdown_list_option, make_dropdown_list_option_universal},
     load_image,
     scene::{
         commands::{ChangeSelectionCommand, CommandGroup, SceneCommand},
@@ -17,21 +29,36 @@ use crate::{
         EditorScene, Selection,
     },
     send_sync_message,
+    settings::animation::{AnimationEditorSettings, CurvePreset, KeyInterpolation},
+    utils::create_file_selector,
 };
 use fyrox::{
-    animation::{Animation, RootMotionSettings},
+    animation::{track::Track, value::ValueBinding, Animation, RootMotionSettings},
     asset::manager::ResourceManager,
-    core::{algebra::Vector2, futures::executor::block_on, log::Log, math::Rect, pool::Handle},
+    core::{
+        algebra::Vector2,
+        color::Color,
+        curve::{Curve, CurveKey, CurveKeyKind, CurveKeyTag},
+        futures::executor::block_on,
+        log::Log,
+        math::Rect,
+        pool::Handle,
+        uuid::Uuid,
+    },
+    fxhash::FxHashSet,
     gui::{
         border::BorderBuilder,
+        brush::Brush,
         button::{Button, ButtonBuilder, ButtonMessage},
-        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        check_box::{CheckBox, CheckBoxBuilder, CheckBoxMessage},
+        curve::TangentMode,
         dropdown_list::{DropdownList, DropdownListBuilder, DropdownListMessage},
-        file_browser::{FileSelectorBuilder, FileSelectorMessage, Filter},
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
         grid::{Column, GridBuilder, Row},
         image::ImageBuilder,
         message::{MessageDirection, UiMessage},
-        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+        messagebox::{MessageBoxBuilder, MessageBoxButtons, MessageBoxMessage, MessageBoxResult},
+        numeric::{NumericUpDown, NumericUpDownBuilder, NumericUpDownMessage},
         popup::{Placement, PopupBuilder, PopupMessage},
         stack_panel::StackPanelBuilder,
         text::{TextBuilder, TextMessage},
@@ -40,13 +67,18 @@ use fyrox::{
         vector_image::{Primitive, VectorImageBuilder},
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
+        wrap_panel::WrapPanelBuilder,
         BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
-        VerticalAlignment, BRUSH_BRIGHT, BRUSH_LIGHT,
+        VerticalAlignment, BRUSH_BRIGHT, BRUSH_LIGHT, BRUSH_TEXT,
     },
-    resource::model::{Model, ModelResourceExtension},
+    resource::model::{Model, ModelResource, ModelResourceExtension},
     scene::{animation::AnimationPlayer, node::Node, Scene},
 };
-use std::path::Path;
+use std::{
+    cmp::Ordering,
+    mem::size_of,
+    path::{Path, PathBuf},
+};
 
 enum ImportMode {
     Import,
@@ -57,6 +89,9 @@ pub struct Toolbar {
     pub panel: Handle<UiNode>,
     pub play_pause: Handle<UiNode>,
     pub stop: Handle<UiNode>,
+    pub previous_key: Handle<UiNode>,
+    pub next_key: Handle<UiNode>,
+    pub go_to_time: Handle<UiNode>,
     pub speed: Handle<UiNode>,
     pub animations: Handle<UiNode>,
     pub add_animation: Handle<UiNode>,
@@ -65,18 +100,333 @@ pub struct Toolbar {
     pub clone_current_animation: Handle<UiNode>,
     pub animation_name: Handle<UiNode>,
     pub preview: Handle<UiNode>,
+    pub preview_loop: Handle<UiNode>,
     pub time_slice_start: Handle<UiNode>,
     pub time_slice_end: Handle<UiNode>,
+    pub length: Handle<UiNode>,
+    pub fit_length: Handle<UiNode>,
+    length_confirmation: Handle<UiNode>,
+    // New length of the animation, that waits for a confirmation from a user.
+    pending_length: Option<f32>,
     pub import: Handle<UiNode>,
     pub reimport: Handle<UiNode>,
     pub node_selector: Handle<UiNode>,
     pub import_file_selector: Handle<UiNode>,
     pub selected_import_root: Handle<Node>,
+    pub save_animation: Handle<UiNode>,
+    save_animation_file_selector: Handle<UiNode>,
+    pub load_animation: Handle<UiNode>,
+    load_animation_file_selector: Handle<UiNode>,
     pub looping: Handle<UiNode>,
     pub enabled: Handle<UiNode>,
     root_motion_dropdown_area: RootMotionDropdownArea,
     pub root_motion: Handle<UiNode>,
+    pub snap: Handle<UiNode>,
+    pub frame_rate: Handle<UiNode>,
+    pub show_frames: Handle<UiNode>,
+    pub snap_keys: Handle<UiNode>,
+    pub add_key: Handle<UiNode>,
+    pub key_all: Handle<UiNode>,
+    pub key_interpolation: Handle<UiNode>,
+    pub bake: Handle<UiNode>,
+    pub reverse: Handle<UiNode>,
+    pub normalize: Handle<UiNode>,
+    pub simplify: Handle<UiNode>,
+    pub simplify_tolerance: Handle<UiNode>,
+    simplify_preview: Handle<UiNode>,
+    key_stats: Handle<UiNode>,
+    /// Maximum deviation of simplified curves from the original ones.
+    pub simplification_tolerance: f32,
+    pub delete_range: Handle<UiNode>,
+    pub scale_values: Handle<UiNode>,
+    scale_values_dialog: ScaleValuesDialog,
+    pub import_curve: Handle<UiNode>,
+    curve_import_dialog: CurveImportDialog,
+    pub export_curve: Handle<UiNode>,
+    export_file_selector: Handle<UiNode>,
+    pub zoom_to_selection: Handle<UiNode>,
+    pub reset_view: Handle<UiNode>,
+    pub pin_reference: Handle<UiNode>,
+    pub clear_references: Handle<UiNode>,
+    pub tangent_modes: Handle<UiNode>,
+    pub flatten_tangents: Handle<UiNode>,
+    pub smooth_tangents: Handle<UiNode>,
+    pub align_first: Handle<UiNode>,
+    pub align_last: Handle<UiNode>,
+    pub distribute_keys: Handle<UiNode>,
+    pub cleanup: Handle<UiNode>,
+    cleanup_popup: Handle<UiNode>,
+    round_values: Handle<UiNode>,
+    round_decimals: Handle<UiNode>,
+    /// Amount of decimal places the values of selected keys are rounded to.
+    pub rounding_decimals: u32,
+    pub key_tags: Handle<UiNode>,
+    pub tag_keys: Handle<UiNode>,
+    pub key_tag_filter: Handle<UiNode>,
+    pub show_tangents: Handle<UiNode>,
+    pub curve_presets: Handle<UiNode>,
+    pub apply_preset: Handle<UiNode>,
+    pub remap_time: Handle<UiNode>,
+    pub save_preset: Handle<UiNode>,
+    /// Built-in presets followed by the presets of a user, in the order of the dropdown list.
+    presets: Vec<CurvePreset>,
+    selected_preset: Option<usize>,
+    /// Tag that is assigned to selected keys by the tag button.
+    selected_key_tag: CurveKeyTag,
     import_mode: ImportMode,
+    /// Defines whether keys should be snapped to the frames or not.
+    pub snapping: bool,
+    /// Amount of frames per second, used for key snapping and ruler time grid.
+    pub fps: f32,
+    pub time_format: TimeFormat,
+    /// Defines how tangents of keys are edited, kept between selections.
+    pub tangent_mode: TangentMode,
+    /// Defines whether the time cursor should be snapped to keys of visible curves or not.
+    pub snap_to_keys: bool,
+    pub onion_skin: Handle<UiNode>,
+    pub onion_skin_delta: Handle<UiNode>,
+    /// Defines whether poses at neighbour times are shown in preview mode or not.
+    pub onion_skinning: bool,
+    /// Defines whether playback loops in preview mode. It is independent of the saved loop flag
+    /// of the animation, which is not changed by preview.
+    pub loop_preview: bool,
+    /// Time offset of the neighbour poses, in seconds.
+    pub onion_skin_offset: f32,
+    pub isolate_curve: Handle<UiNode>,
+    /// Defines whether only the selected curve is applied in preview mode or not.
+    pub isolate_selected_curve: bool,
+    pub euler_view: Handle<UiNode>,
+    /// Defines whether curves of rotation tracks are shown as Euler angles in degrees or not.
+    pub show_euler_angles: bool,
+    pub compact_ruler: Handle<UiNode>,
+    /// Defines whether the ruler is collapsed to a thin strip without time labels or not.
+    pub is_ruler_compact: bool,
+    pub load_audio: Handle<UiNode>,
+    audio_file_selector: Handle<UiNode>,
+    pub clear_audio: Handle<UiNode>,
+    pub play_audio: Handle<UiNode>,
+    /// Defines whether the reference audio of the animation is played in preview mode or not.
+    pub play_audio_in_preview: bool,
+    pub auto_key: Handle<UiNode>,
+    auto_key_label: Handle<UiNode>,
+    /// Defines whether changes of animated properties made in the scene are keyed at the current
+    /// time or not.
+    pub auto_key_enabled: bool,
+}
+
+struct ScaleValuesDialog {
+    window: Handle<UiNode>,
+    factor: Handle<UiNode>,
+    shared_pivot: Handle<UiNode>,
+    pivot: Handle<UiNode>,
+    ok: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+}
+
+impl ScaleValuesDialog {
+    fn new(ctx: &mut BuildContext) -> Self {
+        fn text(text: &str, row: usize, ctx: &mut BuildContext) -> Handle<UiNode> {
+            TextBuilder::new(
+                WidgetBuilder::new()
+                    .with_vertical_alignment(VerticalAlignment::Center)
+                    .on_row(row)
+                    .on_column(0),
+            )
+            .with_text(text)
+            .build(ctx)
+        }
+
+        let factor = NumericUpDownBuilder::<f32>::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(1.0))
+                .on_row(0)
+                .on_column(1),
+        )
+        .with_value(1.0)
+        .build(ctx);
+        let shared_pivot = CheckBoxBuilder::new(
+            WidgetBuilder::new()
+                .with_width(18.0)
+                .with_height(18.0)
+                .with_margin(Thickness::uniform(1.0))
+                .with_vertical_alignment(VerticalAlignment::Center)
+                .with_horizontal_alignment(HorizontalAlignment::Left)
+                .with_tooltip(make_simple_tooltip(
+                    ctx,
+                    "Scale every curve around the same pivot, otherwise each curve \
+                    is scaled around the mean value of its keys",
+                ))
+                .on_row(1)
+                .on_column(1),
+        )
+        .checked(Some(false))
+        .build(ctx);
+        let pivot = NumericUpDownBuilder::<f32>::new(
+            WidgetBuilder::new()
+                .with_enabled(false)
+                .with_margin(Thickness::uniform(1.0))
+                .on_row(2)
+                .on_column(1),
+        )
+        .with_value(0.0)
+        .build(ctx);
+        let ok;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(240.0).with_height(130.0))
+            .open(false)
+            .with_title(WindowTitle::text("Scale Key Values"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(2.0))
+                        .with_child(text("Factor", 0, ctx))
+                        .with_child(factor)
+                        .with_child(text("Shared Pivot", 1, ctx))
+                        .with_child(shared_pivot)
+                        .with_child(text("Pivot", 2, ctx))
+                        .with_child(pivot)
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .on_row(3)
+                                    .on_column(1)
+                                    .with_child({
+                                        ok = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(70.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("OK")
+                                        .build(ctx);
+                                        ok
+                                    })
+                                    .with_child({
+                                        cancel = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(70.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Cancel")
+                                        .build(ctx);
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_column(Column::strict(90.0))
+                .add_column(Column::stretch())
+                .add_row(Row::strict(22.0))
+                .add_row(Row::strict(22.0))
+                .add_row(Row::strict(22.0))
+                .add_row(Row::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            factor,
+            shared_pivot,
+            pivot,
+            ok,
+            cancel,
+        }
+    }
+
+    fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        sender: &MessageSender,
+        ui: &UserInterface,
+        animation_player: &AnimationPlayer,
+        selection: &AnimationSelection,
+        locked_curves: &FxHashSet<Uuid>,
+    ) {
+        if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.shared_pivot
+                && message.direction() == MessageDirection::FromWidget
+            {
+                ui.send_message(WidgetMessage::enabled(
+                    self.pivot,
+                    MessageDirection::ToWidget,
+                    *value,
+                ));
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.ok {
+                let factor = ui
+                    .node(self.factor)
+                    .query_component::<NumericUpDown<f32>>()
+                    .unwrap()
+                    .value;
+                let shared_pivot = ui
+                    .node(self.shared_pivot)
+                    .query_component::<CheckBox>()
+                    .unwrap()
+                    .checked
+                    == Some(true);
+                let pivot = ui
+                    .node(self.pivot)
+                    .query_component::<NumericUpDown<f32>>()
+                    .unwrap()
+                    .value;
+
+                if let Some(animation) = animation_player.animations().try_get(selection.animation)
+                {
+                    let curves = unlocked_curves(
+                        selected_curves(animation, selection),
+                        locked_curves,
+                        sender,
+                    );
+                    let commands = curves
+                        .into_iter()
+                        .filter(|curve| !curve.keys().is_empty())
+                        .map(|curve| {
+                            let pivot = if shared_pivot {
+                                pivot
+                            } else {
+                                curve.keys().iter().map(|k| k.value).sum::<f32>()
+                                    / curve.keys().len() as f32
+                            };
+
+                            SceneCommand::new(ScaleCurveValuesCommand::new(
+                                selection.animation_player,
+                                selection.animation,
+                                curve.id(),
+                                factor,
+                                pivot,
+                            ))
+                        })
+                        .collect::<Vec<_>>();
+
+                    if !commands.is_empty() {
+                        sender.do_scene_command(CommandGroup::from(commands));
+                    }
+                }
+
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.cancel {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        }
+    }
 }
 
 struct RootMotionDropdownArea {
@@ -87,7 +437,36 @@ struct RootMotionDropdownArea {
     ignore_y: Handle<UiNode>,
     ignore_z: Handle<UiNode>,
     ignore_rotation: Handle<UiNode>,
+    root_tracks: Handle<UiNode>,
+    preview_extraction: Handle<UiNode>,
     node_selector: Handle<UiNode>,
+    /// Defines whether root motion is extracted in preview mode. When it is off, the root node
+    /// moves as the animation was authored, instead of staying in place.
+    extract_in_preview: bool,
+}
+
+/// Returns a short description of the tracks, which motion is extracted as root motion.
+fn root_motion_tracks_text(animation: &Animation) -> String {
+    match animation.root_motion_settings_ref() {
+        None => String::from("<Disabled>"),
+        Some(settings) if settings.node.is_none() => String::from("<Unassigned>"),
+        Some(settings) => {
+            let tracks = animation
+                .tracks()
+                .iter()
+                .filter(|t| {
+                    t.target() == settings.node
+                        && matches!(t.binding(), ValueBinding::Position | ValueBinding::Rotation)
+                })
+                .map(|t| t.binding().to_string())
+                .collect::<Vec<_>>();
+            if tracks.is_empty() {
+                String::from("<No Root Tracks>")
+            } else {
+                tracks.join(", ")
+            }
+        }
+    }
 }
 
 impl RootMotionDropdownArea {
@@ -123,10 +502,36 @@ impl RootMotionDropdownArea {
         let ignore_y = check_box(3, ctx);
         let ignore_z = check_box(4, ctx);
         let ignore_rotation = check_box(5, ctx);
+        let root_tracks = TextBuilder::new(
+            WidgetBuilder::new()
+                .with_vertical_alignment(VerticalAlignment::Center)
+                .on_row(6)
+                .on_column(1),
+        )
+        .with_text("<Disabled>")
+        .build(ctx);
+        let preview_extraction = CheckBoxBuilder::new(
+            WidgetBuilder::new()
+                .with_width(18.0)
+                .with_height(18.0)
+                .with_margin(Thickness::uniform(1.0))
+                .with_vertical_alignment(VerticalAlignment::Center)
+                .with_horizontal_alignment(HorizontalAlignment::Left)
+                .on_row(7)
+                .on_column(1)
+                .with_tooltip(make_simple_tooltip(
+                    ctx,
+                    "Extract root motion in preview mode. The root node stays in place when \
+                    the motion is extracted and moves as authored otherwise. Affects only \
+                    preview, the saved settings are not changed.",
+                )),
+        )
+        .checked(Some(true))
+        .build(ctx);
         let popup = PopupBuilder::new(
             WidgetBuilder::new()
-                .with_width(220.0)
-                .with_height(135.0)
+                .with_width(260.0)
+                .with_height(180.0)
                 .with_visibility(false),
         )
         .stays_open(false)
@@ -155,9 +560,13 @@ impl RootMotionDropdownArea {
                     .with_child(text("Ignore Z", 4, ctx))
                     .with_child(ignore_z)
                     .with_child(text("Ignore Rotation", 5, ctx))
-                    .with_child(ignore_rotation),
+                    .with_child(ignore_rotation)
+                    .with_child(text("Root Tracks", 6, ctx))
+                    .with_child(root_tracks)
+                    .with_child(text("Preview Extraction", 7, ctx))
+                    .with_child(preview_extraction),
             )
-            .add_column(Column::strict(90.0))
+            .add_column(Column::strict(120.0))
             .add_column(Column::stretch())
             .add_row(Row::strict(22.0))
             .add_row(Row::strict(22.0))
@@ -165,6 +574,8 @@ impl RootMotionDropdownArea {
             .add_row(Row::strict(22.0))
             .add_row(Row::strict(22.0))
             .add_row(Row::strict(22.0))
+            .add_row(Row::strict(22.0))
+            .add_row(Row::strict(22.0))
             .add_row(Row::stretch())
             .build(ctx),
         )
@@ -178,7 +589,10 @@ impl RootMotionDropdownArea {
             ignore_y,
             ignore_z,
             ignore_rotation,
+            root_tracks,
+            preview_extraction,
             node_selector: Default::default(),
+            extract_in_preview: true,
         }
     }
 }
@@ -190,7 +604,7 @@ impl RootMotionDropdownArea {
         scene: &Scene,
         sender: &MessageSender,
         ui: &mut UserInterface,
-        animation_player: &AnimationPlayer,
+        animation: Option<&Animation>,
         editor_scene: &EditorScene,
         selection: &AnimationSelection,
     ) {
@@ -202,10 +616,12 @@ impl RootMotionDropdownArea {
             });
         };
 
-        if let Some(animation) = animation_player.animations().try_get(selection.animation) {
+        if let Some(animation) = animation {
             if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
                 if message.direction() == MessageDirection::FromWidget {
-                    if message.destination() == self.enabled {
+                    if message.destination() == self.preview_extraction {
+                        self.extract_in_preview = *value;
+                    } else if message.destination() == self.enabled {
                         send_command(value.then(Default::default));
                     } else if message.destination() == self.ignore_x {
                         if let Some(settings) = animation.root_motion_settings_ref() {
@@ -305,8 +721,7 @@ impl RootMotionDropdownArea {
 
     pub fn sync_to_model(
         &self,
-        animation_player: &AnimationPlayer,
-        selection: &AnimationSelection,
+        animation: Option<&Animation>,
         scene: &Scene,
         ui: &mut UserInterface,
     ) {
@@ -317,7 +732,7 @@ impl RootMotionDropdownArea {
             );
         }
 
-        if let Some(animation) = animation_player.animations().try_get(selection.animation) {
+        if let Some(animation) = animation {
             let root_motion_enabled = animation.root_motion_settings_ref().is_some();
 
             sync_checked(ui, self.enabled, root_motion_enabled);
@@ -357,9 +772,135 @@ impl RootMotionDropdownArea {
                 sync_checked(ui, self.ignore_z, settings.ignore_z_movement);
                 sync_checked(ui, self.ignore_rotation, settings.ignore_rotations);
             }
+
+            send_sync_message(
+                ui,
+                TextMessage::text(
+                    self.root_tracks,
+                    MessageDirection::ToWidget,
+                    root_motion_tracks_text(animation),
+                ),
+            );
+        }
+    }
+}
+
+/// Returns time of the last key among every track of the animation. Disabled (muted) tracks are
+/// also taken into account, muting affects only the preview.
+fn last_key_time(animation: &Animation) -> Option<f32> {
+    animation
+        .tracks()
+        .iter()
+        .flat_map(|t| t.data_container().curves_ref())
+        .filter_map(|c| c.keys().last().map(|k| k.location()))
+        .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+}
+
+/// Returns total amount of keys of the animation and a rough estimate of memory used by its
+/// tracks, curves and keys. Only lengths of the collections are read, so it is cheap even for
+/// large animations.
+fn key_stats(animation: &Animation) -> (usize, usize) {
+    let mut keys = 0;
+    let mut bytes = size_of::<Animation>();
+    for track in animation.tracks() {
+        bytes += size_of::<Track>();
+        for curve in track.data_container().curves_ref() {
+            keys += curve.keys().len();
+            bytes += size_of::<Curve>() + curve.name().len();
+        }
+    }
+    bytes += keys * size_of::<CurveKey>();
+    (keys, bytes)
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const KB: f32 = 1024.0;
+    let bytes = bytes as f32;
+    if bytes < KB {
+        format!("{} B", bytes)
+    } else if bytes < KB * KB {
+        format!("{:.1} KB", bytes / KB)
+    } else {
+        format!("{:.1} MB", bytes / (KB * KB))
+    }
+}
+
+/// Returns the animation that is selected in the animation player along with the selection.
+fn selected_animation<'a>(
+    scene: &'a Scene,
+    animation_player_handle: Handle<Node>,
+    editor_scene: &'a EditorScene,
+) -> Option<(&'a Animation, &'a AnimationSelection)> {
+    if let Selection::Animation(ref selection) = editor_scene.selection {
+        scene
+            .graph
+            .try_get_of_type::<AnimationPlayer>(animation_player_handle)
+            .and_then(|p| p.animations().try_get(selection.animation))
+            .map(|animation| (animation, selection))
+    } else {
+        None
+    }
+}
+
+/// Returns names of the nodes of the model, which tracks were not bound to any node of the scene
+/// by retargeting. Animations of the model are enumerated in the same order as retargeting does.
+fn unbound_track_targets(model: &ModelResource, animations: &[Animation]) -> Vec<String> {
+    let data = model.data_ref();
+    let graph = &data.get_scene().graph;
+
+    let mut names = Vec::<String>::new();
+    let source_animations = graph
+        .linear_iter()
+        .filter_map(|n| n.query_component_ref::<AnimationPlayer>())
+        .flat_map(|p| p.animations().iter());
+    for (source, retargeted) in source_animations.zip(animations) {
+        for (source_track, track) in source.tracks().iter().zip(retargeted.tracks()) {
+            if track.target().is_none() {
+                if let Some(node) = graph.try_get(source_track.target()) {
+                    if !names.iter().any(|n| n == node.name()) {
+                        names.push(node.name().to_owned());
+                    }
+                }
+            }
+        }
+    }
+    names
+}
+
+/// Parses a time using the given time format, frames are converted to seconds.
+fn parse_time(text: &str, time_format: TimeFormat, fps: f32) -> Option<f32> {
+    let text = text.trim();
+    match time_format {
+        TimeFormat::Seconds => text.trim_end_matches('s').trim().parse::<f32>().ok(),
+        TimeFormat::Frames => text.parse::<f32>().ok().map(|frame| frame / fps.max(1.0)),
+    }
+    .filter(|time| time.is_finite())
+}
+
+/// Turns the text of the go to time field into an action for the animation editor.
+fn go_to_time_action(text: &str, time_format: TimeFormat, fps: f32) -> ToolbarAction {
+    match parse_time(text, time_format, fps) {
+        Some(time) => ToolbarAction::GoToTime(time),
+        None => {
+            if !text.trim().is_empty() {
+                Log::warn(format!("{} is not a valid time!", text));
+            }
+            ToolbarAction::None
         }
     }
 }
+
+/// Color of the label of the auto key toggle when auto key is on, it reminds that changes made in
+/// the scene are recorded.
+const AUTO_KEY_BRUSH: Brush = Brush::Solid(Color::opaque(230, 70, 70));
+
+/// Tags of keys in the order of the tag dropdown lists.
+const KEY_TAGS: [(CurveKeyTag, &str); 3] = [
+    (CurveKeyTag::Key, "Key"),
+    (CurveKeyTag::Breakdown, "Breakdown"),
+    (CurveKeyTag::InBetween, "In-Between"),
+];
+
 #[must_use]
 pub enum ToolbarAction {
     None,
@@ -368,12 +909,42 @@ pub enum ToolbarAction {
     SelectAnimation(Handle<Animation>),
     PlayPause,
     Stop,
+    PreviousKey,
+    NextKey,
+    TimeGridChanged,
+    ZoomToSelection,
+    ResetView,
+    /// Go to the given time and select the nearest key of the shown curves.
+    GoToTime(f32),
+    SetTangentMode(TangentMode),
+    SetNewKeyKind(CurveKeyKind),
+    FlattenTangents,
+    AutoSmoothTangents,
+    RotationViewChanged,
+    ShowTangents(bool),
+    RulerDensityChanged,
+    PinReference,
+    DeleteKeysInRange,
+    ClearReferences,
+    SetKeyTag(CurveKeyTag),
+    AlignKeys(AlignTarget),
+    DistributeKeys,
+    /// Round values of selected keys to the given amount of decimal places.
+    RoundKeyValues(u32),
+    /// Show only keys with the given tag, or every key if there is no tag.
+    KeyTagFilterChanged(Option<CurveKeyTag>),
+    /// Attach an audio clip from the given file to the selected animation as a reference.
+    SetAudioReference(PathBuf),
+    ClearAudioReference,
 }
 
 impl Toolbar {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let play_pause;
         let stop;
+        let previous_key;
+        let next_key;
+        let go_to_time;
         let speed;
         let animations;
         let add_animation;
@@ -382,19 +953,74 @@ impl Toolbar {
         let clone_current_animation;
         let animation_name;
         let preview;
+        let preview_loop;
         let time_slice_start;
         let time_slice_end;
+        let length;
+        let fit_length;
         let import;
         let reimport;
+        let save_animation;
+        let load_animation;
         let looping;
         let enabled;
         let root_motion;
+        let snap;
+        let frame_rate;
+        let show_frames;
+        let snap_keys;
+        let onion_skin;
+        let isolate_curve;
+        let onion_skin_delta;
+        let euler_view;
+        let compact_ruler;
+        let load_audio;
+        let clear_audio;
+        let play_audio;
+        let auto_key;
+        let auto_key_label;
+        let add_key;
+        let key_all;
+        let key_interpolation;
+        let bake;
+        let reverse;
+        let normalize;
+        let simplify;
+        let simplify_tolerance;
+        let simplify_preview;
+        let key_stats;
+        let delete_range;
+        let scale_values;
+        let import_curve;
+        let export_curve;
+        let zoom_to_selection;
+        let reset_view;
+        let pin_reference;
+        let clear_references;
+        let tangent_modes;
+        let flatten_tangents;
+        let smooth_tangents;
+        let align_first;
+        let align_last;
+        let distribute_keys;
+        let cleanup;
+        let key_tags;
+        let tag_keys;
+        let key_tag_filter;
+        let show_tangents;
+        let curve_presets;
+        let apply_preset;
+        let remap_time;
+        let save_preset;
+        let presets = builtin_presets();
         let panel = BorderBuilder::new(
             WidgetBuilder::new()
                 .on_row(0)
                 .with_foreground(BRUSH_LIGHT)
                 .with_child(
-                    StackPanelBuilder::new(
+                    // Controls are wrapped to the next rows when the window is too narrow for
+                    // all of them.
+                    WrapPanelBuilder::new(
                         WidgetBuilder::new()
                             .with_margin(Thickness::uniform(1.0))
                             .with_child({
@@ -483,6 +1109,38 @@ impl Toolbar {
                                 .build(ctx);
                                 reimport
                             })
+                            .with_child({
+                                save_animation = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Save Animation As.\n\
+                                            Saves the selected animation to a standalone file, \
+                                            so it could be loaded in other scenes.",
+                                        )),
+                                )
+                                .with_text("Save As...")
+                                .build(ctx);
+                                save_animation
+                            })
+                            .with_child({
+                                load_animation = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Load Animation.\n\
+                                            Loads an animation from a standalone file and adds \
+                                            it to the animation player. Tracks are bound to the \
+                                            nodes with the same names.",
+                                        )),
+                                )
+                                .with_text("Load...")
+                                .build(ctx);
+                                load_animation
+                            })
                             .with_child({
                                 rename_current_animation = ButtonBuilder::new(
                                     WidgetBuilder::new()
@@ -572,9 +1230,10 @@ impl Toolbar {
                                     WidgetBuilder::new()
                                         .with_margin(Thickness::uniform(1.0))
                                         .with_tooltip(make_simple_tooltip(
-                                        ctx,
-                                        "Animation looping. Looped animation will play infinitely.",
-                                    )),
+                                            ctx,
+                                            "Saved animation looping. Looped animation will play \
+                                        infinitely.",
+                                        )),
                                 )
                                 .with_content(
                                     TextBuilder::new(
@@ -682,92 +1341,970 @@ impl Toolbar {
                                 time_slice_end
                             })
                             .with_child({
-                                root_motion =
-                                    ButtonBuilder::new(WidgetBuilder::new().with_tooltip(
-                                        make_simple_tooltip(ctx, "Root Motion Settings"),
-                                    ))
-                                    .with_text("RM")
-                                    .build(ctx);
-                                root_motion
+                                length = NumericUpDownBuilder::<f32>::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_width(60.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Length of the Animation",
+                                        )),
+                                )
+                                .with_min_value(0.0)
+                                .with_value(1.0)
+                                .build(ctx);
+                                length
                             })
                             .with_child({
-                                preview = CheckBoxBuilder::new(
-                                    WidgetBuilder::new().with_enabled(false).with_margin(
-                                        Thickness {
-                                            left: 10.0,
-                                            top: 1.0,
-                                            right: 5.0,
-                                            bottom: 1.0,
-                                        },
-                                    ),
+                                fit_length = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Fit Length of the Animation to the Last Key",
+                                        )),
+                                )
+                                .with_text("Fit")
+                                .build(ctx);
+                                fit_length
+                            })
+                            .with_child({
+                                snap = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Snap Keys To Frames",
+                                        )),
                                 )
                                 .with_content(
                                     TextBuilder::new(
                                         WidgetBuilder::new()
                                             .with_vertical_alignment(VerticalAlignment::Center),
                                     )
-                                    .with_text("Preview")
+                                    .with_text("Snap")
                                     .build(ctx),
                                 )
                                 .checked(Some(false))
                                 .build(ctx);
-                                preview
+                                snap
                             })
                             .with_child({
-                                play_pause = ButtonBuilder::new(
-                                    WidgetBuilder::new().with_enabled(false).with_margin(
-                                        Thickness {
-                                            left: 1.0,
-                                            top: 1.0,
-                                            right: 1.0,
-                                            bottom: 1.0,
-                                        },
-                                    ),
+                                frame_rate = NumericUpDownBuilder::<f32>::new(
+                                    WidgetBuilder::new()
+                                        .with_width(40.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Frames Per Second",
+                                        )),
+                                )
+                                .with_min_value(1.0)
+                                .with_max_value(240.0)
+                                .with_precision(0)
+                                .with_value(Self::DEFAULT_FPS)
+                                .build(ctx);
+                                frame_rate
+                            })
+                            .with_child({
+                                show_frames = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Show Time In Frames",
+                                        )),
                                 )
                                 .with_content(
-                                    VectorImageBuilder::new(
+                                    TextBuilder::new(
                                         WidgetBuilder::new()
-                                            .with_foreground(BRUSH_BRIGHT)
-                                            .with_tooltip(make_simple_tooltip(ctx, "Play/Pause")),
+                                            .with_vertical_alignment(VerticalAlignment::Center),
                                     )
-                                    .with_primitives(vec![
-                                        Primitive::Triangle {
-                                            points: [
-                                                Vector2::new(0.0, 0.0),
-                                                Vector2::new(8.0, 8.0),
-                                                Vector2::new(0.0, 16.0),
-                                            ],
-                                        },
-                                        Primitive::RectangleFilled {
-                                            rect: Rect::new(10.0, 0.0, 4.0, 16.0),
-                                        },
-                                        Primitive::RectangleFilled {
-                                            rect: Rect::new(15.0, 0.0, 4.0, 16.0),
-                                        },
-                                    ])
+                                    .with_text("Frames")
                                     .build(ctx),
                                 )
+                                .checked(Some(false))
                                 .build(ctx);
-                                play_pause
+                                show_frames
                             })
                             .with_child({
-                                stop = ButtonBuilder::new(
+                                snap_keys = CheckBoxBuilder::new(
                                     WidgetBuilder::new()
-                                        .with_enabled(false)
                                         .with_margin(Thickness::uniform(1.0))
-                                        .with_tooltip(make_simple_tooltip(ctx, "Stop Playback")),
+                                        .with_tooltip(make_simple_tooltip(
+                                        ctx,
+                                        "Snap Time Cursor To Keys, Or Hold Shift While Dragging",
+                                    )),
                                 )
                                 .with_content(
-                                    VectorImageBuilder::new(
-                                        WidgetBuilder::new().with_foreground(BRUSH_BRIGHT),
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
                                     )
-                                    .with_primitives(vec![Primitive::RectangleFilled {
-                                        rect: Rect::new(0.0, 0.0, 16.0, 16.0),
-                                    }])
+                                    .with_text("Snap Keys")
                                     .build(ctx),
                                 )
+                                .checked(Some(false))
                                 .build(ctx);
-                                stop
+                                snap_keys
+                            })
+                            .with_child({
+                                onion_skin = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Show Poses At Neighbour Times In Preview Mode",
+                                        )),
+                                )
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Onion")
+                                    .build(ctx),
+                                )
+                                .checked(Some(false))
+                                .build(ctx);
+                                onion_skin
+                            })
+                            .with_child({
+                                onion_skin_delta = NumericUpDownBuilder::<f32>::new(
+                                    WidgetBuilder::new()
+                                        .with_width(50.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Time Offset Of Neighbour Poses",
+                                        )),
+                                )
+                                .with_min_value(0.001)
+                                .with_precision(3)
+                                .with_value(Self::DEFAULT_ONION_SKIN_OFFSET)
+                                .build(ctx);
+                                onion_skin_delta
+                            })
+                            .with_child({
+                                isolate_curve = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Apply Only The Selected Curve In Preview Mode, Other \
+                                            Properties Stay At Rest",
+                                        )),
+                                )
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Isolate")
+                                    .build(ctx),
+                                )
+                                .checked(Some(false))
+                                .build(ctx);
+                                isolate_curve
+                            })
+                            .with_child({
+                                euler_view = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                        ctx,
+                                        "Show Rotation Tracks As Pitch, Yaw And Roll In Degrees",
+                                    )),
+                                )
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Euler")
+                                    .build(ctx),
+                                )
+                                .checked(Some(false))
+                                .build(ctx);
+                                euler_view
+                            })
+                            .with_child({
+                                compact_ruler = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Collapse The Ruler To A Thin Strip Without Labels",
+                                        )),
+                                )
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Compact")
+                                    .build(ctx),
+                                )
+                                .checked(Some(false))
+                                .build(ctx);
+                                compact_ruler
+                            })
+                            .with_child({
+                                load_audio = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Attach a reference audio clip (WAV or OGG) to the \
+                                            animation, its waveform is shown on the ruler. The \
+                                            clip is an editor-only reference, it is not saved",
+                                        )),
+                                )
+                                .with_text("Audio...")
+                                .build(ctx);
+                                load_audio
+                            })
+                            .with_child({
+                                clear_audio = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Remove the reference audio of the animation",
+                                        )),
+                                )
+                                .with_text("Clear Audio")
+                                .build(ctx);
+                                clear_audio
+                            })
+                            .with_child({
+                                play_audio = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Play The Reference Audio In Sync With Preview",
+                                        )),
+                                )
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Play Audio")
+                                    .build(ctx),
+                                )
+                                .checked(Some(true))
+                                .build(ctx);
+                                play_audio
+                            })
+                            .with_child({
+                                auto_key = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Auto Key.\n\
+                                            Changes of animated properties made in the scene \
+                                            are keyed at the current time.",
+                                        )),
+                                )
+                                .with_content({
+                                    auto_key_label = TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Auto Key")
+                                    .build(ctx);
+                                    auto_key_label
+                                })
+                                .checked(Some(false))
+                                .build(ctx);
+                                auto_key
+                            })
+                            .with_child({
+                                add_key = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Add a key to every selected curve at current time",
+                                        )),
+                                )
+                                .with_text("Add Key")
+                                .build(ctx);
+                                add_key
+                            })
+                            .with_child({
+                                key_all = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Key current values of the animated properties of \
+                                            every track at current time",
+                                        )),
+                                )
+                                .with_text("Key All")
+                                .build(ctx);
+                                key_all
+                            })
+                            .with_child({
+                                key_interpolation = DropdownListBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_width(70.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Interpolation of New Keys",
+                                        )),
+                                )
+                                .with_items(
+                                    KeyInterpolation::ALL
+                                        .iter()
+                                        .map(|i| make_dropdown_list_option(ctx, i.as_ref()))
+                                        .collect(),
+                                )
+                                .with_selected(0)
+                                .build(ctx);
+                                key_interpolation
+                            })
+                            .with_child({
+                                bake = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Replace every selected curve with linear keys \
+                                            sampled at the frame rate",
+                                        )),
+                                )
+                                .with_text("Bake")
+                                .build(ctx);
+                                bake
+                            })
+                            .with_child({
+                                reverse = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Reverse every selected curve in time",
+                                        )),
+                                )
+                                .with_text("Reverse")
+                                .build(ctx);
+                                reverse
+                            })
+                            .with_child({
+                                normalize = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Remap values of every selected curve to 0..1 range",
+                                        )),
+                                )
+                                .with_text("Normalize")
+                                .build(ctx);
+                                normalize
+                            })
+                            .with_child({
+                                simplify = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Remove keys of every selected curve, which removal \
+                                            changes the curve by less than the tolerance",
+                                        )),
+                                )
+                                .with_text("Simplify")
+                                .build(ctx);
+                                simplify
+                            })
+                            .with_child({
+                                simplify_tolerance = NumericUpDownBuilder::<f32>::new(
+                                    WidgetBuilder::new()
+                                        .with_width(50.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Simplification Tolerance",
+                                        )),
+                                )
+                                .with_min_value(0.0)
+                                .with_precision(3)
+                                .with_step(0.001)
+                                .with_value(Self::DEFAULT_SIMPLIFICATION_TOLERANCE)
+                                .build(ctx);
+                                simplify_tolerance
+                            })
+                            .with_child({
+                                simplify_preview = TextBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Amount Of Keys That Simplify Will Remove",
+                                        )),
+                                )
+                                .with_vertical_text_alignment(VerticalAlignment::Center)
+                                .build(ctx);
+                                simplify_preview
+                            })
+                            .with_child({
+                                key_stats = TextBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::left_right(4.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Keys Of Every Track And Estimated Memory Usage Of \
+                                            The Animation",
+                                        )),
+                                )
+                                .with_vertical_text_alignment(VerticalAlignment::Center)
+                                .build(ctx);
+                                key_stats
+                            })
+                            .with_child({
+                                delete_range = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Delete keys of every selected curve within the loop \
+                                            range of the ruler",
+                                        )),
+                                )
+                                .with_text("Delete Range")
+                                .build(ctx);
+                                delete_range
+                            })
+                            .with_child({
+                                scale_values = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Scale key values of every selected curve",
+                                        )),
+                                )
+                                .with_text("Scale")
+                                .build(ctx);
+                                scale_values
+                            })
+                            .with_child({
+                                import_curve = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Import keys of the selected curve from a CSV \
+                                            or JSON file",
+                                        )),
+                                )
+                                .with_text("Import Curve")
+                                .build(ctx);
+                                import_curve
+                            })
+                            .with_child({
+                                export_curve = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Export keys of every selected curve to a CSV file",
+                                        )),
+                                )
+                                .with_text("Export Curve")
+                                .build(ctx);
+                                export_curve
+                            })
+                            .with_child({
+                                zoom_to_selection = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Zoom To Selected Keys (F)",
+                                        )),
+                                )
+                                .with_text("Zoom Sel.")
+                                .build(ctx);
+                                zoom_to_selection
+                            })
+                            .with_child({
+                                reset_view = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Frame Every Visible Curve (Home)",
+                                        )),
+                                )
+                                .with_text("Reset View")
+                                .build(ctx);
+                                reset_view
+                            })
+                            .with_child({
+                                pin_reference = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Pin Selected Curves As Read-Only References",
+                                        )),
+                                )
+                                .with_text("Pin Ref.")
+                                .build(ctx);
+                                pin_reference
+                            })
+                            .with_child({
+                                clear_references = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Remove Every Pinned Reference Curve",
+                                        )),
+                                )
+                                .with_text("Clear Refs")
+                                .build(ctx);
+                                clear_references
+                            })
+                            .with_child({
+                                tangent_modes = DropdownListBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_width(70.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Tangent Mode of Selected Keys",
+                                        )),
+                                )
+                                .with_items(
+                                    TangentMode::ALL
+                                        .iter()
+                                        .map(|mode| make_dropdown_list_option(ctx, mode.name()))
+                                        .collect(),
+                                )
+                                .with_selected(
+                                    TangentMode::ALL
+                                        .iter()
+                                        .position(|mode| *mode == TangentMode::default())
+                                        .unwrap_or_default(),
+                                )
+                                .build(ctx);
+                                tangent_modes
+                            })
+                            .with_child({
+                                flatten_tangents = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Set tangents of every selected key to zero",
+                                        )),
+                                )
+                                .with_text("Flatten")
+                                .build(ctx);
+                                flatten_tangents
+                            })
+                            .with_child({
+                                smooth_tangents = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Calculate smooth tangents of every selected key \
+                                            from its neighbour keys",
+                                        )),
+                                )
+                                .with_text("Smooth")
+                                .build(ctx);
+                                smooth_tangents
+                            })
+                            .with_child({
+                                align_first = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Move every selected key to the time of the earliest \
+                                            selected key",
+                                        )),
+                                )
+                                .with_text("Align First")
+                                .build(ctx);
+                                align_first
+                            })
+                            .with_child({
+                                align_last = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Move every selected key to the time of the latest \
+                                            selected key",
+                                        )),
+                                )
+                                .with_text("Align Last")
+                                .build(ctx);
+                                align_last
+                            })
+                            .with_child({
+                                distribute_keys = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Spread selected keys evenly in time between the \
+                                            earliest and the latest selected key",
+                                        )),
+                                )
+                                .with_text("Distribute")
+                                .build(ctx);
+                                distribute_keys
+                            })
+                            .with_child({
+                                cleanup = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Clean up values of selected keys",
+                                        )),
+                                )
+                                .with_text("Cleanup")
+                                .build(ctx);
+                                cleanup
+                            })
+                            .with_child({
+                                key_tags = DropdownListBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_width(90.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Tag That Is Assigned To Selected Keys",
+                                        )),
+                                )
+                                .with_items(
+                                    KEY_TAGS
+                                        .iter()
+                                        .map(|(_, name)| make_dropdown_list_option(ctx, name))
+                                        .collect(),
+                                )
+                                .with_selected(0)
+                                .build(ctx);
+                                key_tags
+                            })
+                            .with_child({
+                                tag_keys = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Assign the tag to every selected key",
+                                        )),
+                                )
+                                .with_text("Tag")
+                                .build(ctx);
+                                tag_keys
+                            })
+                            .with_child({
+                                key_tag_filter = DropdownListBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_width(90.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Show Only Keys With The Tag",
+                                        )),
+                                )
+                                .with_items(
+                                    std::iter::once("All Keys")
+                                        .chain(KEY_TAGS.iter().map(|(_, name)| *name))
+                                        .map(|name| make_dropdown_list_option(ctx, name))
+                                        .collect(),
+                                )
+                                .with_selected(0)
+                                .build(ctx);
+                                key_tag_filter
+                            })
+                            .with_child({
+                                show_tangents = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Show Tangent Handles Of Selected Keys (H)",
+                                        )),
+                                )
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Handles")
+                                    .build(ctx),
+                                )
+                                .checked(Some(true))
+                                .build(ctx);
+                                show_tangents
+                            })
+                            .with_child({
+                                curve_presets = DropdownListBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_width(90.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(ctx, "Curve Preset")),
+                                )
+                                .with_items(
+                                    presets
+                                        .iter()
+                                        .map(|preset| make_dropdown_list_option(ctx, &preset.name))
+                                        .collect(),
+                                )
+                                .with_selected(0)
+                                .build(ctx);
+                                curve_presets
+                            })
+                            .with_child({
+                                apply_preset = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Replace the shape of every selected curve between \
+                                            its first and last keys with the preset",
+                                        )),
+                                )
+                                .with_text("Apply")
+                                .build(ctx);
+                                apply_preset
+                            })
+                            .with_child({
+                                remap_time = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Remap the time of every key of the animation through \
+                                            the preset, for example to make the animation start \
+                                            slowly and end fast. The preset must always go \
+                                            forward.",
+                                        )),
+                                )
+                                .with_text("Remap Time")
+                                .build(ctx);
+                                remap_time
+                            })
+                            .with_child({
+                                save_preset = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Save the shape of the selected curve as a new preset",
+                                        )),
+                                )
+                                .with_text("Save Preset")
+                                .build(ctx);
+                                save_preset
+                            })
+                            .with_child({
+                                root_motion =
+                                    ButtonBuilder::new(WidgetBuilder::new().with_tooltip(
+                                        make_simple_tooltip(ctx, "Root Motion Settings"),
+                                    ))
+                                    .with_text("RM")
+                                    .build(ctx);
+                                root_motion
+                            })
+                            .with_child({
+                                preview = CheckBoxBuilder::new(
+                                    WidgetBuilder::new().with_enabled(false).with_margin(
+                                        Thickness {
+                                            left: 10.0,
+                                            top: 1.0,
+                                            right: 5.0,
+                                            bottom: 1.0,
+                                        },
+                                    ),
+                                )
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Preview")
+                                    .build(ctx),
+                                )
+                                .checked(Some(false))
+                                .build(ctx);
+                                preview
+                            })
+                            .with_child({
+                                preview_loop = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                        ctx,
+                                        "Loop playback in preview mode. The saved Loop flag of \
+                                            the animation is not changed, playback loops within \
+                                            the loop range of the ruler if it is set.",
+                                    )),
+                                )
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Loop Preview")
+                                    .build(ctx),
+                                )
+                                .checked(Some(true))
+                                .build(ctx);
+                                preview_loop
+                            })
+                            .with_child({
+                                play_pause = ButtonBuilder::new(
+                                    WidgetBuilder::new().with_enabled(false).with_margin(
+                                        Thickness {
+                                            left: 1.0,
+                                            top: 1.0,
+                                            right: 1.0,
+                                            bottom: 1.0,
+                                        },
+                                    ),
+                                )
+                                .with_content(
+                                    VectorImageBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_foreground(BRUSH_BRIGHT)
+                                            .with_tooltip(make_simple_tooltip(ctx, "Play/Pause")),
+                                    )
+                                    .with_primitives(vec![
+                                        Primitive::Triangle {
+                                            points: [
+                                                Vector2::new(0.0, 0.0),
+                                                Vector2::new(8.0, 8.0),
+                                                Vector2::new(0.0, 16.0),
+                                            ],
+                                        },
+                                        Primitive::RectangleFilled {
+                                            rect: Rect::new(10.0, 0.0, 4.0, 16.0),
+                                        },
+                                        Primitive::RectangleFilled {
+                                            rect: Rect::new(15.0, 0.0, 4.0, 16.0),
+                                        },
+                                    ])
+                                    .build(ctx),
+                                )
+                                .build(ctx);
+                                play_pause
+                            })
+                            .with_child({
+                                stop = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(ctx, "Stop Playback")),
+                                )
+                                .with_content(
+                                    VectorImageBuilder::new(
+                                        WidgetBuilder::new().with_foreground(BRUSH_BRIGHT),
+                                    )
+                                    .with_primitives(vec![Primitive::RectangleFilled {
+                                        rect: Rect::new(0.0, 0.0, 16.0, 16.0),
+                                    }])
+                                    .build(ctx),
+                                )
+                                .build(ctx);
+                                stop
+                            })
+                            .with_child({
+                                previous_key = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Go To Previous Key (,)",
+                                        )),
+                                )
+                                .with_content(
+                                    VectorImageBuilder::new(
+                                        WidgetBuilder::new().with_foreground(BRUSH_BRIGHT),
+                                    )
+                                    .with_primitives(vec![
+                                        Primitive::RectangleFilled {
+                                            rect: Rect::new(0.0, 0.0, 3.0, 16.0),
+                                        },
+                                        Primitive::Triangle {
+                                            points: [
+                                                Vector2::new(12.0, 0.0),
+                                                Vector2::new(4.0, 8.0),
+                                                Vector2::new(12.0, 16.0),
+                                            ],
+                                        },
+                                    ])
+                                    .build(ctx),
+                                )
+                                .build(ctx);
+                                previous_key
+                            })
+                            .with_child({
+                                next_key = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Go To Next Key (.)",
+                                        )),
+                                )
+                                .with_content(
+                                    VectorImageBuilder::new(
+                                        WidgetBuilder::new().with_foreground(BRUSH_BRIGHT),
+                                    )
+                                    .with_primitives(vec![
+                                        Primitive::Triangle {
+                                            points: [
+                                                Vector2::new(0.0, 0.0),
+                                                Vector2::new(8.0, 8.0),
+                                                Vector2::new(0.0, 16.0),
+                                            ],
+                                        },
+                                        Primitive::RectangleFilled {
+                                            rect: Rect::new(9.0, 0.0, 3.0, 16.0),
+                                        },
+                                    ])
+                                    .build(ctx),
+                                )
+                                .build(ctx);
+                                next_key
+                            })
+                            .with_child({
+                                go_to_time = TextBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_width(50.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Go To Time And Select The Nearest Key.\n\
+                                            Time is in seconds or in frames, depending on \
+                                            the time format.",
+                                        )),
+                                )
+                                .with_vertical_text_alignment(VerticalAlignment::Center)
+                                .build(ctx);
+                                go_to_time
                             }),
                     )
                     .with_orientation(Orientation::Horizontal)
@@ -784,6 +2321,20 @@ impl Toolbar {
         )
         .build(ctx);
 
+        let length_confirmation = MessageBoxBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(100.0))
+                .can_close(false)
+                .can_minimize(false)
+                .open(false)
+                .with_title(WindowTitle::text("Truncate Animation")),
+        )
+        .with_text(
+            "Some keys are located after the new end of the animation and will be removed. \
+            Do you wish to continue?",
+        )
+        .with_buttons(MessageBoxButtons::YesNo)
+        .build(ctx);
+
         let file_selector = FileSelectorBuilder::new(
             WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
                 .open(false)
@@ -791,22 +2342,120 @@ impl Toolbar {
         )
         .with_filter(Filter::new(|p: &Path| {
             if let Some(ext) = p.extension() {
-                // TODO: Here we allow importing only FBX files, but they can contain
-                // multiple animations and it might be good to also add animation selector
-                // that will be used to select a particular animation to import.
-                ext.to_string_lossy().as_ref() == "fbx"
+                // Import adds every animation of a model, reimport uses only the first one.
+                matches!(ext.to_string_lossy().as_ref(), "fbx" | "rgs")
+            } else {
+                p.is_dir()
+            }
+        }))
+        .build(ctx);
+
+        let audio_file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text("Select Reference Audio")),
+        )
+        .with_filter(Filter::new(|p: &Path| {
+            if let Some(ext) = p.extension() {
+                matches!(ext.to_string_lossy().to_lowercase().as_ref(), "wav" | "ogg")
             } else {
                 p.is_dir()
             }
         }))
         .build(ctx);
 
+        let round_decimals;
+        let round_values;
+        let cleanup_popup = PopupBuilder::new(
+            WidgetBuilder::new()
+                .with_width(200.0)
+                .with_height(52.0)
+                .with_visibility(false),
+        )
+        .stays_open(false)
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness::uniform(2.0))
+                    .with_child(
+                        TextBuilder::new(
+                            WidgetBuilder::new()
+                                .with_vertical_alignment(VerticalAlignment::Center)
+                                .on_row(0)
+                                .on_column(0),
+                        )
+                        .with_text("Decimals")
+                        .build(ctx),
+                    )
+                    .with_child({
+                        round_decimals = NumericUpDownBuilder::<f32>::new(
+                            WidgetBuilder::new()
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_tooltip(make_simple_tooltip(
+                                    ctx,
+                                    "Amount of decimal places, zero rounds to the nearest integer",
+                                ))
+                                .on_row(0)
+                                .on_column(1),
+                        )
+                        .with_min_value(0.0)
+                        .with_max_value(Self::MAX_ROUNDING_DECIMALS as f32)
+                        .with_precision(0)
+                        .with_step(1.0)
+                        .with_value(Self::DEFAULT_ROUNDING_DECIMALS as f32)
+                        .build(ctx);
+                        round_decimals
+                    })
+                    .with_child({
+                        round_values = ButtonBuilder::new(
+                            WidgetBuilder::new()
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_tooltip(make_simple_tooltip(
+                                    ctx,
+                                    "Round values of every selected key",
+                                ))
+                                .on_row(1)
+                                .on_column(1),
+                        )
+                        .with_text("Round Values")
+                        .build(ctx);
+                        round_values
+                    }),
+            )
+            .add_column(Column::strict(70.0))
+            .add_column(Column::stretch())
+            .add_row(Row::strict(22.0))
+            .add_row(Row::strict(22.0))
+            .build(ctx),
+        )
+        .build(ctx);
+
         let root_motion_dropdown_area = RootMotionDropdownArea::new(ctx);
+        let scale_values_dialog = ScaleValuesDialog::new(ctx);
+        let curve_import_dialog = CurveImportDialog::new(ctx);
+        let export_file_selector = create_file_selector(
+            ctx,
+            "csv",
+            FileBrowserMode::Save {
+                default_file_name: PathBuf::from("curve.csv"),
+            },
+        );
+        let save_animation_file_selector = create_file_selector(
+            ctx,
+            "anim",
+            FileBrowserMode::Save {
+                default_file_name: PathBuf::from("animation.anim"),
+            },
+        );
+        let load_animation_file_selector = create_file_selector(ctx, "anim", FileBrowserMode::Open);
 
         Self {
             panel,
             play_pause,
             stop,
+            previous_key,
+            next_key,
+            go_to_time,
             speed,
             animations,
             add_animation,
@@ -814,19 +2463,182 @@ impl Toolbar {
             remove_current_animation,
             animation_name,
             preview,
+            preview_loop,
+            loop_preview: true,
             time_slice_start,
             time_slice_end,
+            length,
+            fit_length,
+            length_confirmation,
+            pending_length: None,
             clone_current_animation,
             import,
             reimport,
             node_selector,
             import_file_selector: file_selector,
             selected_import_root: Default::default(),
+            save_animation,
+            save_animation_file_selector,
+            load_animation,
+            load_animation_file_selector,
             looping,
             enabled,
             root_motion,
             root_motion_dropdown_area,
+            snap,
+            frame_rate,
+            show_frames,
+            snap_keys,
+            add_key,
+            key_all,
+            key_interpolation,
+            bake,
+            reverse,
+            normalize,
+            simplify,
+            simplify_tolerance,
+            simplify_preview,
+            key_stats,
+            simplification_tolerance: Self::DEFAULT_SIMPLIFICATION_TOLERANCE,
+            delete_range,
+            scale_values,
+            scale_values_dialog,
+            import_curve,
+            curve_import_dialog,
+            export_curve,
+            export_file_selector,
+            zoom_to_selection,
+            reset_view,
+            pin_reference,
+            clear_references,
+            tangent_modes,
+            flatten_tangents,
+            smooth_tangents,
+            align_first,
+            align_last,
+            distribute_keys,
+            cleanup,
+            cleanup_popup,
+            round_values,
+            round_decimals,
+            rounding_decimals: Self::DEFAULT_ROUNDING_DECIMALS,
+            key_tags,
+            tag_keys,
+            key_tag_filter,
+            selected_key_tag: CurveKeyTag::default(),
+            show_tangents,
+            curve_presets,
+            apply_preset,
+            remap_time,
+            save_preset,
+            presets,
+            selected_preset: Some(0),
             import_mode: ImportMode::Import,
+            snapping: false,
+            fps: Self::DEFAULT_FPS,
+            time_format: TimeFormat::Seconds,
+            tangent_mode: Default::default(),
+            snap_to_keys: false,
+            onion_skin,
+            onion_skin_delta,
+            onion_skinning: false,
+            isolate_curve,
+            isolate_selected_curve: false,
+            onion_skin_offset: Self::DEFAULT_ONION_SKIN_OFFSET,
+            euler_view,
+            show_euler_angles: false,
+            compact_ruler,
+            is_ruler_compact: false,
+            load_audio,
+            audio_file_selector,
+            clear_audio,
+            play_audio,
+            play_audio_in_preview: true,
+            auto_key,
+            auto_key_label,
+            auto_key_enabled: false,
+        }
+    }
+
+    pub const DEFAULT_FPS: f32 = 30.0;
+
+    pub const DEFAULT_ONION_SKIN_OFFSET: f32 = 0.1;
+    pub const DEFAULT_SIMPLIFICATION_TOLERANCE: f32 = 0.01;
+    pub const DEFAULT_ROUNDING_DECIMALS: u32 = 3;
+    pub const MAX_ROUNDING_DECIMALS: u32 = 6;
+
+    /// Shows interpolation of new keys from the settings.
+    pub fn sync_key_interpolation(&self, settings: &AnimationEditorSettings, ui: &UserInterface) {
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(
+                self.key_interpolation,
+                MessageDirection::ToWidget,
+                KeyInterpolation::ALL
+                    .iter()
+                    .position(|i| *i == settings.new_key_interpolation),
+            ),
+        );
+    }
+
+    /// Shows visibility of tangent handles from the settings.
+    pub fn sync_tangent_visibility(&self, settings: &AnimationEditorSettings, ui: &UserInterface) {
+        send_sync_message(
+            ui,
+            CheckBoxMessage::checked(
+                self.show_tangents,
+                MessageDirection::ToWidget,
+                Some(settings.show_tangent_handles),
+            ),
+        );
+    }
+
+    /// Refreshes the list of curve presets, built-in presets always go first.
+    pub fn sync_presets(&mut self, settings: &AnimationEditorSettings, ui: &mut UserInterface) {
+        let presets = builtin_presets()
+            .into_iter()
+            .chain(settings.curve_presets.iter().cloned())
+            .collect::<Vec<_>>();
+
+        if presets == self.presets {
+            return;
+        }
+
+        self.presets = presets;
+        self.selected_preset = self
+            .selected_preset
+            .filter(|index| *index < self.presets.len());
+
+        let items = self
+            .presets
+            .iter()
+            .map(|preset| make_dropdown_list_option(&mut ui.build_ctx(), &preset.name))
+            .collect();
+        send_sync_message(
+            ui,
+            DropdownListMessage::items(self.curve_presets, MessageDirection::ToWidget, items),
+        );
+        send_sync_message(
+            ui,
+            DropdownListMessage::selection(
+                self.curve_presets,
+                MessageDirection::ToWidget,
+                self.selected_preset,
+            ),
+        );
+    }
+
+    /// Returns `true` if root motion of the animation is extracted in preview mode.
+    pub fn extract_root_motion_in_preview(&self) -> bool {
+        self.root_motion_dropdown_area.extract_in_preview
+    }
+
+    /// Returns time interval between two adjacent frames, if snapping is enabled.
+    pub fn snap_step(&self) -> Option<f32> {
+        if self.snapping {
+            Some(1.0 / self.fps)
+        } else {
+            None
         }
     }
 
@@ -838,18 +2650,39 @@ impl Toolbar {
         ui: &mut UserInterface,
         animation_player_handle: Handle<Node>,
         animation_player: &AnimationPlayer,
+        saved_animation: Option<&Animation>,
         editor_scene: &EditorScene,
         selection: &AnimationSelection,
+        locked_curves: &FxHashSet<Uuid>,
+        editor_settings: &mut AnimationEditorSettings,
     ) -> ToolbarAction {
         self.root_motion_dropdown_area.handle_ui_message(
             message,
             scene,
             sender,
             ui,
-            animation_player,
+            saved_animation.or_else(|| animation_player.animations().try_get(selection.animation)),
             editor_scene,
             selection,
         );
+        self.scale_values_dialog.handle_ui_message(
+            message,
+            sender,
+            ui,
+            animation_player,
+            selection,
+            locked_curves,
+        );
+        self.curve_import_dialog.handle_ui_message(
+            message,
+            sender,
+            ui,
+            animation_player,
+            selection,
+            locked_curves,
+            self.fps,
+            key_collision(editor_settings),
+        );
 
         if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
             if message.destination() == self.animations
@@ -870,12 +2703,87 @@ impl Toolbar {
                     editor_scene.selection.clone(),
                 ));
                 return ToolbarAction::SelectAnimation(*animation);
+            } else if message.destination() == self.tangent_modes
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(mode) = TangentMode::ALL.get(*index) {
+                    self.tangent_mode = *mode;
+                    return ToolbarAction::SetTangentMode(*mode);
+                }
+            } else if message.destination() == self.curve_presets
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.selected_preset = Some(*index);
+            } else if message.destination() == self.key_tags
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some((tag, _)) = KEY_TAGS.get(*index) {
+                    self.selected_key_tag = *tag;
+                }
+            } else if message.destination() == self.key_tag_filter
+                && message.direction() == MessageDirection::FromWidget
+            {
+                // The first item shows every key.
+                let filter = index
+                    .checked_sub(1)
+                    .and_then(|i| KEY_TAGS.get(i))
+                    .map(|(tag, _)| *tag);
+                return ToolbarAction::KeyTagFilterChanged(filter);
+            } else if message.destination() == self.key_interpolation
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(interpolation) = KeyInterpolation::ALL.get(*index) {
+                    editor_settings.new_key_interpolation = *interpolation;
+                    return ToolbarAction::SetNewKeyKind(interpolation.key_kind());
+                }
             }
         } else if let Some(ButtonMessage::Click) = message.data() {
             if message.destination() == self.play_pause {
                 return ToolbarAction::PlayPause;
             } else if message.destination() == self.stop {
                 return ToolbarAction::Stop;
+            } else if message.destination() == self.flatten_tangents {
+                return ToolbarAction::FlattenTangents;
+            } else if message.destination() == self.smooth_tangents {
+                return ToolbarAction::AutoSmoothTangents;
+            } else if message.destination() == self.align_first {
+                return ToolbarAction::AlignKeys(AlignTarget::First);
+            } else if message.destination() == self.align_last {
+                return ToolbarAction::AlignKeys(AlignTarget::Last);
+            } else if message.destination() == self.distribute_keys {
+                return ToolbarAction::DistributeKeys;
+            } else if message.destination() == self.cleanup {
+                ui.send_message(PopupMessage::placement(
+                    self.cleanup_popup,
+                    MessageDirection::ToWidget,
+                    Placement::LeftBottom(self.cleanup),
+                ));
+                ui.send_message(PopupMessage::open(
+                    self.cleanup_popup,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.round_values {
+                ui.send_message(PopupMessage::close(
+                    self.cleanup_popup,
+                    MessageDirection::ToWidget,
+                ));
+                return ToolbarAction::RoundKeyValues(self.rounding_decimals);
+            } else if message.destination() == self.tag_keys {
+                return ToolbarAction::SetKeyTag(self.selected_key_tag);
+            } else if message.destination() == self.previous_key {
+                return ToolbarAction::PreviousKey;
+            } else if message.destination() == self.next_key {
+                return ToolbarAction::NextKey;
+            } else if message.destination() == self.zoom_to_selection {
+                return ToolbarAction::ZoomToSelection;
+            } else if message.destination() == self.reset_view {
+                return ToolbarAction::ResetView;
+            } else if message.destination() == self.delete_range {
+                return ToolbarAction::DeleteKeysInRange;
+            } else if message.destination() == self.pin_reference {
+                return ToolbarAction::PinReference;
+            } else if message.destination() == self.clear_references {
+                return ToolbarAction::ClearReferences;
             } else if message.destination() == self.root_motion {
                 ui.send_message(PopupMessage::placement(
                     self.root_motion_dropdown_area.popup,
@@ -910,34 +2818,305 @@ impl Toolbar {
                     sender.do_scene_command(CommandGroup::from(group));
                 }
             } else if message.destination() == self.rename_current_animation {
-                sender.do_scene_command(SetAnimationNameCommand {
-                    node_handle: animation_player_handle,
-                    animation_handle: selection.animation,
-                    value: ui
+                if let Some(animation) = animation_player.animations().try_get(selection.animation)
+                {
+                    let new_name = ui
                         .node(self.animation_name)
                         .query_component::<TextBox>()
                         .unwrap()
-                        .text(),
-                });
+                        .text();
+
+                    if new_name.trim().is_empty() {
+                        Log::warn("Animation name cannot be empty!");
+                    } else if new_name != animation.name() {
+                        sender.do_scene_command(SetAnimationNameCommand {
+                            node_handle: animation_player_handle,
+                            animation_handle: selection.animation,
+                            value: new_name,
+                        });
+                    }
+                }
             } else if message.destination() == self.add_animation {
+                let mut name = ui
+                    .node(self.animation_name)
+                    .query_component::<TextBox>()
+                    .unwrap()
+                    .text();
+                if name.is_empty() {
+                    name = "New Animation".to_string();
+                }
+
                 let mut animation = Animation::default();
-                animation.set_name(
-                    ui.node(self.animation_name)
-                        .query_component::<TextBox>()
-                        .unwrap()
-                        .text(),
-                );
+                animation.set_name(name);
                 sender
                     .do_scene_command(AddAnimationCommand::new(animation_player_handle, animation));
-            } else if message.destination() == self.clone_current_animation {
+            } else if message.destination() == self.add_key {
+                if let Some(animation) = animation_player.animations().try_get(selection.animation)
+                {
+                    let time = animation.time_position();
+
+                    let curves = unlocked_curves(
+                        selected_curves(animation, selection),
+                        locked_curves,
+                        sender,
+                    );
+                    let commands = curves
+                        .into_iter()
+                        .map(|curve| {
+                            SceneCommand::new(InsertKeyframeCommand::new(
+                                animation_player_handle,
+                                selection.animation,
+                                curve.id(),
+                                time,
+                                curve.value_at(time),
+                                editor_settings.new_key_interpolation.key_kind(),
+                            ))
+                        })
+                        .collect::<Vec<_>>();
+
+                    if !commands.is_empty() {
+                        sender.do_scene_command(CommandGroup::from(commands));
+                    }
+                }
+            } else if message.destination() == self.key_all {
+                if let Some(animation) = animation_player.animations().try_get(selection.animation)
+                {
+                    let time = animation.time_position();
+
+                    let mut values = Vec::new();
+                    for track in animation.tracks() {
+                        // Tracks of deleted nodes have nothing to sample.
+                        if let Some(node) = scene.graph.try_get(track.target()) {
+                            values.extend(current_key_values(track, node, time));
+                        }
+                    }
+
+                    let count = values.len();
+                    values.retain(|(curve, _)| !locked_curves.contains(curve));
+                    if values.len() != count {
+                        report_locked_edit(sender);
+                    }
+
+                    if !values.is_empty() {
+                        sender.do_scene_command(InsertKeyframesCommand::new(
+                            animation_player_handle,
+                            selection.animation,
+                            values,
+                            time,
+                            editor_settings.new_key_interpolation.key_kind(),
+                        ));
+                    }
+                }
+            } else if message.destination() == self.bake {
+                if let Some(animation) = animation_player.animations().try_get(selection.animation)
+                {
+                    let curves = unlocked_curves(
+                        selected_curves(animation, selection),
+                        locked_curves,
+                        sender,
+                    );
+                    let commands = curves
+                        .into_iter()
+                        .map(|curve| {
+                            SceneCommand::new(BakeCurveCommand::new(
+                                animation_player_handle,
+                                selection.animation,
+                                curve.id(),
+                                self.fps,
+                            ))
+                        })
+                        .collect::<Vec<_>>();
+
+                    if !commands.is_empty() {
+                        sender.do_scene_command(CommandGroup::from(commands));
+                    }
+                }
+            } else if message.destination() == self.scale_values {
+                self.scale_values_dialog.open(ui);
+            } else if message.destination() == self.import_curve {
+                self.curve_import_dialog.open(ui);
+            } else if message.destination() == self.load_audio {
+                ui.send_message(WindowMessage::open_modal(
+                    self.audio_file_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+                ui.send_message(FileSelectorMessage::root(
+                    self.audio_file_selector,
+                    MessageDirection::ToWidget,
+                    Some(std::env::current_dir().unwrap()),
+                ));
+            } else if message.destination() == self.clear_audio {
+                return ToolbarAction::ClearAudioReference;
+            } else if message.destination() == self.export_curve {
+                ui.send_message(WindowMessage::open_modal(
+                    self.export_file_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+                ui.send_message(FileSelectorMessage::root(
+                    self.export_file_selector,
+                    MessageDirection::ToWidget,
+                    Some(std::env::current_dir().unwrap()),
+                ));
+            } else if message.destination() == self.reverse {
                 if let Some(animation) = animation_player.animations().try_get(selection.animation)
                 {
-                    let mut animation_clone = animation.clone();
-                    animation_clone.set_name(format!("{} Copy", animation.name()));
+                    let curves = unlocked_curves(
+                        selected_curves(animation, selection),
+                        locked_curves,
+                        sender,
+                    );
+                    let commands = curves
+                        .into_iter()
+                        .map(|curve| {
+                            SceneCommand::new(ReverseCurveCommand::new(
+                                animation_player_handle,
+                                selection.animation,
+                                curve.id(),
+                            ))
+                        })
+                        .collect::<Vec<_>>();
 
-                    sender.do_scene_command(AddAnimationCommand::new(
+                    if !commands.is_empty() {
+                        sender.do_scene_command(CommandGroup::from(commands));
+                    }
+                }
+            } else if message.destination() == self.simplify {
+                if let Some(animation) = animation_player.animations().try_get(selection.animation)
+                {
+                    let curves = unlocked_curves(
+                        selected_curves(animation, selection),
+                        locked_curves,
+                        sender,
+                    );
+                    let commands = curves
+                        .into_iter()
+                        .map(|curve| {
+                            SceneCommand::new(SimplifyCurveCommand::new(
+                                animation_player_handle,
+                                selection.animation,
+                                curve.id(),
+                                self.simplification_tolerance,
+                            ))
+                        })
+                        .collect::<Vec<_>>();
+
+                    if !commands.is_empty() {
+                        sender.do_scene_command(CommandGroup::from(commands));
+                    }
+                }
+            } else if message.destination() == self.normalize {
+                if let Some(animation) = animation_player.animations().try_get(selection.animation)
+                {
+                    let curves = unlocked_curves(
+                        selected_curves(animation, selection),
+                        locked_curves,
+                        sender,
+                    );
+                    let commands = curves
+                        .into_iter()
+                        .map(|curve| {
+                            SceneCommand::new(NormalizeCurveCommand::new(
+                                animation_player_handle,
+                                selection.animation,
+                                curve.id(),
+                            ))
+                        })
+                        .collect::<Vec<_>>();
+
+                    if !commands.is_empty() {
+                        sender.do_scene_command(CommandGroup::from(commands));
+                    }
+                }
+            } else if message.destination() == self.apply_preset {
+                if let (Some(animation), Some(preset)) = (
+                    animation_player.animations().try_get(selection.animation),
+                    self.selected_preset.and_then(|i| self.presets.get(i)),
+                ) {
+                    let curves = unlocked_curves(
+                        selected_curves(animation, selection),
+                        locked_curves,
+                        sender,
+                    );
+                    let commands = curves
+                        .into_iter()
+                        .filter_map(|curve| apply_preset(curve, preset))
+                        .map(|curve| {
+                            SceneCommand::new(ReplaceTrackCurveCommand {
+                                animation_player: animation_player_handle,
+                                animation: selection.animation,
+                                curve,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+
+                    if commands.is_empty() {
+                        Log::warn("Select at least one curve with two or more keys!");
+                    } else {
+                        sender.do_scene_command(CommandGroup::from(commands));
+                    }
+                }
+            } else if message.destination() == self.remap_time {
+                // Remapping moves keys of every track, so a single locked track prevents it.
+                if !locked_curves.is_empty() {
+                    report_locked_edit(sender);
+                } else if let Some(preset) = self.selected_preset.and_then(|i| self.presets.get(i))
+                {
+                    match RemapAnimationTimeCommand::new(
+                        animation_player_handle,
+                        selection.animation,
+                        preset,
+                    ) {
+                        Ok(command) => sender.do_scene_command(command),
+                        Err(err) => Log::err(format!(
+                            "Unable to remap time using {} preset. {}",
+                            preset.name, err
+                        )),
+                    }
+                }
+            } else if message.destination() == self.save_preset {
+                let preset = animation_player
+                    .animations()
+                    .try_get(selection.animation)
+                    .and_then(|animation| selected_curves(animation, selection).next())
+                    .and_then(|curve| {
+                        make_preset(
+                            format!("Custom {}", editor_settings.curve_presets.len() + 1),
+                            curve,
+                        )
+                    });
+
+                match preset {
+                    Some(preset) => {
+                        editor_settings.curve_presets.push(preset);
+                        self.selected_preset = Some(self.presets.len());
+                        self.sync_presets(editor_settings, ui);
+                    }
+                    None => Log::warn(
+                        "Select a curve whose first and last keys differ in time and value!",
+                    ),
+                }
+            } else if message.destination() == self.fit_length {
+                if let Some(animation) = animation_player.animations().try_get(selection.animation)
+                {
+                    if let Some(last_key_time) = last_key_time(animation) {
+                        let length = (last_key_time - animation.time_slice().start).max(0.0);
+                        if length != animation.length() {
+                            sender.do_scene_command(SetAnimationLengthCommand::new(
+                                animation_player_handle,
+                                selection.animation,
+                                length,
+                            ));
+                        }
+                    }
+                }
+            } else if message.destination() == self.clone_current_animation {
+                if let Some(animation) = animation_player.animations().try_get(selection.animation)
+                {
+                    sender.do_scene_command(DuplicateAnimationCommand::new(
                         animation_player_handle,
-                        animation_clone,
+                        animation,
                     ));
                 }
             }
@@ -949,6 +3128,8 @@ impl Toolbar {
                     } else {
                         ToolbarAction::LeavePreviewMode
                     };
+                } else if message.destination() == self.preview_loop {
+                    self.loop_preview = *checked;
                 } else if message.destination() == self.looping {
                     sender.do_scene_command(SetAnimationLoopingCommand {
                         node_handle: animation_player_handle,
@@ -961,6 +3142,46 @@ impl Toolbar {
                         animation_handle: selection.animation,
                         value: *checked,
                     });
+                } else if message.destination() == self.snap {
+                    self.snapping = *checked;
+                    return ToolbarAction::TimeGridChanged;
+                } else if message.destination() == self.snap_keys {
+                    self.snap_to_keys = *checked;
+                    return ToolbarAction::TimeGridChanged;
+                } else if message.destination() == self.onion_skin {
+                    self.onion_skinning = *checked;
+                } else if message.destination() == self.show_tangents {
+                    editor_settings.show_tangent_handles = *checked;
+                    return ToolbarAction::ShowTangents(*checked);
+                } else if message.destination() == self.isolate_curve {
+                    self.isolate_selected_curve = *checked;
+                } else if message.destination() == self.euler_view {
+                    self.show_euler_angles = *checked;
+                    return ToolbarAction::RotationViewChanged;
+                } else if message.destination() == self.compact_ruler {
+                    self.is_ruler_compact = *checked;
+                    return ToolbarAction::RulerDensityChanged;
+                } else if message.destination() == self.play_audio {
+                    self.play_audio_in_preview = *checked;
+                } else if message.destination() == self.auto_key {
+                    self.auto_key_enabled = *checked;
+                    ui.send_message(WidgetMessage::foreground(
+                        self.auto_key_label,
+                        MessageDirection::ToWidget,
+                        if *checked { AUTO_KEY_BRUSH } else { BRUSH_TEXT },
+                    ));
+                } else if message.destination() == self.show_frames {
+                    self.time_format = if *checked {
+                        TimeFormat::Frames
+                    } else {
+                        TimeFormat::Seconds
+                    };
+                    if let Some(animation) =
+                        animation_player.animations().try_get(selection.animation)
+                    {
+                        self.sync_length(animation, ui);
+                    }
+                    return ToolbarAction::TimeGridChanged;
                 }
             }
         } else if let Some(NumericUpDownMessage::<f32>::Value(value)) = message.data() {
@@ -989,6 +3210,85 @@ impl Toolbar {
                         animation_handle: selection.animation,
                         value: *value,
                     });
+                } else if message.destination() == self.length {
+                    if let Some(animation) =
+                        animation_player.animations().try_get(selection.animation)
+                    {
+                        let length = match self.time_format {
+                            TimeFormat::Seconds => *value,
+                            TimeFormat::Frames => *value / self.fps,
+                        };
+
+                        let end = animation.time_slice().start + length;
+                        let has_keys_after_end = animation
+                            .tracks()
+                            .iter()
+                            .flat_map(|t| t.data_container().curves_ref())
+                            .any(|c| c.keys().iter().any(|k| k.location() > end));
+
+                        if has_keys_after_end {
+                            self.pending_length = Some(length);
+                            ui.send_message(MessageBoxMessage::open(
+                                self.length_confirmation,
+                                MessageDirection::ToWidget,
+                                None,
+                                None,
+                            ));
+                        } else if length != animation.length() {
+                            sender.do_scene_command(SetAnimationLengthCommand::new(
+                                animation_player_handle,
+                                selection.animation,
+                                length,
+                            ));
+                        }
+                    }
+                } else if message.destination() == self.simplify_tolerance {
+                    self.simplification_tolerance = value.max(0.0);
+                    if let Some(animation) =
+                        animation_player.animations().try_get(selection.animation)
+                    {
+                        self.sync_simplify_preview(animation, selection, ui);
+                    }
+                } else if message.destination() == self.round_decimals {
+                    self.rounding_decimals =
+                        value.round().clamp(0.0, Self::MAX_ROUNDING_DECIMALS as f32) as u32;
+                } else if message.destination() == self.onion_skin_delta {
+                    self.onion_skin_offset = value.max(0.001);
+                } else if message.destination() == self.frame_rate {
+                    self.fps = value.max(1.0);
+                    if let Some(animation) =
+                        animation_player.animations().try_get(selection.animation)
+                    {
+                        self.sync_length(animation, ui);
+                    }
+                    return ToolbarAction::TimeGridChanged;
+                }
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data() {
+            if message.destination() == self.go_to_time
+                && message.direction() == MessageDirection::FromWidget
+            {
+                return go_to_time_action(text, self.time_format, self.fps);
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.audio_file_selector {
+                return ToolbarAction::SetAudioReference(path.clone());
+            }
+        } else if let Some(MessageBoxMessage::Close(result)) = message.data() {
+            if message.destination() == self.length_confirmation {
+                if let Some(length) = self.pending_length.take() {
+                    if *result == MessageBoxResult::Yes {
+                        sender.do_scene_command(SetAnimationLengthCommand::new(
+                            animation_player_handle,
+                            selection.animation,
+                            length,
+                        ));
+                    } else if let Some(animation) =
+                        animation_player.animations().try_get(selection.animation)
+                    {
+                        // Revert the field to actual length.
+                        self.sync_length(animation, ui);
+                    }
                 }
             }
         }
@@ -1029,6 +3329,25 @@ impl Toolbar {
                 } else {
                     self.import_mode = ImportMode::Import;
                 }
+            } else if message.destination() == self.save_animation
+                || message.destination() == self.load_animation
+            {
+                let file_selector = if message.destination() == self.save_animation {
+                    self.save_animation_file_selector
+                } else {
+                    self.load_animation_file_selector
+                };
+
+                ui.send_message(WindowMessage::open_modal(
+                    file_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+                ui.send_message(FileSelectorMessage::root(
+                    file_selector,
+                    MessageDirection::ToWidget,
+                    Some(std::env::current_dir().unwrap()),
+                ));
             }
         } else if let Some(NodeSelectorMessage::Selection(selected_nodes)) = message.data() {
             if message.destination() == self.node_selector
@@ -1056,6 +3375,19 @@ impl Toolbar {
                         let mut animations = model
                             .retarget_animations_directly(self.selected_import_root, &scene.graph);
 
+                        // Tracks of unmatched nodes are kept unbound, so they could be rebound
+                        // manually instead of losing the whole animation.
+                        let unbound = unbound_track_targets(&model, &animations);
+                        if !unbound.is_empty() {
+                            Log::warn(format!(
+                                "Tracks of {} node(s) were not matched by name in {}: {}. Use \
+                                Set Target... in the track list to rebind them.",
+                                unbound.len(),
+                                path.display(),
+                                unbound.join(", ")
+                            ));
+                        }
+
                         let file_stem = path
                             .file_stem()
                             .map(|p| p.to_string_lossy().to_string())
@@ -1089,7 +3421,16 @@ impl Toolbar {
                                 if let Selection::Animation(ref selection) = editor_scene.selection
                                 {
                                     if animations.len() > 1 {
-                                        Log::warn("More than one animation found! Only first will be used");
+                                        Log::warn(format!(
+                                            "More than one animation found in {}! Only first \
+                                            will be used, skipped: {}. Use Import to add them.",
+                                            path.display(),
+                                            animations[1..]
+                                                .iter()
+                                                .map(|a| a.name().to_owned())
+                                                .collect::<Vec<_>>()
+                                                .join(", ")
+                                        ));
                                     }
 
                                     if !animations.is_empty() {
@@ -1109,6 +3450,57 @@ impl Toolbar {
                         err
                     )),
                 }
+            } else if message.destination() == self.load_animation_file_selector {
+                match load_animation(path, &scene.graph, editor_scene.scene_content_root) {
+                    Ok((animation, unmatched)) => {
+                        if !unmatched.is_empty() {
+                            Log::warn(format!(
+                                "Target nodes of {} track(s) were not found by name: {}. Use \
+                                Set Target... in the track list to rebind them.",
+                                unmatched.len(),
+                                unmatched.join(", ")
+                            ));
+                        }
+
+                        sender.do_scene_command(AddAnimationCommand::new(
+                            animation_player_handle,
+                            animation,
+                        ));
+                    }
+                    Err(err) => Log::err(format!(
+                        "Failed to load {} animation file! Reason: {:?}",
+                        path.display(),
+                        err
+                    )),
+                }
+            } else if let Some((animation, selection)) =
+                selected_animation(scene, animation_player_handle, editor_scene)
+            {
+                if message.destination() == self.save_animation_file_selector {
+                    // A copy of the animation is saved, the scene stays untouched.
+                    if let Err(err) = save_animation(path, animation, &scene.graph) {
+                        Log::err(format!(
+                            "Failed to save animation to {}! Reason: {:?}",
+                            path.display(),
+                            err
+                        ));
+                    }
+                } else if message.destination() == self.export_file_selector {
+                    // Keys are taken directly from the tracks, export doesn't change anything.
+                    let curves = selected_curves(animation, selection).collect::<Vec<_>>();
+
+                    if curves.is_empty() {
+                        Log::warn("Select at least one curve to export!");
+                    } else if let Err(err) =
+                        export_curves(path, &curves, self.time_format, self.fps)
+                    {
+                        Log::err(format!(
+                            "Failed to export curves to {}! Reason: {:?}",
+                            path.display(),
+                            err
+                        ));
+                    }
+                }
             }
         }
     }
@@ -1119,6 +3511,63 @@ impl Toolbar {
             MessageDirection::ToWidget,
             vec![],
         ));
+
+        ui.send_message(TextMessage::text(
+            self.key_stats,
+            MessageDirection::ToWidget,
+            Default::default(),
+        ));
+
+        // There's no animation player to add an animation to.
+        ui.send_message(WidgetMessage::enabled(
+            self.add_animation,
+            MessageDirection::ToWidget,
+            false,
+        ));
+    }
+
+    /// Shows length of the animation in current time format.
+    fn sync_length(&self, animation: &Animation, ui: &UserInterface) {
+        let (length, precision) = match self.time_format {
+            TimeFormat::Seconds => (animation.length(), 3),
+            TimeFormat::Frames => ((animation.length() * self.fps).round(), 0),
+        };
+
+        send_sync_message(
+            ui,
+            NumericUpDownMessage::<f32>::precision(
+                self.length,
+                MessageDirection::ToWidget,
+                precision,
+            ),
+        );
+        send_sync_message(
+            ui,
+            NumericUpDownMessage::value(self.length, MessageDirection::ToWidget, length),
+        );
+    }
+
+    /// Shows how many keys would be removed from the selected curves by simplification.
+    fn sync_simplify_preview(
+        &self,
+        animation: &Animation,
+        selection: &AnimationSelection,
+        ui: &UserInterface,
+    ) {
+        let removed = selected_curves(animation, selection)
+            .map(|curve| {
+                curve.keys().len()
+                    - simplify_curve(curve, self.simplification_tolerance)
+                        .keys()
+                        .len()
+            })
+            .sum::<usize>();
+
+        ui.send_message(TextMessage::text(
+            self.simplify_preview,
+            MessageDirection::ToWidget,
+            format!("-{}", removed),
+        ));
     }
 
     pub fn on_preview_mode_changed(&self, ui: &UserInterface, in_preview_mode: bool) {
@@ -1129,6 +3578,16 @@ impl Toolbar {
                 in_preview_mode,
             ));
         }
+
+        // Previewed animation must not be removed while it is playing, and it must not be saved
+        // since preview changes it.
+        for widget in [self.remove_current_animation, self.save_animation] {
+            ui.send_message(WidgetMessage::enabled(
+                widget,
+                MessageDirection::ToWidget,
+                !in_preview_mode,
+            ));
+        }
     }
 
     pub fn sync_to_model(
@@ -1138,9 +3597,18 @@ impl Toolbar {
         scene: &Scene,
         ui: &mut UserInterface,
         in_preview_mode: bool,
+        saved_animation: Option<&Animation>,
     ) {
-        self.root_motion_dropdown_area
-            .sync_to_model(animation_player, selection, scene, ui);
+        self.root_motion_dropdown_area.sync_to_model(
+            saved_animation.or_else(|| animation_player.animations().try_get(selection.animation)),
+            scene,
+            ui,
+        );
+
+        send_sync_message(
+            ui,
+            WidgetMessage::enabled(self.add_animation, MessageDirection::ToWidget, true),
+        );
 
         let new_items = animation_player
             .animations()
@@ -1168,8 +3636,15 @@ impl Toolbar {
         );
 
         let mut selected_animation_valid = false;
+        let mut has_keys = false;
+        let mut key_stats_text = String::new();
         if let Some(animation) = animation_player.animations().try_get(selection.animation) {
             selected_animation_valid = true;
+            has_keys = last_key_time(animation).is_some();
+
+            let (keys, bytes) = key_stats(animation);
+            key_stats_text = format!("{} Keys (~{})", keys, format_bytes(bytes));
+
             send_sync_message(
                 ui,
                 TextMessage::text(
@@ -1222,12 +3697,16 @@ impl Toolbar {
                 ),
             );
 
+            self.sync_length(animation, ui);
+            self.sync_simplify_preview(animation, selection, ui);
+
             send_sync_message(
                 ui,
                 CheckBoxMessage::checked(
                     self.looping,
                     MessageDirection::ToWidget,
-                    Some(animation.is_loop()),
+                    // The previewed animation loops by the preview toggle.
+                    Some(saved_animation.unwrap_or(animation).is_loop()),
                 ),
             );
 
@@ -1241,17 +3720,26 @@ impl Toolbar {
             );
         }
 
+        send_sync_message(
+            ui,
+            TextMessage::text(self.key_stats, MessageDirection::ToWidget, key_stats_text),
+        );
+
         for widget in [
             self.preview,
+            self.preview_loop,
             self.speed,
             self.rename_current_animation,
-            self.remove_current_animation,
             self.time_slice_start,
             self.time_slice_end,
+            self.length,
             self.clone_current_animation,
             self.looping,
             self.enabled,
             self.root_motion,
+            self.previous_key,
+            self.next_key,
+            self.go_to_time,
         ] {
             send_sync_message(
                 ui,
@@ -1273,5 +3761,50 @@ impl Toolbar {
                 ),
             );
         }
+
+        send_sync_message(
+            ui,
+            WidgetMessage::enabled(self.fit_length, MessageDirection::ToWidget, has_keys),
+        );
+
+        for widget in [self.remove_current_animation, self.save_animation] {
+            send_sync_message(
+                ui,
+                WidgetMessage::enabled(
+                    widget,
+                    MessageDirection::ToWidget,
+                    selected_animation_valid && !in_preview_mode,
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_go_to_time_action() {
+        assert!(matches!(
+            go_to_time_action("1.5", TimeFormat::Seconds, 30.0),
+            ToolbarAction::GoToTime(time) if time == 1.5
+        ));
+        assert!(matches!(
+            go_to_time_action(" 2s ", TimeFormat::Seconds, 30.0),
+            ToolbarAction::GoToTime(time) if time == 2.0
+        ));
+        assert!(matches!(
+            go_to_time_action("15", TimeFormat::Frames, 30.0),
+            ToolbarAction::GoToTime(time) if time == 0.5
+        ));
+        assert!(matches!(
+            go_to_time_action("abc", TimeFormat::Seconds, 30.0),
+            ToolbarAction::None
+        ));
+        assert!(matches!(
+            go_to_time_action("", TimeFormat::Frames, 30.0),
+            ToolbarAction::None
+        ));
     }
 }
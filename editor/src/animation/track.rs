@@ -3,8 +3,12 @@
 use crate::{
     animation::{
         command::{
-            AddTrackCommand, RemoveTrackCommand, SetTrackEnabledCommand, SetTrackTargetCommand,
+            AddTrackCommand, ConvertTrackSpaceCommand, DuplicateTrackCommand, OffsetTrackCommand,
+            RemoveTrackCommand, ReorderTrackCommand, RetargetTrackCommand, SetTrackEnabledCommand,
+            TrackSpace,
         },
+        euler::EULER_COMPONENT_NAMES,
+        ruler::TimeFormat,
         selection::{AnimationSelection, SelectedEntity},
     },
     gui::make_image_button_with_tooltip,
@@ -20,6 +24,8 @@ use crate::{
         EditorScene, Selection,
     },
     send_sync_message, utils,
+    world::graph::{item::SceneItem, selection::GraphSelection},
+    Message,
 };
 use fyrox::core::reflect::Reflect;
 use fyrox::{
@@ -33,6 +39,7 @@ use fyrox::{
         algebra::{UnitQuaternion, Vector2, Vector3, Vector4},
         color::Color,
         log::Log,
+        math::Rect,
         pool::Handle,
         reflect::ResolvePath,
         uuid::Uuid,
@@ -40,26 +47,31 @@ use fyrox::{
     },
     fxhash::{FxHashMap, FxHashSet},
     gui::{
+        border::BorderBuilder,
         brush::Brush,
         button::{ButtonBuilder, ButtonMessage},
         check_box::{CheckBoxBuilder, CheckBoxMessage},
         define_constructor,
         draw::DrawingContext,
+        formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
         image::ImageBuilder,
         menu::MenuItemMessage,
-        message::{MessageDirection, OsEvent, UiMessage},
-        popup::PopupBuilder,
+        message::{MessageDirection, MouseButton, OsEvent, UiMessage},
+        numeric::{NumericUpDown, NumericUpDownBuilder, NumericUpDownMessage},
+        popup::{Placement, PopupBuilder, PopupMessage},
+        scroll_bar::{ScrollBarBuilder, ScrollBarMessage},
         scroll_viewer::ScrollViewerBuilder,
         stack_panel::StackPanelBuilder,
-        text::{Text, TextBuilder, TextMessage},
-        text_box::{TextBoxBuilder, TextCommitMode},
-        tree::{Tree, TreeBuilder, TreeMessage, TreeRootBuilder, TreeRootMessage},
+        text::{TextBuilder, TextMessage},
+        text_box::{TextBox, TextBoxBuilder, TextCommitMode},
+        tree::{Tree, TreeBuilder, TreeMessage, TreeRoot, TreeRootBuilder, TreeRootMessage},
         utils::{make_cross, make_simple_tooltip},
+        vector_image::{Primitive, VectorImageBuilder},
         widget::{Widget, WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
-        BuildContext, Control, NodeHandleMapping, Orientation, RcUiNodeHandle, Thickness, UiNode,
-        UserInterface, VerticalAlignment, BRUSH_BRIGHT, BRUSH_TEXT,
+        BuildContext, Control, HorizontalAlignment, NodeHandleMapping, Orientation, RcUiNodeHandle,
+        Thickness, UiNode, UserInterface, VerticalAlignment, BRUSH_BRIGHT, BRUSH_TEXT,
     },
     scene::{animation::AnimationPlayer, graph::Graph, node::Node, Scene},
 };
@@ -67,6 +79,7 @@ use std::{
     any::{Any, TypeId},
     cmp::Ordering,
     collections::hash_map::Entry,
+    f32::consts::TAU,
     ops::{Deref, DerefMut},
     rc::Rc,
     sync::mpsc::Sender,
@@ -83,14 +96,38 @@ enum PropertyBindingMode {
 struct TrackContextMenu {
     menu: RcUiNodeHandle,
     remove_track: Handle<UiNode>,
+    duplicate_track: Handle<UiNode>,
+    copy_tracks: Handle<UiNode>,
+    paste_tracks: Handle<UiNode>,
+    offset_track: Handle<UiNode>,
+    value_limits: Handle<UiNode>,
+    to_world_space: Handle<UiNode>,
+    to_local_space: Handle<UiNode>,
     set_target: Handle<UiNode>,
+    edit_property_path: Handle<UiNode>,
+    select_all_keys: Handle<UiNode>,
+    mute: Handle<UiNode>,
+    solo: Handle<UiNode>,
+    focus_in_scene: Handle<UiNode>,
     target_node_selector: Handle<UiNode>,
 }
 
 impl TrackContextMenu {
     fn new(ctx: &mut BuildContext) -> Self {
         let remove_track;
+        let duplicate_track;
+        let copy_tracks;
+        let paste_tracks;
+        let offset_track;
+        let value_limits;
+        let to_world_space;
+        let to_local_space;
         let set_target;
+        let edit_property_path;
+        let select_all_keys;
+        let mute;
+        let solo;
+        let focus_in_scene;
         let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
             .with_content(
                 StackPanelBuilder::new(
@@ -99,9 +136,62 @@ impl TrackContextMenu {
                             remove_track = create_menu_item("Remove Selected Tracks", vec![], ctx);
                             remove_track
                         })
+                        .with_child({
+                            duplicate_track =
+                                create_menu_item("Duplicate Selected Tracks", vec![], ctx);
+                            duplicate_track
+                        })
+                        .with_child({
+                            copy_tracks = create_menu_item("Copy Selected Tracks", vec![], ctx);
+                            copy_tracks
+                        })
+                        .with_child({
+                            paste_tracks = create_menu_item("Paste Tracks", vec![], ctx);
+                            paste_tracks
+                        })
+                        .with_child({
+                            offset_track =
+                                create_menu_item("Offset Selected Tracks...", vec![], ctx);
+                            offset_track
+                        })
+                        .with_child({
+                            value_limits = create_menu_item("Set Value Limits...", vec![], ctx);
+                            value_limits
+                        })
+                        .with_child({
+                            to_world_space =
+                                create_menu_item("Convert Positions To World Space", vec![], ctx);
+                            to_world_space
+                        })
+                        .with_child({
+                            to_local_space =
+                                create_menu_item("Convert Positions To Local Space", vec![], ctx);
+                            to_local_space
+                        })
                         .with_child({
                             set_target = create_menu_item("Set Target...", vec![], ctx);
                             set_target
+                        })
+                        .with_child({
+                            edit_property_path =
+                                create_menu_item("Edit Property Path...", vec![], ctx);
+                            edit_property_path
+                        })
+                        .with_child({
+                            select_all_keys = create_menu_item("Select All Keys", vec![], ctx);
+                            select_all_keys
+                        })
+                        .with_child({
+                            mute = create_menu_item("Mute", vec![], ctx);
+                            mute
+                        })
+                        .with_child({
+                            solo = create_menu_item("Solo", vec![], ctx);
+                            solo
+                        })
+                        .with_child({
+                            focus_in_scene = create_menu_item("Focus in Scene", vec![], ctx);
+                            focus_in_scene
                         }),
                 )
                 .build(ctx),
@@ -112,12 +202,648 @@ impl TrackContextMenu {
         Self {
             menu,
             remove_track,
+            duplicate_track,
+            copy_tracks,
+            paste_tracks,
+            offset_track,
+            value_limits,
+            to_world_space,
+            to_local_space,
             set_target,
+            edit_property_path,
+            select_all_keys,
+            mute,
+            solo,
+            focus_in_scene,
             target_node_selector: Default::default(),
         }
     }
 }
 
+/// A dialog that asks for a time offset of the selected tracks. The offset is entered in the time
+/// format of the ruler.
+struct OffsetTrackDialog {
+    window: Handle<UiNode>,
+    unit: Handle<UiNode>,
+    offset: Handle<UiNode>,
+    ok: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    time_format: TimeFormat,
+    fps: f32,
+}
+
+impl OffsetTrackDialog {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let unit = TextBuilder::new(
+            WidgetBuilder::new()
+                .with_vertical_alignment(VerticalAlignment::Center)
+                .on_row(0)
+                .on_column(0),
+        )
+        .build(ctx);
+        let offset = NumericUpDownBuilder::<f32>::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(1.0))
+                .on_row(0)
+                .on_column(1),
+        )
+        .with_value(0.0)
+        .build(ctx);
+        let ok;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(240.0).with_height(80.0))
+            .open(false)
+            .with_title(WindowTitle::text("Offset Tracks"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(2.0))
+                        .with_child(unit)
+                        .with_child(offset)
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .on_row(1)
+                                    .on_column(1)
+                                    .with_child({
+                                        ok = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(70.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("OK")
+                                        .build(ctx);
+                                        ok
+                                    })
+                                    .with_child({
+                                        cancel = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(70.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Cancel")
+                                        .build(ctx);
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_column(Column::strict(90.0))
+                .add_column(Column::stretch())
+                .add_row(Row::strict(22.0))
+                .add_row(Row::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            unit,
+            offset,
+            ok,
+            cancel,
+            time_format: TimeFormat::Seconds,
+            fps: 30.0,
+        }
+    }
+
+    fn open(&mut self, time_format: TimeFormat, fps: f32, ui: &UserInterface) {
+        self.time_format = time_format;
+        self.fps = fps;
+
+        ui.send_message(TextMessage::text(
+            self.unit,
+            MessageDirection::ToWidget,
+            match time_format {
+                TimeFormat::Seconds => "Offset (s)",
+                TimeFormat::Frames => "Offset (frames)",
+            }
+            .to_string(),
+        ));
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+        ui: &UserInterface,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.ok {
+                let value = ui
+                    .node(self.offset)
+                    .query_component::<NumericUpDown<f32>>()
+                    .unwrap()
+                    .value;
+                let offset = match self.time_format {
+                    TimeFormat::Seconds => value,
+                    TimeFormat::Frames => value / self.fps,
+                };
+
+                if let Selection::Animation(ref selection) = editor_scene.selection {
+                    let commands = selection
+                        .entities
+                        .iter()
+                        .filter_map(|e| {
+                            if let SelectedEntity::Track(id) = e {
+                                Some(SceneCommand::new(OffsetTrackCommand::new(
+                                    selection.animation_player,
+                                    selection.animation,
+                                    *id,
+                                    offset,
+                                )))
+                            } else {
+                                None
+                            }
+                        })
+                        .collect::<Vec<_>>();
+
+                    if offset != 0.0 && !commands.is_empty() {
+                        sender.do_scene_command(CommandGroup::from(commands));
+                    }
+                }
+
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.cancel {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        }
+    }
+}
+
+/// Range of valid values of the keys of a track. Values are in the units of the track, rotation
+/// tracks are limited in radians.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ValueLimits {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl ValueLimits {
+    pub fn clamp(&self, value: f32) -> f32 {
+        value.clamp(self.min, self.max)
+    }
+
+    pub fn contains(&self, value: f32) -> bool {
+        value >= self.min && value <= self.max
+    }
+}
+
+/// A dialog that asks for value limits of the selected tracks.
+struct ValueLimitsDialog {
+    window: Handle<UiNode>,
+    min: Handle<UiNode>,
+    max: Handle<UiNode>,
+    ok: Handle<UiNode>,
+    remove: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    // Tracks that will get the limits.
+    tracks: Vec<Uuid>,
+}
+
+impl ValueLimitsDialog {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let make_label = |ctx: &mut BuildContext, text: &str, row: usize| {
+            TextBuilder::new(
+                WidgetBuilder::new()
+                    .with_vertical_alignment(VerticalAlignment::Center)
+                    .on_row(row)
+                    .on_column(0),
+            )
+            .with_text(text)
+            .build(ctx)
+        };
+        let make_field = |ctx: &mut BuildContext, value: f32, row: usize| {
+            NumericUpDownBuilder::<f32>::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness::uniform(1.0))
+                    .on_row(row)
+                    .on_column(1),
+            )
+            .with_value(value)
+            .build(ctx)
+        };
+        let make_button = |ctx: &mut BuildContext, text: &str| {
+            ButtonBuilder::new(
+                WidgetBuilder::new()
+                    .with_width(70.0)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .with_text(text)
+            .build(ctx)
+        };
+
+        let min_label = make_label(ctx, "Min", 0);
+        let min = make_field(ctx, 0.0, 0);
+        let max_label = make_label(ctx, "Max", 1);
+        let max = make_field(ctx, 1.0, 1);
+        let ok = make_button(ctx, "OK");
+        let remove = make_button(ctx, "No Limits");
+        let cancel = make_button(ctx, "Cancel");
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(260.0).with_height(100.0))
+            .open(false)
+            .with_title(WindowTitle::text("Value Limits"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(2.0))
+                        .with_child(min_label)
+                        .with_child(min)
+                        .with_child(max_label)
+                        .with_child(max)
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .on_row(2)
+                                    .on_column(1)
+                                    .with_child(ok)
+                                    .with_child(remove)
+                                    .with_child(cancel),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_column(Column::strict(40.0))
+                .add_column(Column::stretch())
+                .add_row(Row::strict(22.0))
+                .add_row(Row::strict(22.0))
+                .add_row(Row::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            min,
+            max,
+            ok,
+            remove,
+            cancel,
+            tracks: Default::default(),
+        }
+    }
+
+    fn open(&mut self, tracks: Vec<Uuid>, current: Option<ValueLimits>, ui: &UserInterface) {
+        self.tracks = tracks;
+
+        let current = current.unwrap_or(ValueLimits { min: 0.0, max: 1.0 });
+        for (field, value) in [(self.min, current.min), (self.max, current.max)] {
+            ui.send_message(NumericUpDownMessage::value(
+                field,
+                MessageDirection::ToWidget,
+                value,
+            ));
+        }
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    /// Returns the tracks and their new limits, when a user has confirmed the changes. `None`
+    /// limits mean that values of the tracks are not limited anymore.
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+    ) -> Option<(Vec<Uuid>, Option<ValueLimits>)> {
+        if let Some(ButtonMessage::Click) = message.data() {
+            let result = if message.destination() == self.ok {
+                let value = |field: Handle<UiNode>| {
+                    ui.node(field)
+                        .query_component::<NumericUpDown<f32>>()
+                        .unwrap()
+                        .value
+                };
+                let (a, b) = (value(self.min), value(self.max));
+                Some((
+                    std::mem::take(&mut self.tracks),
+                    Some(ValueLimits {
+                        min: a.min(b),
+                        max: a.max(b),
+                    }),
+                ))
+            } else if message.destination() == self.remove {
+                Some((std::mem::take(&mut self.tracks), None))
+            } else if message.destination() == self.cancel {
+                None
+            } else {
+                return None;
+            };
+
+            ui.send_message(WindowMessage::close(
+                self.window,
+                MessageDirection::ToWidget,
+            ));
+
+            return result;
+        }
+        None
+    }
+}
+
+/// A dialog that edits the property path of a track. The path is validated against the
+/// reflected properties of the target node of the track and invalid paths are rejected.
+struct PropertyPathDialog {
+    window: Handle<UiNode>,
+    path: Handle<UiNode>,
+    error: Handle<UiNode>,
+    ok: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    // Track, which property path is edited.
+    track: Option<Uuid>,
+}
+
+impl PropertyPathDialog {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let path = TextBoxBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(1.0))
+                .on_row(0)
+                .on_column(1),
+        )
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx);
+        let error = TextBuilder::new(
+            WidgetBuilder::new()
+                .with_foreground(Brush::Solid(Color::RED))
+                .with_margin(Thickness::uniform(1.0))
+                .on_row(1)
+                .on_column(1),
+        )
+        .with_wrap(WrapMode::Word)
+        .build(ctx);
+        let ok;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(360.0).with_height(120.0))
+            .open(false)
+            .with_title(WindowTitle::text("Edit Property Path"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(2.0))
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_vertical_alignment(VerticalAlignment::Center)
+                                    .on_row(0)
+                                    .on_column(0),
+                            )
+                            .with_text("Path")
+                            .build(ctx),
+                        )
+                        .with_child(path)
+                        .with_child(error)
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .on_row(2)
+                                    .on_column(1)
+                                    .with_child({
+                                        ok = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(70.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("OK")
+                                        .build(ctx);
+                                        ok
+                                    })
+                                    .with_child({
+                                        cancel = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(70.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Cancel")
+                                        .build(ctx);
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_column(Column::strict(40.0))
+                .add_column(Column::stretch())
+                .add_row(Row::strict(22.0))
+                .add_row(Row::stretch())
+                .add_row(Row::strict(24.0))
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            path,
+            error,
+            ok,
+            cancel,
+            track: None,
+        }
+    }
+
+    fn open(&mut self, track: &Track, ui: &UserInterface) {
+        self.track = Some(track.id());
+
+        ui.send_message(TextMessage::text(
+            self.path,
+            MessageDirection::ToWidget,
+            property_path(track.binding()).to_owned(),
+        ));
+        ui.send_message(TextMessage::text(
+            self.error,
+            MessageDirection::ToWidget,
+            Default::default(),
+        ));
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+        scene: &Scene,
+        ui: &UserInterface,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.ok {
+                let path = ui
+                    .node(self.path)
+                    .query_component::<TextBox>()
+                    .unwrap()
+                    .text();
+
+                if let (Selection::Animation(ref selection), Some(track_id)) =
+                    (&editor_scene.selection, self.track)
+                {
+                    let track = scene
+                        .graph
+                        .try_get(selection.animation_player)
+                        .and_then(|n| n.query_component_ref::<AnimationPlayer>())
+                        .and_then(|p| p.animations().try_get(selection.animation))
+                        .and_then(|a| a.tracks().iter().find(|t| t.id() == track_id));
+
+                    if let Some(track) = track {
+                        let result = match scene.graph.try_get(track.target()) {
+                            Some(target) => binding_from_path(
+                                target,
+                                path.trim(),
+                                track.data_container().value_kind(),
+                            ),
+                            None => Err("The target node of the track does not exist!".to_owned()),
+                        };
+
+                        match result {
+                            Ok(binding) => {
+                                if &binding != track.binding() {
+                                    sender.do_scene_command(RetargetTrackCommand {
+                                        animation_player_handle: selection.animation_player,
+                                        animation_handle: selection.animation,
+                                        track: track_id,
+                                        target: track.target(),
+                                        binding,
+                                    });
+                                }
+                            }
+                            Err(err) => {
+                                // Keep the dialog open, so the path could be fixed.
+                                ui.send_message(TextMessage::text(
+                                    self.error,
+                                    MessageDirection::ToWidget,
+                                    err,
+                                ));
+                                return;
+                            }
+                        }
+                    }
+                }
+
+                self.track = None;
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.cancel {
+                self.track = None;
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        }
+    }
+}
+
+/// A menu that is shown when a scene node is dropped on the track list, it is used to select
+/// a property of the node to animate.
+struct NodeDropMenu {
+    menu: Handle<UiNode>,
+    position: Handle<UiNode>,
+    rotation: Handle<UiNode>,
+    scale: Handle<UiNode>,
+    property: Handle<UiNode>,
+}
+
+impl NodeDropMenu {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let position;
+        let rotation;
+        let scale;
+        let property;
+        let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
+            .with_content(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            position = create_menu_item("Position", vec![], ctx);
+                            position
+                        })
+                        .with_child({
+                            rotation = create_menu_item("Rotation", vec![], ctx);
+                            rotation
+                        })
+                        .with_child({
+                            scale = create_menu_item("Scale", vec![], ctx);
+                            scale
+                        })
+                        .with_child({
+                            property = create_menu_item("Property...", vec![], ctx);
+                            property
+                        }),
+                )
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            menu,
+            position,
+            rotation,
+            scale,
+            property,
+        }
+    }
+}
+
+/// Adds the track to the animation, unless the animation already has a track with the same
+/// target and binding.
+fn add_track(
+    track: Track,
+    sender: &MessageSender,
+    scene: &Scene,
+    animation_player: Handle<Node>,
+    animation: Handle<Animation>,
+) {
+    let is_duplicate = scene
+        .graph
+        .try_get_of_type::<AnimationPlayer>(animation_player)
+        .and_then(|p| p.animations().try_get(animation))
+        .map_or(false, |a| {
+            a.tracks()
+                .iter()
+                .any(|t| t.target() == track.target() && t.binding() == track.binding())
+        });
+
+    if is_duplicate {
+        Log::warn(format!(
+            "The animation already has a {} track of {} node!",
+            track.binding(),
+            scene
+                .graph
+                .try_get(track.target())
+                .map(|n| n.name())
+                .unwrap_or_default()
+        ));
+    } else {
+        sender.do_scene_command(AddTrackCommand::new(animation_player, animation, track));
+    }
+}
+
 fn type_id_to_supported_type(property_type: TypeId) -> Option<(TrackValueKind, ValueType)> {
     if property_type == TypeId::of::<f32>() {
         Some((TrackValueKind::Real, ValueType::F32))
@@ -217,17 +943,39 @@ fn type_id_to_supported_type(property_type: TypeId) -> Option<(TrackValueKind, V
 }
 
 #[allow(clippy::enum_variant_names)] // GTFO
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TrackViewMessage {
     TrackEnabled(bool),
     TrackName(String),
     TrackTargetIsValid(Result<(), String>),
+    TrackMuted(bool),
+    TrackSoloed(bool),
+    TrackLocked(bool),
+    /// Pinned tracks are shown in a fixed section above the scrollable list of tracks.
+    TrackPinned(bool),
+    /// Weight of the track in preview mode, in `[0; 1]` range.
+    TrackWeight(f32),
+    /// Amount of keys of the track, which values are outside of the value limits of the track.
+    TrackKeysOutOfLimits(usize),
+    /// Total amount of keys of every curve of the track, tracks with more keys than the threshold
+    /// are flagged.
+    TrackKeyCount {
+        count: usize,
+        threshold: usize,
+    },
 }
 
 impl TrackViewMessage {
     define_constructor!(TrackViewMessage:TrackEnabled => fn track_enabled(bool), layout: false);
     define_constructor!(TrackViewMessage:TrackName => fn track_name(String), layout: false);
     define_constructor!(TrackViewMessage:TrackTargetIsValid => fn track_target_is_valid(Result<(), String>), layout: false);
+    define_constructor!(TrackViewMessage:TrackMuted => fn track_muted(bool), layout: false);
+    define_constructor!(TrackViewMessage:TrackSoloed => fn track_soloed(bool), layout: false);
+    define_constructor!(TrackViewMessage:TrackLocked => fn track_locked(bool), layout: false);
+    define_constructor!(TrackViewMessage:TrackPinned => fn track_pinned(bool), layout: false);
+    define_constructor!(TrackViewMessage:TrackWeight => fn track_weight(f32), layout: false);
+    define_constructor!(TrackViewMessage:TrackKeysOutOfLimits => fn track_keys_out_of_limits(usize), layout: false);
+    define_constructor!(TrackViewMessage:TrackKeyCount => fn track_key_count(count: usize, threshold: usize), layout: false);
 }
 
 #[derive(Clone)]
@@ -238,6 +986,22 @@ struct TrackView {
     track_enabled_switch: Handle<UiNode>,
     track_enabled: bool,
     name_text: Handle<UiNode>,
+    mute_switch: Handle<UiNode>,
+    muted: bool,
+    solo_switch: Handle<UiNode>,
+    soloed: bool,
+    lock_switch: Handle<UiNode>,
+    lock_icon: Handle<UiNode>,
+    locked: bool,
+    pin_switch: Handle<UiNode>,
+    pinned: bool,
+    weight_slider: Handle<UiNode>,
+    weight: f32,
+    key_count_text: Handle<UiNode>,
+    key_count: usize,
+    key_threshold: usize,
+    out_of_limits_text: Handle<UiNode>,
+    keys_out_of_limits: usize,
 }
 
 impl Deref for TrackView {
@@ -269,56 +1033,256 @@ impl Control for TrackView {
         self.tree.resolve(node_map)
     }
 
-    fn on_remove(&self, sender: &Sender<UiMessage>) {
-        self.tree.on_remove(sender)
-    }
+    fn on_remove(&self, sender: &Sender<UiMessage>) {
+        self.tree.on_remove(sender)
+    }
+
+    fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
+        self.tree.measure_override(ui, available_size)
+    }
+
+    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
+        self.tree.arrange_override(ui, final_size)
+    }
+
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        self.tree.draw(drawing_context)
+    }
+
+    fn update(&mut self, dt: f32, sender: &Sender<UiMessage>) {
+        self.tree.update(dt, sender)
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        stop_switch_click(
+            &[
+                self.mute_switch,
+                self.solo_switch,
+                self.lock_switch,
+                self.pin_switch,
+            ],
+            ui,
+            message,
+        );
+        self.tree.handle_routed_message(ui, message);
+
+        if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.track_enabled_switch
+                && message.direction() == MessageDirection::FromWidget
+                && self.track_enabled != *value
+            {
+                ui.send_message(TrackViewMessage::track_enabled(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    *value,
+                ));
+            } else if message.destination() == self.mute_switch
+                && message.direction() == MessageDirection::FromWidget
+                && self.muted != *value
+            {
+                ui.send_message(TrackViewMessage::track_muted(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    *value,
+                ));
+            } else if message.destination() == self.solo_switch
+                && message.direction() == MessageDirection::FromWidget
+                && self.soloed != *value
+            {
+                ui.send_message(TrackViewMessage::track_soloed(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    *value,
+                ));
+            } else if message.destination() == self.lock_switch
+                && message.direction() == MessageDirection::FromWidget
+                && self.locked != *value
+            {
+                ui.send_message(TrackViewMessage::track_locked(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    *value,
+                ));
+            } else if message.destination() == self.pin_switch
+                && message.direction() == MessageDirection::FromWidget
+                && self.pinned != *value
+            {
+                ui.send_message(TrackViewMessage::track_pinned(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    *value,
+                ));
+            }
+        } else if let Some(ScrollBarMessage::Value(value)) = message.data() {
+            if message.destination() == self.weight_slider
+                && message.direction() == MessageDirection::FromWidget
+                && self.weight != *value
+            {
+                ui.send_message(TrackViewMessage::track_weight(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    *value,
+                ));
+            }
+        } else if let Some(msg) = message.data::<TrackViewMessage>() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    TrackViewMessage::TrackEnabled(enabled) => {
+                        if self.track_enabled != *enabled {
+                            self.track_enabled = *enabled;
+
+                            ui.send_message(CheckBoxMessage::checked(
+                                self.track_enabled_switch,
+                                MessageDirection::ToWidget,
+                                Some(*enabled),
+                            ));
+
+                            for widget in std::iter::once(self.name_text)
+                                .chain(self.tree.items.iter().cloned())
+                            {
+                                ui.send_message(WidgetMessage::opacity(
+                                    widget,
+                                    MessageDirection::ToWidget,
+                                    track_opacity(*enabled),
+                                ));
+                            }
+
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    TrackViewMessage::TrackMuted(muted) => {
+                        if self.muted != *muted {
+                            self.muted = *muted;
+
+                            ui.send_message(CheckBoxMessage::checked(
+                                self.mute_switch,
+                                MessageDirection::ToWidget,
+                                Some(*muted),
+                            ));
+
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    TrackViewMessage::TrackSoloed(soloed) => {
+                        if self.soloed != *soloed {
+                            self.soloed = *soloed;
+
+                            ui.send_message(CheckBoxMessage::checked(
+                                self.solo_switch,
+                                MessageDirection::ToWidget,
+                                Some(*soloed),
+                            ));
+
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    TrackViewMessage::TrackLocked(locked) => {
+                        if self.locked != *locked {
+                            self.locked = *locked;
+
+                            ui.send_message(CheckBoxMessage::checked(
+                                self.lock_switch,
+                                MessageDirection::ToWidget,
+                                Some(*locked),
+                            ));
+                            ui.send_message(WidgetMessage::opacity(
+                                self.lock_icon,
+                                MessageDirection::ToWidget,
+                                lock_icon_opacity(*locked),
+                            ));
+
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    TrackViewMessage::TrackPinned(pinned) => {
+                        if self.pinned != *pinned {
+                            self.pinned = *pinned;
+
+                            ui.send_message(CheckBoxMessage::checked(
+                                self.pin_switch,
+                                MessageDirection::ToWidget,
+                                Some(*pinned),
+                            ));
 
-    fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
-        self.tree.measure_override(ui, available_size)
-    }
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    TrackViewMessage::TrackWeight(weight) => {
+                        if self.weight != *weight {
+                            self.weight = *weight;
 
-    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
-        self.tree.arrange_override(ui, final_size)
-    }
+                            ui.send_message(ScrollBarMessage::value(
+                                self.weight_slider,
+                                MessageDirection::ToWidget,
+                                *weight,
+                            ));
 
-    fn draw(&self, drawing_context: &mut DrawingContext) {
-        self.tree.draw(drawing_context)
-    }
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    TrackViewMessage::TrackKeysOutOfLimits(count) => {
+                        if self.keys_out_of_limits != *count {
+                            self.keys_out_of_limits = *count;
 
-    fn update(&mut self, dt: f32, sender: &Sender<UiMessage>) {
-        self.tree.update(dt, sender)
-    }
+                            ui.send_message(WidgetMessage::visibility(
+                                self.out_of_limits_text,
+                                MessageDirection::ToWidget,
+                                *count > 0,
+                            ));
 
-    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
-        self.tree.handle_routed_message(ui, message);
+                            let tooltip = make_simple_tooltip(
+                                &mut ui.build_ctx(),
+                                &format!(
+                                    "{} key(s) of the track are outside of its value limits",
+                                    count
+                                ),
+                            );
+                            ui.send_message(WidgetMessage::tooltip(
+                                self.out_of_limits_text,
+                                MessageDirection::ToWidget,
+                                Some(tooltip),
+                            ));
+                        }
+                    }
+                    TrackViewMessage::TrackKeyCount { count, threshold } => {
+                        if self.key_count != *count || self.key_threshold != *threshold {
+                            self.key_count = *count;
+                            self.key_threshold = *threshold;
 
-        if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
-            if message.destination() == self.track_enabled_switch
-                && message.direction() == MessageDirection::FromWidget
-                && self.track_enabled != *value
-            {
-                ui.send_message(TrackViewMessage::track_enabled(
-                    self.handle,
-                    MessageDirection::ToWidget,
-                    *value,
-                ));
-            }
-        } else if let Some(msg) = message.data::<TrackViewMessage>() {
-            if message.destination() == self.handle
-                && message.direction() == MessageDirection::ToWidget
-            {
-                match msg {
-                    TrackViewMessage::TrackEnabled(enabled) => {
-                        if self.track_enabled != *enabled {
-                            self.track_enabled = *enabled;
+                            let dense = *count > *threshold;
 
-                            ui.send_message(CheckBoxMessage::checked(
-                                self.track_enabled_switch,
+                            ui.send_message(TextMessage::text(
+                                self.key_count_text,
                                 MessageDirection::ToWidget,
-                                Some(*enabled),
+                                count.to_string(),
+                            ));
+                            ui.send_message(WidgetMessage::foreground(
+                                self.key_count_text,
+                                MessageDirection::ToWidget,
+                                if dense {
+                                    Brush::Solid(DENSE_TRACK_COLOR)
+                                } else {
+                                    BRUSH_TEXT
+                                },
                             ));
 
-                            ui.send_message(message.reverse());
+                            let tooltip = if dense {
+                                format!(
+                                    "Track has {} keys, which is more than {}. Simplify or bake \
+                                    its curves to reduce amount of keys.",
+                                    count, threshold
+                                )
+                            } else {
+                                format!("Track has {} keys", count)
+                            };
+                            let tooltip = make_simple_tooltip(&mut ui.build_ctx(), &tooltip);
+                            ui.send_message(WidgetMessage::tooltip(
+                                self.key_count_text,
+                                MessageDirection::ToWidget,
+                                Some(tooltip),
+                            ));
                         }
                     }
                     TrackViewMessage::TrackName(name) => {
@@ -378,12 +1342,478 @@ impl Control for TrackView {
     }
 }
 
+/// Returns a color of a curve with the given index in a track of the given kind. Components of
+/// vector tracks use conventional X/Y/Z coloring, curves of other tracks cycle a separate palette.
+pub fn curve_color(kind: TrackValueKind, index: usize) -> Color {
+    const COMPONENTS: [Color; 4] = [
+        Color::opaque(220, 70, 70),
+        Color::opaque(80, 200, 80),
+        Color::opaque(80, 130, 230),
+        Color::opaque(210, 210, 210),
+    ];
+    const PALETTE: [Color; 4] = [
+        Color::opaque(230, 150, 50),
+        Color::opaque(170, 100, 220),
+        Color::opaque(60, 200, 200),
+        Color::opaque(230, 110, 170),
+    ];
+
+    match kind {
+        TrackValueKind::Vector2 | TrackValueKind::Vector3 | TrackValueKind::Vector4 => {
+            COMPONENTS[index % COMPONENTS.len()]
+        }
+        TrackValueKind::Real | TrackValueKind::UnitQuaternion => PALETTE[index % PALETTE.len()],
+    }
+}
+
+/// Returns a name of a curve with the given index in a track of the given kind. Curves of
+/// rotation tracks are named by the Euler angles they store.
+pub fn curve_component_name(kind: TrackValueKind, index: usize) -> &'static str {
+    match kind {
+        TrackValueKind::UnitQuaternion => EULER_COMPONENT_NAMES.get(index).unwrap_or(&"_"),
+        _ => ["X", "Y", "Z", "W"].get(index).unwrap_or(&"_"),
+    }
+}
+
+const POSITION_PATH: &str = "local_transform.local_position";
+const ROTATION_PATH: &str = "local_transform.local_rotation";
+const SCALE_PATH: &str = "local_transform.local_scale";
+
+/// Returns the path of the property of a node, which is animated by the binding.
+pub fn property_path(binding: &ValueBinding) -> &str {
+    match binding {
+        ValueBinding::Position => POSITION_PATH,
+        ValueBinding::Rotation => ROTATION_PATH,
+        ValueBinding::Scale => SCALE_PATH,
+        ValueBinding::Property { name, .. } => name,
+    }
+}
+
+/// Returns the name of a track in the track list, it includes the full property path, so tracks
+/// of a node could be told apart.
+fn track_name(binding: &ValueBinding) -> String {
+    match binding {
+        ValueBinding::Property { name, .. } => name.clone(),
+        _ => format!("{} ({})", binding, property_path(binding)),
+    }
+}
+
+/// Makes a binding to the property of the node with the given path. Paths of position, rotation
+/// and scale result in the respective special bindings. The property must have the same kind of
+/// values as the track, otherwise curves of the track could not be applied to it.
+fn binding_from_path(
+    target: &Node,
+    path: &str,
+    kind: TrackValueKind,
+) -> Result<ValueBinding, String> {
+    let (property_kind, binding) = match path {
+        POSITION_PATH => (TrackValueKind::Vector3, ValueBinding::Position),
+        ROTATION_PATH => (TrackValueKind::UnitQuaternion, ValueBinding::Rotation),
+        SCALE_PATH => (TrackValueKind::Vector3, ValueBinding::Scale),
+        _ => {
+            let mut result = Err(format!("Unable to resolve property path {}.", path));
+            target.resolve_path(path, &mut |resolved| match resolved {
+                Ok(value) => {
+                    let mut property_type = TypeId::of::<u32>();
+                    value.as_any(&mut |any| property_type = any.type_id());
+
+                    result = type_id_to_supported_type(property_type)
+                        .map(|(kind, value_type)| {
+                            (
+                                kind,
+                                ValueBinding::Property {
+                                    name: path.to_owned(),
+                                    value_type,
+                                },
+                            )
+                        })
+                        .ok_or_else(|| format!("Property {} has unsupported type.", path));
+                }
+                Err(err) => {
+                    result = Err(format!(
+                        "Unable to resolve property path {}. Reason: {:?}",
+                        path, err
+                    ));
+                }
+            });
+            result?
+        }
+    };
+
+    if property_kind == kind {
+        Ok(binding)
+    } else {
+        Err(format!(
+            "Property {} has {:?} values, but the track animates {:?} values.",
+            path, property_kind, kind
+        ))
+    }
+}
+
+/// Checks whether the node has a property of the binding and the type of the property is the
+/// same as the one of the binding.
+fn validate_binding(target: &Node, binding: &ValueBinding) -> Result<(), String> {
+    let mut validation_result = Ok(());
+    if let ValueBinding::Property { name, value_type } = binding {
+        target.resolve_path(name, &mut |result| match result {
+            Ok(value) => {
+                let mut property_type = TypeId::of::<u32>();
+                value.as_any(&mut |any| property_type = any.type_id());
+
+                if let Some((_, type_)) = type_id_to_supported_type(property_type) {
+                    if *value_type != type_ {
+                        validation_result = Err(format!(
+                            "Property type mismatch. Expected {:?}, got {:?}",
+                            value_type, type_
+                        ));
+                    }
+                } else {
+                    validation_result = Err(format!(
+                        "Unsupported property type of {:?} type id.",
+                        property_type
+                    ));
+                }
+            }
+            Err(err) => {
+                validation_result = Err(format!(
+                    "Unable to resolve property path {}. Reason: {:?}",
+                    name, err
+                ));
+            }
+        });
+    }
+    validation_result
+}
+
+/// Returns components of the property of the node, to which the binding points to. Only
+/// properties of `f32`-based types are supported, `None` is returned for the rest.
+pub fn bound_values(node: &Node, binding: &ValueBinding) -> Option<Vec<f32>> {
+    match binding {
+        ValueBinding::Position => Some(node.local_transform().position().as_slice().to_vec()),
+        ValueBinding::Scale => Some(node.local_transform().scale().as_slice().to_vec()),
+        ValueBinding::Rotation => {
+            // Rotation tracks store Euler angles in XYZ order.
+            let (x, y, z) = node.local_transform().rotation().euler_angles();
+            Some(vec![x, y, z])
+        }
+        ValueBinding::Property { name, .. } => {
+            let mut values = None;
+            node.resolve_path(name, &mut |result| {
+                if let Ok(property) = result {
+                    property.as_any(&mut |any| {
+                        values = if let Some(v) = any.downcast_ref::<f32>() {
+                            Some(vec![*v])
+                        } else if let Some(v) = any.downcast_ref::<Vector2<f32>>() {
+                            Some(v.as_slice().to_vec())
+                        } else if let Some(v) = any.downcast_ref::<Vector3<f32>>() {
+                            Some(v.as_slice().to_vec())
+                        } else {
+                            any.downcast_ref::<Vector4<f32>>()
+                                .map(|v| v.as_slice().to_vec())
+                        };
+                    })
+                }
+            });
+            values
+        }
+    }
+}
+
+/// Returns pairs of (curve id, value) that key current values of the property of the node at the
+/// time. Rotation angles are kept within a half of a turn from the curves, so new keys do not
+/// spin the node around.
+pub fn current_key_values(track: &Track, node: &Node, time: f32) -> Vec<(Uuid, f32)> {
+    let current = match bound_values(node, track.binding()) {
+        Some(current) => current,
+        None => return Default::default(),
+    };
+
+    let is_rotation = track.data_container().value_kind() == TrackValueKind::UnitQuaternion;
+    track
+        .data_container()
+        .curves_ref()
+        .iter()
+        .zip(current)
+        .map(|(curve, mut value)| {
+            if is_rotation {
+                let delta = value - curve.value_at(time);
+                value -= (delta / TAU).round() * TAU;
+            }
+            (curve.id(), value)
+        })
+        .collect()
+}
+
+/// Returns selection of every key of the selected tracks, as well as of the tracks that own
+/// selected curves. Curves of the tracks are selected too, so the curve editor shows the keys.
+/// Returns `None` if there's no such tracks.
+pub fn select_all_track_keys(
+    animation: &Animation,
+    selection: &AnimationSelection,
+) -> Option<AnimationSelection> {
+    let mut entities = Vec::new();
+    for track in animation.tracks() {
+        let curves = track.data_container().curves_ref();
+        let is_selected = selection.entities.iter().any(|e| match e {
+            SelectedEntity::Track(id) => *id == track.id(),
+            SelectedEntity::Curve(id) => curves.iter().any(|c| c.id() == *id),
+            _ => false,
+        });
+
+        if is_selected {
+            entities.push(SelectedEntity::Track(track.id()));
+            for curve in curves {
+                entities.push(SelectedEntity::Curve(curve.id()));
+                entities.extend(curve.keys().iter().map(|k| SelectedEntity::Keyframe {
+                    curve: curve.id(),
+                    key: k.id,
+                }));
+            }
+        }
+    }
+
+    if entities.is_empty() {
+        None
+    } else {
+        Some(AnimationSelection {
+            animation_player: selection.animation_player,
+            animation: selection.animation,
+            entities,
+        })
+    }
+}
+
+/// Inserts a copy of every selected track right after the track and selects the copies.
+fn duplicate_selected_tracks(editor_scene: &EditorScene, sender: &MessageSender, scene: &Scene) {
+    let selection = match editor_scene.selection {
+        Selection::Animation(ref selection) => selection,
+        _ => return,
+    };
+
+    let animation = match scene
+        .graph
+        .try_get(selection.animation_player)
+        .and_then(|n| n.query_component_ref::<AnimationPlayer>())
+        .and_then(|p| p.animations().try_get(selection.animation))
+    {
+        Some(animation) => animation,
+        None => return,
+    };
+
+    let mut indices = animation
+        .tracks()
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| {
+            selection
+                .entities
+                .iter()
+                .any(|e| matches!(e, SelectedEntity::Track(id) if *id == t.id()))
+        })
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    if indices.is_empty() {
+        return;
+    }
+
+    // Insert copies starting from the last track, so insertion does not shift indices of the
+    // other selected tracks.
+    indices.reverse();
+
+    let mut commands = Vec::new();
+    let mut entities = Vec::new();
+    for index in indices {
+        let command = DuplicateTrackCommand::new(
+            selection.animation_player,
+            selection.animation,
+            index,
+            &animation.tracks()[index],
+        );
+        entities.push(SelectedEntity::Track(command.duplicate_id()));
+        commands.push(SceneCommand::new(command));
+    }
+
+    commands.push(SceneCommand::new(ChangeSelectionCommand::new(
+        Selection::Animation(AnimationSelection {
+            animation_player: selection.animation_player,
+            animation: selection.animation,
+            entities,
+        }),
+        editor_scene.selection.clone(),
+    )));
+
+    sender.do_scene_command(CommandGroup::from(commands));
+}
+
+/// Color of the key count of a track, which has more keys than the threshold from the settings.
+const DENSE_TRACK_COLOR: Color = Color::opaque(240, 140, 40);
+
+/// Disabled tracks (and their curves) are shown greyed, the same way as disabled widgets.
+fn track_opacity(enabled: bool) -> Option<f32> {
+    if enabled {
+        None
+    } else {
+        Some(0.4)
+    }
+}
+
+/// The lock icon is shown dimmed on unlocked tracks, so locked ones stand out.
+fn lock_icon_opacity(locked: bool) -> Option<f32> {
+    if locked {
+        None
+    } else {
+        Some(0.3)
+    }
+}
+
+fn make_lock_icon(ctx: &mut BuildContext, locked: bool) -> Handle<UiNode> {
+    VectorImageBuilder::new(
+        WidgetBuilder::new()
+            .with_margin(Thickness::uniform(2.0))
+            .with_vertical_alignment(VerticalAlignment::Center)
+            .with_foreground(BRUSH_BRIGHT)
+            .with_opacity(lock_icon_opacity(locked)),
+    )
+    .with_primitives(vec![
+        // Shackle.
+        Primitive::Rectangle {
+            rect: Rect::new(2.5, 0.0, 5.0, 7.0),
+            thickness: 1.5,
+        },
+        // Body.
+        Primitive::RectangleFilled {
+            rect: Rect::new(0.0, 5.0, 10.0, 7.0),
+        },
+    ])
+    .build(ctx)
+}
+
+fn make_track_toggle(
+    ctx: &mut BuildContext,
+    text: &str,
+    tooltip: &str,
+    color: Color,
+    checked: bool,
+) -> Handle<UiNode> {
+    CheckBoxBuilder::new(
+        WidgetBuilder::new()
+            .with_height(18.0)
+            .with_margin(Thickness::left(4.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_content(
+        TextBuilder::new(
+            WidgetBuilder::new()
+                .with_margin(Thickness::uniform(1.0))
+                .with_foreground(Brush::Solid(color)),
+        )
+        .with_text(text)
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx),
+    )
+    .checked(Some(checked))
+    .build(ctx)
+}
+
+/// Marks a click on any of the switches as handled, so the tree item that contains the switches
+/// keeps its selection when a switch is toggled.
+fn stop_switch_click(switches: &[Handle<UiNode>], ui: &UserInterface, message: &mut UiMessage) {
+    if let Some(WidgetMessage::MouseDown { .. }) = message.data() {
+        if switches.iter().any(|switch| {
+            *switch == message.destination()
+                || ui.node(*switch).has_descendant(message.destination(), ui)
+        }) {
+            message.set_handled(true);
+        }
+    }
+}
+
+/// Tree item of a group or a curve, its content has switches that must not change the selection.
+#[derive(Clone)]
+struct SwitchRow {
+    tree: Tree,
+    switches: Vec<Handle<UiNode>>,
+}
+
+impl Deref for SwitchRow {
+    type Target = Widget;
+
+    fn deref(&self) -> &Self::Target {
+        &self.tree.widget
+    }
+}
+
+impl DerefMut for SwitchRow {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.tree.widget
+    }
+}
+
+impl Control for SwitchRow {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        self.tree.query_component(type_id).or_else(|| {
+            if type_id == TypeId::of::<Self>() {
+                Some(self)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn resolve(&mut self, node_map: &NodeHandleMapping) {
+        self.tree.resolve(node_map)
+    }
+
+    fn on_remove(&self, sender: &Sender<UiMessage>) {
+        self.tree.on_remove(sender)
+    }
+
+    fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
+        self.tree.measure_override(ui, available_size)
+    }
+
+    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
+        self.tree.arrange_override(ui, final_size)
+    }
+
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        self.tree.draw(drawing_context)
+    }
+
+    fn update(&mut self, dt: f32, sender: &Sender<UiMessage>) {
+        self.tree.update(dt, sender)
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        stop_switch_click(&self.switches, ui, message);
+        self.tree.handle_routed_message(ui, message);
+    }
+}
+
+fn build_switch_row(
+    tree_builder: TreeBuilder,
+    switches: Vec<Handle<UiNode>>,
+    ctx: &mut BuildContext,
+) -> Handle<UiNode> {
+    let row = SwitchRow {
+        tree: tree_builder.build_tree(ctx),
+        switches,
+    };
+    ctx.add_node(UiNode::new(row))
+}
+
 struct TrackViewBuilder {
     tree_builder: TreeBuilder,
     id: Uuid,
     target: Handle<Node>,
     name: String,
     track_enabled: bool,
+    muted: bool,
+    soloed: bool,
+    locked: bool,
+    pinned: bool,
+    weight: f32,
 }
 
 impl TrackViewBuilder {
@@ -394,6 +1824,11 @@ impl TrackViewBuilder {
             target: Default::default(),
             name: Default::default(),
             track_enabled: true,
+            muted: false,
+            soloed: false,
+            locked: false,
+            pinned: false,
+            weight: 1.0,
         }
     }
 
@@ -417,30 +1852,157 @@ impl TrackViewBuilder {
         self
     }
 
+    pub fn with_muted(mut self, muted: bool) -> Self {
+        self.muted = muted;
+        self
+    }
+
+    pub fn with_soloed(mut self, soloed: bool) -> Self {
+        self.soloed = soloed;
+        self
+    }
+
+    pub fn with_locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+
+    pub fn with_pinned(mut self, pinned: bool) -> Self {
+        self.pinned = pinned;
+        self
+    }
+
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let name_text;
         let track_enabled_switch = CheckBoxBuilder::new(WidgetBuilder::new().with_height(18.0))
             .with_content({
-                name_text =
-                    TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
-                        .with_text(self.name)
-                        .with_vertical_text_alignment(VerticalAlignment::Center)
-                        .build(ctx);
+                name_text = TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_margin(Thickness::uniform(1.0))
+                        .with_opacity(track_opacity(self.track_enabled)),
+                )
+                .with_text(self.name)
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .build(ctx);
                 name_text
             })
             .checked(Some(self.track_enabled))
             .build(ctx);
 
+        let mute_switch = make_track_toggle(
+            ctx,
+            "M",
+            "Mute Track In Preview",
+            Color::opaque(220, 80, 80),
+            self.muted,
+        );
+        let solo_switch = make_track_toggle(
+            ctx,
+            "S",
+            "Solo Track In Preview",
+            Color::opaque(230, 200, 60),
+            self.soloed,
+        );
+
+        let lock_icon = make_lock_icon(ctx, self.locked);
+        let lock_switch = CheckBoxBuilder::new(
+            WidgetBuilder::new()
+                .with_height(18.0)
+                .with_margin(Thickness::left(4.0))
+                .with_tooltip(make_simple_tooltip(
+                    ctx,
+                    "Lock Track, keys of a locked track cannot be moved or removed",
+                )),
+        )
+        .with_content(lock_icon)
+        .checked(Some(self.locked))
+        .build(ctx);
+
+        let pin_switch = make_track_toggle(
+            ctx,
+            "P",
+            "Pin Track, pinned tracks stay visible above the list of tracks while scrolling",
+            Color::opaque(100, 180, 240),
+            self.pinned,
+        );
+
+        let weight_slider = ScrollBarBuilder::new(
+            WidgetBuilder::new()
+                .with_width(50.0)
+                .with_height(14.0)
+                .with_margin(Thickness::left(4.0))
+                .with_vertical_alignment(VerticalAlignment::Center)
+                .with_tooltip(make_simple_tooltip(
+                    ctx,
+                    "Weight of the track in preview, it scales the contribution of the track \
+                    relative to the rest pose. Saved data is not affected.",
+                )),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .with_min(0.0)
+        .with_max(1.0)
+        .with_step(0.05)
+        .with_value(self.weight)
+        .build(ctx);
+
+        // Key count is set on first sync.
+        let key_count_text =
+            TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(4.0)))
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .build(ctx);
+
+        let out_of_limits_text = TextBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_margin(Thickness::left(4.0))
+                .with_foreground(Brush::Solid(Color::RED)),
+        )
+        .with_text("!")
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx);
+
+        let content = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_child(track_enabled_switch)
+                .with_child(mute_switch)
+                .with_child(solo_switch)
+                .with_child(lock_switch)
+                .with_child(pin_switch)
+                .with_child(weight_slider)
+                .with_child(key_count_text)
+                .with_child(out_of_limits_text),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
         let track_view = TrackView {
-            tree: self
-                .tree_builder
-                .with_content(track_enabled_switch)
-                .build_tree(ctx),
+            tree: self.tree_builder.with_content(content).build_tree(ctx),
             id: self.id,
             target: self.target,
             track_enabled: self.track_enabled,
             track_enabled_switch,
             name_text,
+            mute_switch,
+            muted: self.muted,
+            solo_switch,
+            soloed: self.soloed,
+            lock_switch,
+            lock_icon,
+            locked: self.locked,
+            pin_switch,
+            pinned: self.pinned,
+            weight_slider,
+            weight: self.weight,
+            key_count_text,
+            key_count: usize::MAX,
+            key_threshold: 0,
+            out_of_limits_text,
+            keys_out_of_limits: 0,
         };
 
         ctx.add_node(UiNode::new(track_view))
@@ -550,10 +2112,13 @@ pub struct TrackList {
     toolbar: Toolbar,
     pub panel: Handle<UiNode>,
     tree_root: Handle<UiNode>,
+    pinned_section: Handle<UiNode>,
+    pinned_root: Handle<UiNode>,
     add_track: Handle<UiNode>,
     add_position_track: Handle<UiNode>,
     add_rotation_track: Handle<UiNode>,
     add_scale_track: Handle<UiNode>,
+    duplicate_track: Handle<UiNode>,
     node_selector: Handle<UiNode>,
     property_selector: Handle<UiNode>,
     selected_node: Handle<Node>,
@@ -561,7 +2126,82 @@ pub struct TrackList {
     track_views: FxHashMap<Uuid, Handle<UiNode>>,
     curve_views: FxHashMap<Uuid, Handle<UiNode>>,
     context_menu: TrackContextMenu,
+    offset_track_dialog: OffsetTrackDialog,
+    value_limits_dialog: ValueLimitsDialog,
+    property_path_dialog: PropertyPathDialog,
+    node_drop_menu: NodeDropMenu,
     property_binding_mode: PropertyBindingMode,
+    // Lowercase text of the search filter.
+    filter_text: String,
+    // Tracks that are excluded from preview. This is an editor-only state, it is not saved.
+    muted_tracks: FxHashSet<Uuid>,
+    // If not empty, only these tracks are used in preview.
+    soloed_tracks: FxHashSet<Uuid>,
+    // Keys of these tracks cannot be edited in the curve editor. This is an editor-only state,
+    // it is not saved.
+    locked_tracks: FxHashSet<Uuid>,
+    // These tracks are shown in a separate tree above the scrollable list, so they stay visible
+    // while scrolling. This is an editor-only state, it is not saved.
+    pinned_tracks: FxHashSet<Uuid>,
+    // Weights of the tracks in preview mode, tracks without a weight have full weight. This is an
+    // editor-only state, it is not saved.
+    track_weights: FxHashMap<Uuid, f32>,
+    // Keys of these tracks are clamped to the limits when edited in the curve editor. This is an
+    // editor-only state, it is not saved.
+    value_limits: FxHashMap<Uuid, ValueLimits>,
+    // Order of the tracks at the moment of last sync.
+    track_order: Vec<Uuid>,
+    // Groups are keyed by target nodes, so their state is preserved when an animation is changed.
+    collapsed_groups: FxHashSet<Handle<Node>>,
+    group_toggles: FxHashMap<Handle<UiNode>, GroupToggle>,
+    // Curves that are not shown in the curve editor. This is a view-only state, it is reset for
+    // curves that are deselected.
+    hidden_curves: FxHashSet<Uuid>,
+    curve_visibility_switches: FxHashMap<Handle<UiNode>, Uuid>,
+    // Selection before the last selection change made in the tree. The first click of a double
+    // click already changes the selection, this one is the selection that a user saw before.
+    selection_before_click: Vec<SelectedEntity>,
+}
+
+/// Group-level mute and solo switches, they're applied to every track of a group.
+#[derive(Copy, Clone)]
+enum GroupToggle {
+    Mute { group: Handle<UiNode> },
+    Solo { group: Handle<UiNode> },
+}
+
+/// Returns a key of a group to which a track with the given target belongs to. Tracks with
+/// invalid target nodes belong to "Orphaned" group with [`Handle::NONE`] key.
+fn group_key(graph: &Graph, target: Handle<Node>) -> Handle<Node> {
+    if graph.is_valid_handle(target) {
+        target
+    } else {
+        Handle::NONE
+    }
+}
+
+#[must_use]
+pub enum TrackListAction {
+    None,
+    /// Set of tracks that should be used in preview mode has changed.
+    PreviewTracksChanged,
+    /// Set of curves that are hidden in the curve editor has changed.
+    CurveVisibilityChanged,
+    /// A user wants to offset selected tracks in time, the offset dialog should be opened with
+    /// [`TrackList::open_offset_dialog`].
+    OffsetSelectedTracks,
+    /// Selected tracks should be copied into the track clipboard of the animation editor.
+    CopySelectedTracks,
+    /// Tracks from the track clipboard of the animation editor should be added to the current
+    /// animation.
+    PasteTracks,
+    /// A row of a track or a curve was double clicked, the entity should be isolated in the curve
+    /// editor, or the selection before isolation should be restored if it is isolated already.
+    ToggleIsolation {
+        entity: SelectedEntity,
+        /// Selection before the clicks of the double click.
+        previous: Vec<SelectedEntity>,
+    },
 }
 
 struct CurveViewData {
@@ -584,18 +2224,40 @@ impl TrackList {
         let toolbar = Toolbar::new(ctx);
 
         let tree_root;
+        let pinned_section;
+        let pinned_root;
         let add_track;
         let add_position_track;
         let add_rotation_track;
         let add_scale_track;
+        let duplicate_track;
 
         let panel = GridBuilder::new(
             WidgetBuilder::new()
                 .with_child(toolbar.panel)
+                .with_child({
+                    pinned_section = BorderBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(1)
+                            .on_column(0)
+                            .with_visibility(false)
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_child({
+                                pinned_root = TreeRootBuilder::new(
+                                    WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                                )
+                                .build(ctx);
+                                pinned_root
+                            }),
+                    )
+                    .with_stroke_thickness(Thickness::bottom(1.0))
+                    .build(ctx);
+                    pinned_section
+                })
                 .with_child(
                     ScrollViewerBuilder::new(
                         WidgetBuilder::new()
-                            .on_row(1)
+                            .on_row(2)
                             .on_column(0)
                             .with_margin(Thickness::uniform(1.0)),
                     )
@@ -611,7 +2273,7 @@ impl TrackList {
                 .with_child(
                     StackPanelBuilder::new(
                         WidgetBuilder::new()
-                            .on_row(2)
+                            .on_row(3)
                             .on_column(0)
                             .with_margin(Thickness::uniform(1.0))
                             .with_child({
@@ -671,6 +2333,23 @@ impl TrackList {
                                     property binding",
                                 );
                                 add_rotation_track
+                            })
+                            .with_child({
+                                duplicate_track = ButtonBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_enabled(false)
+                                        .with_height(22.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Duplicate Selected Tracks.\n\
+                                            Copies are bound to the same properties of \
+                                            the same nodes",
+                                        )),
+                                )
+                                .with_text("Duplicate")
+                                .build(ctx);
+                                duplicate_track
                             }),
                     )
                     .with_orientation(Orientation::Horizontal)
@@ -678,6 +2357,7 @@ impl TrackList {
                 ),
         )
         .add_row(Row::auto())
+        .add_row(Row::auto())
         .add_row(Row::stretch())
         .add_row(Row::strict(28.0))
         .add_column(Column::stretch())
@@ -686,12 +2366,19 @@ impl TrackList {
         Self {
             toolbar,
             context_menu: TrackContextMenu::new(ctx),
+            offset_track_dialog: OffsetTrackDialog::new(ctx),
+            value_limits_dialog: ValueLimitsDialog::new(ctx),
+            property_path_dialog: PropertyPathDialog::new(ctx),
+            node_drop_menu: NodeDropMenu::new(ctx),
             panel,
             tree_root,
+            pinned_section,
+            pinned_root,
             add_track,
             add_position_track,
             add_rotation_track,
             add_scale_track,
+            duplicate_track,
             node_selector: Default::default(),
             property_selector: Default::default(),
             selected_node: Default::default(),
@@ -699,9 +2386,231 @@ impl TrackList {
             track_views: Default::default(),
             curve_views: Default::default(),
             property_binding_mode: PropertyBindingMode::Generic,
+            filter_text: Default::default(),
+            muted_tracks: Default::default(),
+            locked_tracks: Default::default(),
+            pinned_tracks: Default::default(),
+            track_weights: Default::default(),
+            value_limits: Default::default(),
+            soloed_tracks: Default::default(),
+            track_order: Default::default(),
+            collapsed_groups: Default::default(),
+            group_toggles: Default::default(),
+            hidden_curves: Default::default(),
+            curve_visibility_switches: Default::default(),
+            selection_before_click: Default::default(),
+        }
+    }
+
+    /// Returns a track or a curve, whose row in the tree contains the given widget.
+    fn row_entity(&self, widget: Handle<UiNode>, ui: &UserInterface) -> Option<SelectedEntity> {
+        let mut handle = widget;
+        while handle.is_some() && handle != self.tree_root && handle != self.pinned_root {
+            let node = ui.node(handle);
+            if let Some(track_view) = node.query_component::<TrackView>() {
+                return Some(SelectedEntity::Track(track_view.id));
+            } else if let Some(curve_data) = node.user_data_ref::<CurveViewData>() {
+                return Some(SelectedEntity::Curve(curve_data.id));
+            }
+            handle = node.parent();
+        }
+        None
+    }
+
+    fn group_track_ids(&self, group: Handle<UiNode>, ui: &UserInterface) -> Vec<Uuid> {
+        ui.node(group)
+            .query_component::<Tree>()
+            .map(|tree| {
+                tree.items
+                    .iter()
+                    .filter_map(|item| ui.node(*item).query_component::<TrackView>())
+                    .map(|track_view| track_view.id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Makes group-level switches checked if every track of their group is muted (soloed).
+    fn sync_group_toggles(&self, ui: &UserInterface) {
+        for (switch, toggle) in self.group_toggles.iter() {
+            let (group, tracks) = match toggle {
+                GroupToggle::Mute { group } => (*group, &self.muted_tracks),
+                GroupToggle::Solo { group } => (*group, &self.soloed_tracks),
+            };
+
+            let ids = self.group_track_ids(group, ui);
+            let checked = !ids.is_empty() && ids.iter().all(|id| tracks.contains(id));
+
+            send_sync_message(
+                ui,
+                CheckBoxMessage::checked(*switch, MessageDirection::ToWidget, Some(checked)),
+            );
+        }
+    }
+
+    /// Returns ids of the curves that should not be shown in the curve editor.
+    pub fn hidden_curves(&self) -> Vec<Uuid> {
+        self.hidden_curves.iter().cloned().collect()
+    }
+
+    pub fn is_curve_visible(&self, id: &Uuid) -> bool {
+        !self.hidden_curves.contains(id)
+    }
+
+    /// Returns `true` if the curve belongs to a locked track of the animation.
+    pub fn is_track_locked(&self, id: Uuid) -> bool {
+        self.locked_tracks.contains(&id)
+    }
+
+    pub fn is_curve_locked(&self, animation: &Animation, id: Uuid) -> bool {
+        animation.tracks().iter().any(|t| {
+            self.locked_tracks.contains(&t.id())
+                && t.data_container().curves_ref().iter().any(|c| c.id() == id)
+        })
+    }
+
+    /// Returns ids of the curves of locked tracks of the animation.
+    pub fn locked_curves(&self, animation: &Animation) -> FxHashSet<Uuid> {
+        animation
+            .tracks()
+            .iter()
+            .filter(|t| self.locked_tracks.contains(&t.id()))
+            .flat_map(|t| t.data_container().curves_ref().iter().map(|c| c.id()))
+            .collect()
+    }
+
+    /// Returns value limits of the track which the curve belongs to.
+    pub fn curve_value_limits(&self, animation: &Animation, id: Uuid) -> Option<ValueLimits> {
+        animation
+            .tracks()
+            .iter()
+            .find(|t| t.data_container().curves_ref().iter().any(|c| c.id() == id))
+            .and_then(|t| self.value_limits.get(&t.id()).cloned())
+    }
+
+    /// Returns weight of the track with the given id in preview mode.
+    pub fn track_weight(&self, id: &Uuid) -> f32 {
+        self.track_weights.get(id).cloned().unwrap_or(1.0)
+    }
+
+    /// Returns `true` if the track with the given id should be used in preview mode. If there is
+    /// at least one soloed track, then every other track is muted regardless of its own mute state.
+    pub fn is_track_previewed(&self, id: &Uuid) -> bool {
+        // Solo state is kept per track, so tracks of other animations must not affect the
+        // current one.
+        let any_soloed = self
+            .soloed_tracks
+            .iter()
+            .any(|soloed| self.track_views.contains_key(soloed));
+
+        if !any_soloed {
+            !self.muted_tracks.contains(id)
+        } else {
+            self.soloed_tracks.contains(id)
+        }
+    }
+
+    /// Pinned tracks are not filtered, they must stay visible.
+    fn apply_filter(&self, graph: &Graph, ui: &UserInterface) {
+        utils::apply_visibility_filter(self.tree_root, ui, |node| {
+            if let Some(track_view) = node.query_component::<TrackView>() {
+                Some(graph.try_get(track_view.target).map_or(false, |n| {
+                    n.name().to_lowercase().contains(&self.filter_text)
+                }))
+            } else if node.user_data_ref::<CurveViewData>().is_some() {
+                // Curves are hidden together with their tracks.
+                None
+            } else if node.query_component::<Tree>().is_some() {
+                // Group is visible only if it has at least one visible track.
+                Some(false)
+            } else {
+                None
+            }
+        });
+    }
+
+    /// Selects a property of the selected node to animate, or adds a track of the standard
+    /// property right away.
+    fn bind_selected_node(
+        &mut self,
+        ui: &mut UserInterface,
+        sender: &MessageSender,
+        scene: &Scene,
+        animation_player: Handle<Node>,
+        animation: Handle<Animation>,
+    ) {
+        match self.property_binding_mode {
+            PropertyBindingMode::Generic => {
+                let mut descriptors = Vec::new();
+                scene.graph[self.selected_node].as_reflect(&mut |node| {
+                    descriptors = object_to_property_tree("", node);
+                });
+
+                self.property_selector = PropertySelectorWindowBuilder::new(
+                    WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                        .with_title(WindowTitle::text("Select a Numeric Property To Animate"))
+                        .open(false),
+                )
+                .with_allowed_types(Some(FxHashSet::from_iter(define_allowed_types! {
+                    f32, f64, u64, i64, u32, i32, u16, i16, u8, i8, bool,
+
+                    Vector2<f32>, Vector2<f64>, Vector2<u64>, Vector2<i64>,
+                    Vector2<u32>, Vector2<i32>,
+                    Vector2<i16>, Vector2<u16>, Vector2<i8>, Vector2<u8>,
+
+                    Vector3<f32>, Vector3<f64>, Vector3<u64>, Vector3<i64>,
+                    Vector3<u32>, Vector3<i32>,
+                    Vector3<i16>, Vector3<u16>, Vector3<i8>, Vector3<u8>,
+
+                    Vector4<f32>, Vector4<f64>, Vector4<u64>, Vector4<i64>,
+                    Vector4<u32>, Vector4<i32>,
+                    Vector4<i16>, Vector4<u16>, Vector4<i8>, Vector4<u8>,
+
+                    UnitQuaternion<f32>
+                })))
+                .with_property_descriptors(descriptors)
+                .build(&mut ui.build_ctx());
+
+                ui.send_message(WindowMessage::open_modal(
+                    self.property_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            }
+            PropertyBindingMode::Position => {
+                add_track(
+                    Track::new_position().with_target(self.selected_node),
+                    sender,
+                    scene,
+                    animation_player,
+                    animation,
+                );
+            }
+            PropertyBindingMode::Rotation => {
+                add_track(
+                    Track::new_rotation().with_target(self.selected_node),
+                    sender,
+                    scene,
+                    animation_player,
+                    animation,
+                );
+            }
+            PropertyBindingMode::Scale => {
+                add_track(
+                    Track::new_scale().with_target(self.selected_node),
+                    sender,
+                    scene,
+                    animation_player,
+                    animation,
+                );
+            }
         }
     }
 
+    pub fn open_offset_dialog(&mut self, time_format: TimeFormat, fps: f32, ui: &UserInterface) {
+        self.offset_track_dialog.open(time_format, fps, ui);
+    }
+
     pub fn handle_ui_message(
         &mut self,
         message: &UiMessage,
@@ -711,7 +2620,27 @@ impl TrackList {
         animation: Handle<Animation>,
         ui: &mut UserInterface,
         scene: &Scene,
-    ) {
+    ) -> TrackListAction {
+        self.offset_track_dialog
+            .handle_ui_message(message, editor_scene, sender, ui);
+        self.property_path_dialog
+            .handle_ui_message(message, editor_scene, sender, scene, ui);
+
+        if let Some((tracks, limits)) = self.value_limits_dialog.handle_ui_message(message, ui) {
+            for track in tracks {
+                match limits {
+                    Some(limits) => {
+                        self.value_limits.insert(track, limits);
+                    }
+                    None => {
+                        self.value_limits.remove(&track);
+                    }
+                }
+            }
+            // Out of limits keys must be flagged again.
+            sender.send(Message::ForceSync);
+        }
+
         if let Some(ButtonMessage::Click) = message.data() {
             if message.destination() == self.add_track
                 || message.destination() == self.add_position_track
@@ -744,16 +2673,22 @@ impl TrackList {
                 } else if message.destination() == self.add_rotation_track {
                     self.property_binding_mode = PropertyBindingMode::Rotation;
                 }
+            } else if message.destination() == self.duplicate_track {
+                duplicate_selected_tracks(editor_scene, sender, scene);
             } else if message.destination() == self.toolbar.expand_all {
-                ui.send_message(TreeRootMessage::expand_all(
-                    self.tree_root,
-                    MessageDirection::ToWidget,
-                ));
+                for root in [self.tree_root, self.pinned_root] {
+                    ui.send_message(TreeRootMessage::expand_all(
+                        root,
+                        MessageDirection::ToWidget,
+                    ));
+                }
             } else if message.destination() == self.toolbar.collapse_all {
-                ui.send_message(TreeRootMessage::collapse_all(
-                    self.tree_root,
-                    MessageDirection::ToWidget,
-                ));
+                for root in [self.tree_root, self.pinned_root] {
+                    ui.send_message(TreeRootMessage::collapse_all(
+                        root,
+                        MessageDirection::ToWidget,
+                    ));
+                }
             } else if message.destination() == self.toolbar.clear_search_text {
                 ui.send_message(TextMessage::text(
                     self.toolbar.search_text,
@@ -761,20 +2696,67 @@ impl TrackList {
                     Default::default(),
                 ));
             }
+        } else if let Some(WidgetMessage::Drop(dropped)) = message.data() {
+            // A row could be dropped on any of the widgets of another row.
+            let target = ui.find_by_criteria_up(message.destination(), |n| {
+                n.query_component::<TrackView>().is_some()
+            });
+
+            if let (Some(dropped_view), Some(target_view)) = (
+                ui.try_get_node(*dropped)
+                    .and_then(|n| n.query_component::<TrackView>()),
+                ui.try_get_node(target)
+                    .and_then(|n| n.query_component::<TrackView>()),
+            ) {
+                if let Some(animation_ref) = scene
+                    .graph
+                    .try_get_of_type::<AnimationPlayer>(animation_player)
+                    .and_then(|p| p.animations().try_get(animation))
+                {
+                    let tracks = animation_ref.tracks();
+                    if let (Some(from), Some(to)) = (
+                        tracks.iter().position(|t| t.id() == dropped_view.id),
+                        tracks.iter().position(|t| t.id() == target_view.id),
+                    ) {
+                        if from != to {
+                            sender.do_scene_command(ReorderTrackCommand {
+                                animation_player,
+                                animation,
+                                from,
+                                to,
+                            });
+                        }
+                    }
+                }
+            } else if let Some(node) = ui
+                .try_get_node(*dropped)
+                .and_then(|n| n.cast::<SceneItem>())
+                .map(|item| item.entity_handle)
+            {
+                // A node from the world viewer is dropped, ask which property to animate.
+                if (message.destination() == self.panel
+                    || ui.is_node_child_of(message.destination(), self.panel))
+                    && scene.graph.is_valid_handle(node)
+                {
+                    self.selected_node = node;
+
+                    ui.send_message(PopupMessage::placement(
+                        self.node_drop_menu.menu,
+                        MessageDirection::ToWidget,
+                        Placement::Cursor(message.destination()),
+                    ));
+                    ui.send_message(PopupMessage::open(
+                        self.node_drop_menu.menu,
+                        MessageDirection::ToWidget,
+                    ));
+                }
+            }
         } else if let Some(TextMessage::Text(text)) = message.data() {
             if message.destination() == self.toolbar.search_text
                 && message.direction() == MessageDirection::FromWidget
             {
-                let filter_text = text.to_lowercase();
-                utils::apply_visibility_filter(self.tree_root, ui, |node| {
-                    if let Some(tree) = node.query_component::<Tree>() {
-                        if let Some(tree_text) = ui.node(tree.content).query_component::<Text>() {
-                            return Some(tree_text.text().to_lowercase().contains(&filter_text));
-                        }
-                    }
-
-                    None
-                });
+                self.filter_text = text.to_lowercase();
+                self.apply_filter(&scene.graph, ui);
             }
         } else if let Some(WindowMessage::Close) = message.data() {
             if message.destination() == self.node_selector
@@ -791,88 +2773,52 @@ impl TrackList {
                 if let Some(first) = node_selection.first() {
                     self.selected_node = *first;
 
-                    match self.property_binding_mode {
-                        PropertyBindingMode::Generic => {
-                            let mut descriptors = Vec::new();
-                            scene.graph[*first].as_reflect(&mut |node| {
-                                descriptors = object_to_property_tree("", node);
-                            });
-
-                            self.property_selector = PropertySelectorWindowBuilder::new(
-                                WindowBuilder::new(
-                                    WidgetBuilder::new().with_width(300.0).with_height(400.0),
-                                )
-                                .with_title(WindowTitle::text(
-                                    "Select a Numeric Property To Animate",
-                                ))
-                                .open(false),
-                            )
-                            .with_allowed_types(Some(FxHashSet::from_iter(define_allowed_types! {
-                                f32, f64, u64, i64, u32, i32, u16, i16, u8, i8, bool,
-
-                                Vector2<f32>, Vector2<f64>, Vector2<u64>, Vector2<i64>,
-                                Vector2<u32>, Vector2<i32>,
-                                Vector2<i16>, Vector2<u16>, Vector2<i8>, Vector2<u8>,
-
-                                Vector3<f32>, Vector3<f64>, Vector3<u64>, Vector3<i64>,
-                                Vector3<u32>, Vector3<i32>,
-                                Vector3<i16>, Vector3<u16>, Vector3<i8>, Vector3<u8>,
-
-                                Vector4<f32>, Vector4<f64>, Vector4<u64>, Vector4<i64>,
-                                Vector4<u32>, Vector4<i32>,
-                                Vector4<i16>, Vector4<u16>, Vector4<i8>, Vector4<u8>,
-
-                                UnitQuaternion<f32>
-                            })))
-                            .with_property_descriptors(descriptors)
-                            .build(&mut ui.build_ctx());
-
-                            ui.send_message(WindowMessage::open_modal(
-                                self.property_selector,
-                                MessageDirection::ToWidget,
-                                true,
-                            ));
-                        }
-                        PropertyBindingMode::Position => {
-                            sender.do_scene_command(AddTrackCommand::new(
-                                animation_player,
-                                animation,
-                                Track::new_position().with_target(self.selected_node),
-                            ));
-                        }
-                        PropertyBindingMode::Rotation => {
-                            sender.do_scene_command(AddTrackCommand::new(
-                                animation_player,
-                                animation,
-                                Track::new_rotation().with_target(self.selected_node),
-                            ));
-                        }
-                        PropertyBindingMode::Scale => {
-                            sender.do_scene_command(AddTrackCommand::new(
-                                animation_player,
-                                animation,
-                                Track::new_scale().with_target(self.selected_node),
-                            ));
-                        }
-                    }
+                    self.bind_selected_node(ui, sender, scene, animation_player, animation);
                 }
             } else if message.destination() == self.context_menu.target_node_selector {
                 if let Selection::Animation(ref scene_selection) = editor_scene.selection {
-                    if let Some(first) = node_selection.first() {
+                    let animation = scene
+                        .graph
+                        .try_get(scene_selection.animation_player)
+                        .and_then(|n| n.query_component_ref::<AnimationPlayer>())
+                        .and_then(|p| p.animations().try_get(scene_selection.animation));
+                    if let (Some(first), Some(animation)) = (node_selection.first(), animation) {
                         let mut commands = Vec::new();
 
                         for entity in scene_selection.entities.iter() {
                             if let SelectedEntity::Track(id) = entity {
-                                commands.push(SceneCommand::new(SetTrackTargetCommand {
-                                    animation_player_handle: scene_selection.animation_player,
-                                    animation_handle: scene_selection.animation,
-                                    track: *id,
-                                    target: *first,
-                                }));
+                                if let Some(track) =
+                                    animation.tracks().iter().find(|t| t.id() == *id)
+                                {
+                                    // A track can be bound only to a node that has the animated
+                                    // property.
+                                    match validate_binding(&scene.graph[*first], track.binding()) {
+                                        Ok(()) => {
+                                            commands.push(SceneCommand::new(
+                                                RetargetTrackCommand {
+                                                    animation_player_handle: scene_selection
+                                                        .animation_player,
+                                                    animation_handle: scene_selection.animation,
+                                                    track: *id,
+                                                    target: *first,
+                                                    binding: track.binding().clone(),
+                                                },
+                                            ));
+                                        }
+                                        Err(err) => Log::err(format!(
+                                            "Unable to bind {} track to {} node. Reason: {}",
+                                            track.binding(),
+                                            scene.graph[*first].name(),
+                                            err
+                                        )),
+                                    }
+                                }
                             }
                         }
 
-                        sender.do_scene_command(CommandGroup::from(commands));
+                        if !commands.is_empty() {
+                            sender.do_scene_command(CommandGroup::from(commands));
+                        }
                     }
                 }
             }
@@ -901,11 +2847,7 @@ impl TrackList {
 
                                     track.set_target(self.selected_node);
 
-                                    sender.do_scene_command(AddTrackCommand::new(
-                                        animation_player,
-                                        animation,
-                                        track,
-                                    ));
+                                    add_track(track, sender, scene, animation_player, animation);
                                 }
                             }
                             Err(e) => {
@@ -921,9 +2863,37 @@ impl TrackList {
                 }
             }
         } else if let Some(TreeRootMessage::Selected(selection)) = message.data() {
-            if message.destination() == self.tree_root
+            if (message.destination() == self.tree_root
+                || message.destination() == self.pinned_root)
                 && message.direction == MessageDirection::FromWidget
             {
+                let current = if let Selection::Animation(ref current) = editor_scene.selection {
+                    current.entities.clone()
+                } else {
+                    Default::default()
+                };
+
+                // Pinned and regular rows are in separate trees, selection in one of them
+                // replaces selection in the other one, unless Ctrl is held.
+                let mut selection = selection.clone();
+                if ui.keyboard_modifiers().control {
+                    let other_root = if message.destination() == self.tree_root {
+                        self.pinned_root
+                    } else {
+                        self.tree_root
+                    };
+                    if let Some(other_root) = ui.node(other_root).query_component::<TreeRoot>() {
+                        selection.extend(
+                            other_root
+                                .selected
+                                .iter()
+                                .filter(|s| !selection.contains(s))
+                                .cloned()
+                                .collect::<Vec<_>>(),
+                        );
+                    }
+                }
+
                 let selection = Selection::Animation(AnimationSelection {
                     animation_player,
                     animation,
@@ -945,53 +2915,252 @@ impl TrackList {
                         .collect(),
                 });
 
+                // The second click of a double click selects the same row again.
+                if !matches!(selection, Selection::Animation(ref new) if new.entities == current) {
+                    self.selection_before_click = current;
+                }
+
                 sender.do_scene_command(ChangeSelectionCommand::new(
                     selection,
                     editor_scene.selection.clone(),
                 ));
             }
+        } else if let Some(WidgetMessage::DoubleClick { button }) = message.data() {
+            if *button == MouseButton::Left {
+                if let Some(entity) = self.row_entity(message.destination(), ui) {
+                    return TrackListAction::ToggleIsolation {
+                        entity,
+                        previous: self.selection_before_click.clone(),
+                    };
+                }
+            }
         } else if let Some(MenuItemMessage::Click) = message.data() {
             if message.destination() == self.context_menu.remove_track {
                 if let Selection::Animation(ref selection) = editor_scene.selection {
-                    if let Some(animation_player) = scene
+                    if let Some(animation_player) = scene
+                        .graph
+                        .try_get(selection.animation_player)
+                        .and_then(|n| n.query_component_ref::<AnimationPlayer>())
+                    {
+                        if let Some(animation) =
+                            animation_player.animations().try_get(selection.animation)
+                        {
+                            let mut commands =
+                                vec![SceneCommand::new(ChangeSelectionCommand::new(
+                                    Selection::Animation(AnimationSelection {
+                                        animation_player: selection.animation_player,
+                                        animation: selection.animation,
+                                        // Just reset inner selection.
+                                        entities: vec![],
+                                    }),
+                                    editor_scene.selection.clone(),
+                                ))];
+
+                            for entity in selection.entities.iter() {
+                                if let SelectedEntity::Track(id) = entity {
+                                    let index = animation
+                                        .tracks()
+                                        .iter()
+                                        .position(|t| t.id() == *id)
+                                        .unwrap();
+
+                                    commands.push(SceneCommand::new(RemoveTrackCommand::new(
+                                        selection.animation_player,
+                                        selection.animation,
+                                        index,
+                                    )));
+                                }
+                            }
+
+                            sender.do_scene_command(CommandGroup::from(commands));
+                        }
+                    }
+                }
+            } else if message.destination() == self.context_menu.duplicate_track {
+                duplicate_selected_tracks(editor_scene, sender, scene);
+            } else if message.destination() == self.context_menu.copy_tracks {
+                return TrackListAction::CopySelectedTracks;
+            } else if message.destination() == self.context_menu.paste_tracks {
+                return TrackListAction::PasteTracks;
+            } else if message.destination() == self.context_menu.offset_track {
+                return TrackListAction::OffsetSelectedTracks;
+            } else if message.destination() == self.context_menu.value_limits {
+                if let Selection::Animation(ref selection) = editor_scene.selection {
+                    let tracks = selection
+                        .entities
+                        .iter()
+                        .filter_map(|e| {
+                            if let SelectedEntity::Track(id) = e {
+                                Some(*id)
+                            } else {
+                                None
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    let current = tracks
+                        .iter()
+                        .find_map(|id| self.value_limits.get(id).cloned());
+                    self.value_limits_dialog.open(tracks, current, ui);
+                }
+            } else if message.destination() == self.context_menu.select_all_keys {
+                if let Selection::Animation(ref selection) = editor_scene.selection {
+                    if let Some(new_selection) = scene
+                        .graph
+                        .try_get(selection.animation_player)
+                        .and_then(|n| n.query_component_ref::<AnimationPlayer>())
+                        .and_then(|p| p.animations().try_get(selection.animation))
+                        .and_then(|a| select_all_track_keys(a, selection))
+                    {
+                        sender.do_scene_command(ChangeSelectionCommand::new(
+                            Selection::Animation(new_selection),
+                            editor_scene.selection.clone(),
+                        ));
+                    }
+                }
+            } else if message.destination() == self.node_drop_menu.position
+                || message.destination() == self.node_drop_menu.rotation
+                || message.destination() == self.node_drop_menu.scale
+                || message.destination() == self.node_drop_menu.property
+            {
+                self.property_binding_mode =
+                    if message.destination() == self.node_drop_menu.position {
+                        PropertyBindingMode::Position
+                    } else if message.destination() == self.node_drop_menu.rotation {
+                        PropertyBindingMode::Rotation
+                    } else if message.destination() == self.node_drop_menu.scale {
+                        PropertyBindingMode::Scale
+                    } else {
+                        PropertyBindingMode::Generic
+                    };
+
+                if scene.graph.is_valid_handle(self.selected_node) {
+                    self.bind_selected_node(ui, sender, scene, animation_player, animation);
+                }
+            } else if message.destination() == self.context_menu.mute
+                || message.destination() == self.context_menu.solo
+            {
+                if let Selection::Animation(ref selection) = editor_scene.selection {
+                    let mute = message.destination() == self.context_menu.mute;
+                    let views = selection
+                        .entities
+                        .iter()
+                        .filter_map(|e| {
+                            if let SelectedEntity::Track(id) = e {
+                                self.track_views.get(id).cloned()
+                            } else {
+                                None
+                            }
+                        })
+                        .collect::<Vec<_>>();
+
+                    // Toggle the state of all tracks at once: if any of them is not muted (or
+                    // soloed), then all of them become muted, otherwise the state is cleared.
+                    let value = views.iter().any(|view| {
+                        let view_ref = ui.node(*view).query_component::<TrackView>().unwrap();
+                        if mute {
+                            !view_ref.muted
+                        } else {
+                            !view_ref.soloed
+                        }
+                    });
+
+                    for view in views {
+                        ui.send_message(if mute {
+                            TrackViewMessage::track_muted(view, MessageDirection::ToWidget, value)
+                        } else {
+                            TrackViewMessage::track_soloed(view, MessageDirection::ToWidget, value)
+                        });
+                    }
+                }
+            } else if message.destination() == self.context_menu.to_world_space
+                || message.destination() == self.context_menu.to_local_space
+            {
+                let space = if message.destination() == self.context_menu.to_world_space {
+                    TrackSpace::World
+                } else {
+                    TrackSpace::Local
+                };
+                if let Selection::Animation(ref selection) = editor_scene.selection {
+                    if let Some(animation) = scene
+                        .graph
+                        .try_get(selection.animation_player)
+                        .and_then(|n| n.query_component_ref::<AnimationPlayer>())
+                        .and_then(|p| p.animations().try_get(selection.animation))
+                    {
+                        let mut commands = Vec::new();
+                        for track in animation
+                            .tracks()
+                            .iter()
+                            .filter(|t| selection.entities.contains(&SelectedEntity::Track(t.id())))
+                        {
+                            match ConvertTrackSpaceCommand::new(
+                                selection.animation_player,
+                                selection.animation,
+                                track,
+                                space,
+                                &scene.graph,
+                            ) {
+                                Ok(command) => commands.push(SceneCommand::new(command)),
+                                Err(err) => Log::err(format!(
+                                    "Unable to convert {} track. {}",
+                                    track.binding(),
+                                    err
+                                )),
+                            }
+                        }
+
+                        if !commands.is_empty() {
+                            sender.do_scene_command(CommandGroup::from(commands));
+                        }
+                    }
+                }
+            } else if message.destination() == self.context_menu.focus_in_scene {
+                if let Selection::Animation(ref selection) = editor_scene.selection {
+                    if let Some(animation) = scene
                         .graph
                         .try_get(selection.animation_player)
                         .and_then(|n| n.query_component_ref::<AnimationPlayer>())
+                        .and_then(|p| p.animations().try_get(selection.animation))
                     {
-                        if let Some(animation) =
-                            animation_player.animations().try_get(selection.animation)
-                        {
-                            let mut commands =
-                                vec![SceneCommand::new(ChangeSelectionCommand::new(
-                                    Selection::Animation(AnimationSelection {
-                                        animation_player: selection.animation_player,
-                                        animation: selection.animation,
-                                        // Just reset inner selection.
-                                        entities: vec![],
-                                    }),
-                                    editor_scene.selection.clone(),
-                                ))];
-
-                            for entity in selection.entities.iter() {
-                                if let SelectedEntity::Track(id) = entity {
-                                    let index = animation
-                                        .tracks()
-                                        .iter()
-                                        .position(|t| t.id() == *id)
-                                        .unwrap();
-
-                                    commands.push(SceneCommand::new(RemoveTrackCommand::new(
-                                        selection.animation_player,
-                                        selection.animation,
-                                        index,
-                                    )));
+                        let mut targets = Vec::new();
+                        for entity in selection.entities.iter() {
+                            if let SelectedEntity::Track(id) = entity {
+                                if let Some(track) =
+                                    animation.tracks().iter().find(|t| t.id() == *id)
+                                {
+                                    if scene.graph.is_valid_handle(track.target())
+                                        && !targets.contains(&track.target())
+                                    {
+                                        targets.push(track.target());
+                                    }
                                 }
                             }
+                        }
 
-                            sender.do_scene_command(CommandGroup::from(commands));
+                        if !targets.is_empty() {
+                            sender.do_scene_command(ChangeSelectionCommand::new(
+                                Selection::Graph(GraphSelection::from_list(targets)),
+                                editor_scene.selection.clone(),
+                            ));
                         }
                     }
                 }
+            } else if message.destination() == self.context_menu.edit_property_path {
+                if let Selection::Animation(ref selection) = editor_scene.selection {
+                    let track = scene
+                        .graph
+                        .try_get(selection.animation_player)
+                        .and_then(|n| n.query_component_ref::<AnimationPlayer>())
+                        .and_then(|p| p.animations().try_get(selection.animation))
+                        .and_then(|a| {
+                            a.tracks().iter().find(|t| {
+                                selection.entities.contains(&SelectedEntity::Track(t.id()))
+                            })
+                        });
+                    if let Some(track) = track {
+                        self.property_path_dialog.open(track, ui);
+                    }
+                }
             } else if message.destination() == self.context_menu.set_target {
                 self.context_menu.target_node_selector = NodeSelectorWindowBuilder::new(
                     WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
@@ -1042,27 +3211,216 @@ impl TrackList {
                     }
                 }
             }
+        } else if let Some(TrackViewMessage::TrackMuted(muted)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(track_view_ref) = ui
+                    .node(message.destination())
+                    .query_component::<TrackView>()
+                {
+                    let changed = if *muted {
+                        self.muted_tracks.insert(track_view_ref.id)
+                    } else {
+                        self.muted_tracks.remove(&track_view_ref.id)
+                    };
+
+                    if changed {
+                        self.sync_group_toggles(ui);
+                        return TrackListAction::PreviewTracksChanged;
+                    }
+                }
+            }
+        } else if let Some(TrackViewMessage::TrackLocked(locked)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(track_view_ref) = ui
+                    .node(message.destination())
+                    .query_component::<TrackView>()
+                {
+                    if *locked {
+                        self.locked_tracks.insert(track_view_ref.id);
+                    } else {
+                        self.locked_tracks.remove(&track_view_ref.id);
+                    }
+                }
+            }
+        } else if let Some(TrackViewMessage::TrackPinned(pinned)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(track_view_ref) = ui
+                    .node(message.destination())
+                    .query_component::<TrackView>()
+                {
+                    if *pinned {
+                        self.pinned_tracks.insert(track_view_ref.id);
+                    } else {
+                        self.pinned_tracks.remove(&track_view_ref.id);
+                    }
+
+                    // The row is moved to another tree on next sync.
+                    sender.send(Message::ForceSync);
+                }
+            }
+        } else if let Some(TrackViewMessage::TrackWeight(weight)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(track_view_ref) = ui
+                    .node(message.destination())
+                    .query_component::<TrackView>()
+                {
+                    if *weight == 1.0 {
+                        self.track_weights.remove(&track_view_ref.id);
+                    } else {
+                        self.track_weights.insert(track_view_ref.id, *weight);
+                    }
+                    return TrackListAction::PreviewTracksChanged;
+                }
+            }
+        } else if let Some(TrackViewMessage::TrackSoloed(soloed)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(track_view_ref) = ui
+                    .node(message.destination())
+                    .query_component::<TrackView>()
+                {
+                    let changed = if *soloed {
+                        self.soloed_tracks.insert(track_view_ref.id)
+                    } else {
+                        self.soloed_tracks.remove(&track_view_ref.id)
+                    };
+
+                    if changed {
+                        self.sync_group_toggles(ui);
+                        return TrackListAction::PreviewTracksChanged;
+                    }
+                }
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(checked))) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(curve) = self.curve_visibility_switches.get(&message.destination()) {
+                    let changed = if *checked {
+                        self.hidden_curves.remove(curve)
+                    } else {
+                        self.hidden_curves.insert(*curve)
+                    };
+
+                    if changed {
+                        return TrackListAction::CurveVisibilityChanged;
+                    }
+                } else if let Some(toggle) = self.group_toggles.get(&message.destination()).cloned()
+                {
+                    let group = match toggle {
+                        GroupToggle::Mute { group } | GroupToggle::Solo { group } => group,
+                    };
+
+                    for id in self.group_track_ids(group, ui) {
+                        let (tracks, message) = match toggle {
+                            GroupToggle::Mute { .. } => (
+                                &mut self.muted_tracks,
+                                TrackViewMessage::track_muted(
+                                    self.track_views[&id],
+                                    MessageDirection::ToWidget,
+                                    *checked,
+                                ),
+                            ),
+                            GroupToggle::Solo { .. } => (
+                                &mut self.soloed_tracks,
+                                TrackViewMessage::track_soloed(
+                                    self.track_views[&id],
+                                    MessageDirection::ToWidget,
+                                    *checked,
+                                ),
+                            ),
+                        };
+
+                        if *checked {
+                            tracks.insert(id);
+                        } else {
+                            tracks.remove(&id);
+                        }
+
+                        send_sync_message(ui, message);
+                    }
+
+                    self.sync_group_toggles(ui);
+
+                    return TrackListAction::PreviewTracksChanged;
+                }
+            }
+        } else if let Some(TreeMessage::Expand { expand, .. }) = message.data() {
+            if let Some((key, _)) = self
+                .group_views
+                .iter()
+                .find(|(_, group)| **group == message.destination())
+            {
+                if *expand {
+                    self.collapsed_groups.remove(key);
+                } else {
+                    self.collapsed_groups.insert(*key);
+                }
+            }
         }
+
+        TrackListAction::None
     }
 
     pub fn clear(&mut self, ui: &UserInterface) {
-        ui.send_message(TreeRootMessage::items(
-            self.tree_root,
-            MessageDirection::ToWidget,
-            vec![],
-        ));
+        for root in [self.tree_root, self.pinned_root] {
+            ui.send_message(TreeRootMessage::items(
+                root,
+                MessageDirection::ToWidget,
+                vec![],
+            ));
+        }
         self.group_views.clear();
+        self.group_toggles.clear();
         self.track_views.clear();
+        self.curve_views.clear();
+        self.curve_visibility_switches.clear();
+        self.track_order.clear();
         self.selected_node = Handle::NONE;
     }
 
+    /// `saved_track_states` overrides enabled state of the tracks, it is used in preview mode
+    /// where tracks are enabled or disabled according to their mute and solo state.
     pub fn sync_to_model(
         &mut self,
         animation: &Animation,
         graph: &Graph,
         editor_scene: &EditorScene,
+        saved_track_states: &FxHashMap<Uuid, bool>,
+        key_density_threshold: usize,
         ui: &mut UserInterface,
     ) {
+        let is_track_enabled = |track: &Track| {
+            saved_track_states
+                .get(&track.id())
+                .cloned()
+                .unwrap_or_else(|| track.is_enabled())
+        };
+
+        // Tracks must be re-grouped if their target nodes were deleted (or restored back), or if
+        // they were pinned or unpinned.
+        let regroup = animation.tracks().iter().any(|track| {
+            match (
+                self.track_views.get(&track.id()),
+                self.group_views.get(&group_key(graph, track.target())),
+            ) {
+                (Some(track_view), _) if self.pinned_tracks.contains(&track.id()) => !ui
+                    .node(self.pinned_root)
+                    .query_component::<TreeRoot>()
+                    .map_or(false, |root| root.items().contains(track_view)),
+                (Some(track_view), Some(group)) => !ui
+                    .node(*group)
+                    .query_component::<Tree>()
+                    .map_or(false, |tree| tree.items.contains(track_view)),
+                (Some(_), None) => true,
+                _ => false,
+            }
+        });
+        if regroup {
+            self.clear(ui);
+        }
+
+        // Weights are reset when another animation is selected.
+        self.track_weights
+            .retain(|id, _| animation.tracks().iter().any(|t| t.id() == *id));
+
         match animation.tracks().len().cmp(&self.track_views.len()) {
             Ordering::Less => {
                 for track_view in self.track_views.clone().values() {
@@ -1085,12 +3443,19 @@ impl TrackList {
                                 .user_data_ref::<CurveViewData>()
                                 .unwrap();
                             assert!(self.curve_views.remove(&curve_item_ref.id).is_some());
+                            self.curve_visibility_switches
+                                .retain(|_, id| *id != curve_item_ref.id);
                         }
 
+                        let root = if self.pinned_tracks.contains(&track_view_data.id) {
+                            self.pinned_root
+                        } else {
+                            self.tree_root
+                        };
                         send_sync_message(
                             ui,
                             TreeRootMessage::remove_item(
-                                self.tree_root,
+                                root,
                                 MessageDirection::ToWidget,
                                 *track_view,
                             ),
@@ -1099,9 +3464,20 @@ impl TrackList {
                         assert!(self.track_views.remove(&track_view_data.id).is_some());
 
                         // Remove group if it is empty.
-                        if let Some(group) = self.group_views.get(&track_view_data.target) {
+                        if let Some((key, group)) = self
+                            .group_views
+                            .iter()
+                            .find(|(_, group)| {
+                                ui.node(**group)
+                                    .query_component::<Tree>()
+                                    .unwrap()
+                                    .items
+                                    .contains(track_view)
+                            })
+                            .map(|(key, group)| (*key, *group))
+                        {
                             if ui
-                                .node(*group)
+                                .node(group)
                                 .query_component::<Tree>()
                                 .unwrap()
                                 .items
@@ -1113,11 +3489,16 @@ impl TrackList {
                                     TreeRootMessage::remove_item(
                                         self.tree_root,
                                         MessageDirection::ToWidget,
-                                        *group,
+                                        group,
                                     ),
                                 );
 
-                                assert!(self.group_views.remove(&track_view_data.target).is_some());
+                                self.group_toggles.retain(|_, toggle| match toggle {
+                                    GroupToggle::Mute { group: g }
+                                    | GroupToggle::Solo { group: g } => *g != group,
+                                });
+
+                                assert!(self.group_views.remove(&key).is_some());
                             }
                         }
                     }
@@ -1134,35 +3515,79 @@ impl TrackList {
                         .map(|v| ui.node(*v))
                         .all(|v| v.query_component::<TrackView>().unwrap().id != model_track.id())
                     {
-                        let parent_group = match self.group_views.entry(model_track.target()) {
-                            Entry::Occupied(entry) => *entry.get(),
-                            Entry::Vacant(entry) => {
-                                let ctx = &mut ui.build_ctx();
-                                let group = TreeBuilder::new(WidgetBuilder::new())
-                                    .with_content(
-                                        TextBuilder::new(WidgetBuilder::new())
-                                            .with_text(format!(
-                                                "{} ({}:{})",
-                                                graph
-                                                    .try_get(model_track.target())
-                                                    .map(|n| n.name())
-                                                    .unwrap_or_default(),
-                                                model_track.target().index(),
-                                                model_track.target().generation()
-                                            ))
+                        let pinned = self.pinned_tracks.contains(&model_track.id());
+                        let key = group_key(graph, model_track.target());
+                        // Pinned tracks are added right to the pinned tree, without a group.
+                        let parent_group = if pinned {
+                            Handle::NONE
+                        } else {
+                            match self.group_views.entry(key) {
+                                Entry::Occupied(entry) => *entry.get(),
+                                Entry::Vacant(entry) => {
+                                    let ctx = &mut ui.build_ctx();
+                                    let name = if key.is_some() {
+                                        format!(
+                                            "{} ({}:{})",
+                                            graph[key].name(),
+                                            key.index(),
+                                            key.generation()
+                                        )
+                                    } else {
+                                        "Orphaned".to_string()
+                                    };
+                                    let mute_switch = make_track_toggle(
+                                        ctx,
+                                        "M",
+                                        "Mute Every Track Of The Group In Preview",
+                                        Color::opaque(220, 80, 80),
+                                        false,
+                                    );
+                                    let solo_switch = make_track_toggle(
+                                        ctx,
+                                        "S",
+                                        "Solo Every Track Of The Group In Preview",
+                                        Color::opaque(230, 200, 60),
+                                        false,
+                                    );
+                                    let group_builder = TreeBuilder::new(WidgetBuilder::new())
+                                        .with_content(
+                                            StackPanelBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .with_child(
+                                                        TextBuilder::new(WidgetBuilder::new())
+                                                            .with_text(name)
+                                                            .with_vertical_text_alignment(
+                                                                VerticalAlignment::Center,
+                                                            )
+                                                            .build(ctx),
+                                                    )
+                                                    .with_child(mute_switch)
+                                                    .with_child(solo_switch),
+                                            )
+                                            .with_orientation(Orientation::Horizontal)
                                             .build(ctx),
-                                    )
-                                    .build(ctx);
-                                send_sync_message(
-                                    ui,
-                                    TreeRootMessage::add_item(
-                                        self.tree_root,
-                                        MessageDirection::ToWidget,
-                                        group,
-                                    ),
-                                );
+                                        )
+                                        .with_expanded(!self.collapsed_groups.contains(&key));
+                                    let group = build_switch_row(
+                                        group_builder,
+                                        vec![mute_switch, solo_switch],
+                                        ctx,
+                                    );
+                                    self.group_toggles
+                                        .insert(mute_switch, GroupToggle::Mute { group });
+                                    self.group_toggles
+                                        .insert(solo_switch, GroupToggle::Solo { group });
+                                    send_sync_message(
+                                        ui,
+                                        TreeRootMessage::add_item(
+                                            self.tree_root,
+                                            MessageDirection::ToWidget,
+                                            group,
+                                        ),
+                                    );
 
-                                *entry.insert(group)
+                                    *entry.insert(group)
+                                }
                             }
                         };
 
@@ -1174,19 +3599,71 @@ impl TrackList {
                             .iter()
                             .enumerate()
                             .map(|(i, curve)| {
-                                let curve_view = TreeBuilder::new(
+                                let visibility_switch = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::right(4.0))
+                                        .with_vertical_alignment(VerticalAlignment::Center)
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Show Curve In Curve Editor",
+                                        )),
+                                )
+                                .checked(Some(!self.hidden_curves.contains(&curve.id())))
+                                .build(ctx);
+                                self.curve_visibility_switches
+                                    .insert(visibility_switch, curve.id());
+
+                                let curve_view_builder = TreeBuilder::new(
                                     WidgetBuilder::new()
+                                        .with_opacity(track_opacity(is_track_enabled(model_track)))
                                         .with_user_data(Rc::new(CurveViewData { id: curve.id() })),
                                 )
                                 .with_content(
-                                    TextBuilder::new(WidgetBuilder::new())
-                                        .with_text(format!(
-                                            "Curve - {}",
-                                            ["X", "Y", "Z", "W"].get(i).unwrap_or(&"_"),
-                                        ))
-                                        .build(ctx),
-                                )
-                                .build(ctx);
+                                    StackPanelBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_child(visibility_switch)
+                                            .with_child(
+                                                BorderBuilder::new(
+                                                    WidgetBuilder::new()
+                                                        .with_width(10.0)
+                                                        .with_height(10.0)
+                                                        .with_margin(Thickness::right(4.0))
+                                                        .with_vertical_alignment(
+                                                            VerticalAlignment::Center,
+                                                        )
+                                                        .with_background(Brush::Solid(
+                                                            curve_color(
+                                                                model_track
+                                                                    .data_container()
+                                                                    .value_kind(),
+                                                                i,
+                                                            ),
+                                                        )),
+                                                )
+                                                .build(ctx),
+                                            )
+                                            .with_child(
+                                                TextBuilder::new(WidgetBuilder::new())
+                                                    .with_text(format!(
+                                                        "Curve - {}",
+                                                        curve_component_name(
+                                                            model_track
+                                                                .data_container()
+                                                                .value_kind(),
+                                                            i
+                                                        ),
+                                                    ))
+                                                    .build(ctx),
+                                            ),
+                                    )
+                                    .with_orientation(Orientation::Horizontal)
+                                    .build(ctx),
+                                );
+                                let curve_view = build_switch_row(
+                                    curve_view_builder,
+                                    vec![visibility_switch],
+                                    ctx,
+                                );
 
                                 self.curve_views.insert(curve.id(), curve_view);
 
@@ -1201,20 +3678,36 @@ impl TrackList {
                             )
                             .with_items(curves),
                         )
-                        .with_track_enabled(model_track.is_enabled())
+                        .with_track_enabled(is_track_enabled(model_track))
+                        .with_muted(self.muted_tracks.contains(&model_track.id()))
+                        .with_soloed(self.soloed_tracks.contains(&model_track.id()))
+                        .with_locked(self.locked_tracks.contains(&model_track.id()))
+                        .with_pinned(pinned)
+                        .with_weight(self.track_weight(&model_track.id()))
                         .with_id(model_track.id())
                         .with_target(model_track.target())
-                        .with_name(format!("{}", model_track.binding()))
+                        .with_name(track_name(model_track.binding()))
                         .build(ctx);
 
-                        send_sync_message(
-                            ui,
-                            TreeMessage::add_item(
-                                parent_group,
-                                MessageDirection::ToWidget,
-                                track_view,
-                            ),
-                        );
+                        if pinned {
+                            send_sync_message(
+                                ui,
+                                TreeRootMessage::add_item(
+                                    self.pinned_root,
+                                    MessageDirection::ToWidget,
+                                    track_view,
+                                ),
+                            );
+                        } else {
+                            send_sync_message(
+                                ui,
+                                TreeMessage::add_item(
+                                    parent_group,
+                                    MessageDirection::ToWidget,
+                                    track_view,
+                                ),
+                            );
+                        }
 
                         assert!(self
                             .track_views
@@ -1226,23 +3719,76 @@ impl TrackList {
         }
 
         if let Selection::Animation(ref selection) = editor_scene.selection {
+            // Curves are shown again once they're deselected, so the state does not outlive
+            // the selection.
+            self.hidden_curves.retain(|id| {
+                selection
+                    .entities
+                    .iter()
+                    .any(|e| matches!(e, SelectedEntity::Curve(curve) if curve == id))
+            });
+            for (switch, curve) in self.curve_visibility_switches.iter() {
+                send_sync_message(
+                    ui,
+                    CheckBoxMessage::checked(
+                        *switch,
+                        MessageDirection::ToWidget,
+                        Some(!self.hidden_curves.contains(curve)),
+                    ),
+                );
+            }
+
             let mut any_track_selected = false;
-            let tree_selection = selection
+            let mut any_target_valid = false;
+            let is_pinned = |track: Option<&Track>| {
+                track.map_or(false, |t| self.pinned_tracks.contains(&t.id()))
+            };
+            let (pinned_selection, tree_selection): (Vec<_>, Vec<_>) = selection
                 .entities
                 .iter()
                 .filter_map(|e| match e {
                     SelectedEntity::Track(id) => {
                         any_track_selected = true;
-                        self.track_views.get(id).cloned()
+                        if animation
+                            .tracks()
+                            .iter()
+                            .any(|t| t.id() == *id && graph.is_valid_handle(t.target()))
+                        {
+                            any_target_valid = true;
+                        }
+                        let pinned = is_pinned(animation.tracks().iter().find(|t| t.id() == *id));
+                        self.track_views.get(id).map(|view| (*view, pinned))
+                    }
+                    SelectedEntity::Curve(id) => {
+                        let pinned = is_pinned(animation.tracks().iter().find(|t| {
+                            t.data_container()
+                                .curves_ref()
+                                .iter()
+                                .any(|c| c.id() == *id)
+                        }));
+                        self.curve_views.get(id).map(|view| (*view, pinned))
                     }
-                    SelectedEntity::Curve(id) => self.curve_views.get(id).cloned(),
-                    SelectedEntity::Signal(_) => None,
+                    SelectedEntity::Signal(_)
+                    | SelectedEntity::Marker(_)
+                    | SelectedEntity::Keyframe { .. } => None,
                 })
-                .collect();
+                .partition(|(_, pinned)| *pinned);
 
             send_sync_message(
                 ui,
-                TreeRootMessage::select(self.tree_root, MessageDirection::ToWidget, tree_selection),
+                TreeRootMessage::select(
+                    self.tree_root,
+                    MessageDirection::ToWidget,
+                    tree_selection.into_iter().map(|(view, _)| view).collect(),
+                ),
+            );
+            send_sync_message(
+                ui,
+                TreeRootMessage::select(
+                    self.pinned_root,
+                    MessageDirection::ToWidget,
+                    pinned_selection.into_iter().map(|(view, _)| view).collect(),
+                ),
             );
 
             send_sync_message(
@@ -1261,36 +3807,160 @@ impl TrackList {
                     any_track_selected,
                 ),
             );
+            for item in [
+                self.duplicate_track,
+                self.context_menu.duplicate_track,
+                self.context_menu.copy_tracks,
+                self.context_menu.offset_track,
+                self.context_menu.value_limits,
+                self.context_menu.edit_property_path,
+                self.context_menu.to_world_space,
+                self.context_menu.to_local_space,
+                self.context_menu.select_all_keys,
+                self.context_menu.mute,
+                self.context_menu.solo,
+            ] {
+                send_sync_message(
+                    ui,
+                    WidgetMessage::enabled(item, MessageDirection::ToWidget, any_track_selected),
+                );
+            }
+            // Only existing nodes could be selected in the scene.
+            send_sync_message(
+                ui,
+                WidgetMessage::enabled(
+                    self.context_menu.focus_in_scene,
+                    MessageDirection::ToWidget,
+                    any_target_valid,
+                ),
+            );
         }
 
         for track_model in animation.tracks() {
             if let Some(track_view) = self.track_views.get(&track_model.id()) {
                 let track_view_ref = ui.node(*track_view).query_component::<TrackView>().unwrap();
-                if track_view_ref.track_enabled != track_model.is_enabled() {
+                let enabled = is_track_enabled(track_model);
+                if track_view_ref.track_enabled != enabled {
                     send_sync_message(
                         ui,
                         TrackViewMessage::track_enabled(
                             *track_view,
                             MessageDirection::ToWidget,
-                            track_model.is_enabled(),
+                            enabled,
+                        ),
+                    );
+                }
+
+                let muted = self.muted_tracks.contains(&track_model.id());
+                if track_view_ref.muted != muted {
+                    send_sync_message(
+                        ui,
+                        TrackViewMessage::track_muted(
+                            *track_view,
+                            MessageDirection::ToWidget,
+                            muted,
+                        ),
+                    );
+                }
+
+                let locked = self.locked_tracks.contains(&track_model.id());
+                if track_view_ref.locked != locked {
+                    send_sync_message(
+                        ui,
+                        TrackViewMessage::track_locked(
+                            *track_view,
+                            MessageDirection::ToWidget,
+                            locked,
+                        ),
+                    );
+                }
+
+                let pinned = self.pinned_tracks.contains(&track_model.id());
+                if track_view_ref.pinned != pinned {
+                    send_sync_message(
+                        ui,
+                        TrackViewMessage::track_pinned(
+                            *track_view,
+                            MessageDirection::ToWidget,
+                            pinned,
                         ),
                     );
                 }
 
-                let mut validation_result = Ok(());
-                if let Some(target) = graph.try_get(track_model.target()) {
+                let key_count = track_model
+                    .data_container()
+                    .curves_ref()
+                    .iter()
+                    .map(|c| c.keys().len())
+                    .sum::<usize>();
+                if track_view_ref.key_count != key_count
+                    || track_view_ref.key_threshold != key_density_threshold
+                {
+                    send_sync_message(
+                        ui,
+                        TrackViewMessage::track_key_count(
+                            *track_view,
+                            MessageDirection::ToWidget,
+                            key_count,
+                            key_density_threshold,
+                        ),
+                    );
+                }
+
+                // Keys are flagged, but never changed, until they're edited.
+                let keys_out_of_limits =
+                    self.value_limits
+                        .get(&track_model.id())
+                        .map_or(0, |limits| {
+                            track_model
+                                .data_container()
+                                .curves_ref()
+                                .iter()
+                                .flat_map(|c| c.keys())
+                                .filter(|k| !limits.contains(k.value))
+                                .count()
+                        });
+                if track_view_ref.keys_out_of_limits != keys_out_of_limits {
+                    send_sync_message(
+                        ui,
+                        TrackViewMessage::track_keys_out_of_limits(
+                            *track_view,
+                            MessageDirection::ToWidget,
+                            keys_out_of_limits,
+                        ),
+                    );
+                }
+
+                let soloed = self.soloed_tracks.contains(&track_model.id());
+                if track_view_ref.soloed != soloed {
+                    send_sync_message(
+                        ui,
+                        TrackViewMessage::track_soloed(
+                            *track_view,
+                            MessageDirection::ToWidget,
+                            soloed,
+                        ),
+                    );
+                }
+
+                let validation_result = if let Some(target) = graph.try_get(track_model.target()) {
                     if let Some(parent_group) = self.group_views.get(&track_model.target()) {
-                        send_sync_message(
-                            ui,
-                            TextMessage::text(
-                                ui.node(*parent_group)
-                                    .query_component::<Tree>()
-                                    .unwrap()
-                                    .content,
-                                MessageDirection::ToWidget,
-                                target.name_owned(),
-                            ),
-                        );
+                        // Name of the group is the first child of its content.
+                        let content = ui
+                            .node(*parent_group)
+                            .query_component::<Tree>()
+                            .unwrap()
+                            .content;
+                        if let Some(name_text) = ui.node(content).children().first() {
+                            send_sync_message(
+                                ui,
+                                TextMessage::text(
+                                    *name_text,
+                                    MessageDirection::ToWidget,
+                                    target.name_owned(),
+                                ),
+                            );
+                        }
                     }
 
                     send_sync_message(
@@ -1298,42 +3968,14 @@ impl TrackList {
                         TrackViewMessage::track_name(
                             *track_view,
                             MessageDirection::ToWidget,
-                            format!("{}", track_model.binding()),
+                            track_name(track_model.binding()),
                         ),
                     );
 
-                    if let ValueBinding::Property { name, value_type } = track_model.binding() {
-                        target.resolve_path(name, &mut |result| match result {
-                            Ok(value) => {
-                                let mut property_type = TypeId::of::<u32>();
-                                value.as_any(&mut |any| property_type = any.type_id());
-
-                                if let Some((_, type_)) = type_id_to_supported_type(property_type) {
-                                    if *value_type != type_ {
-                                        validation_result = Err(format!(
-                                            "Property type mismatch. Expected {:?}, got {:?}",
-                                            value_type, type_
-                                        ));
-                                    }
-                                } else {
-                                    validation_result = Err(format!(
-                                        "Unsupported property type of {:?} type id.",
-                                        property_type
-                                    ));
-                                }
-                            }
-                            Err(err) => {
-                                validation_result = Err(format!(
-                                    "Unable to resolve property path {}. Reason: {:?}",
-                                    name, err
-                                ));
-                            }
-                        });
-                    }
+                    validate_binding(target, track_model.binding())
                 } else {
-                    validation_result =
-                        Err("Invalid handle. The target node does not exist!".to_owned());
-                }
+                    Err("Invalid handle. The target node does not exist!".to_owned())
+                };
 
                 send_sync_message(
                     ui,
@@ -1345,5 +3987,57 @@ impl TrackList {
                 );
             }
         }
+
+        // Visual order of the rows must follow the order of the tracks in the animation. Moving
+        // every row to the end of its parent (in the order of the tracks) gives the exact order.
+        let track_order = animation
+            .tracks()
+            .iter()
+            .map(|t| t.id())
+            .collect::<Vec<_>>();
+        if track_order != self.track_order {
+            let mut groups = Vec::new();
+            for track in animation.tracks() {
+                if let Some(group) = self.group_views.get(&group_key(graph, track.target())) {
+                    if !groups.contains(group) {
+                        groups.push(*group);
+                    }
+                }
+
+                if let Some(track_view) = self.track_views.get(&track.id()) {
+                    send_sync_message(
+                        ui,
+                        WidgetMessage::topmost(*track_view, MessageDirection::ToWidget),
+                    );
+                }
+            }
+
+            for group in groups {
+                send_sync_message(
+                    ui,
+                    WidgetMessage::topmost(group, MessageDirection::ToWidget),
+                );
+            }
+
+            self.track_order = track_order;
+        }
+
+        // Pinned section is shown only if the animation has at least one pinned track.
+        send_sync_message(
+            ui,
+            WidgetMessage::visibility(
+                self.pinned_section,
+                MessageDirection::ToWidget,
+                animation
+                    .tracks()
+                    .iter()
+                    .any(|t| self.pinned_tracks.contains(&t.id())),
+            ),
+        );
+
+        self.sync_group_toggles(ui);
+
+        // Re-apply the filter, because some of the views could be re-created.
+        self.apply_filter(graph, ui);
     }
 }
@@ -27,6 +27,9 @@ pub struct CommandStackViewer {
     undo: Handle<UiNode>,
     redo: Handle<UiNode>,
     clear: Handle<UiNode>,
+    // State of the command stack at the moment of last sync.
+    top: Option<usize>,
+    len: usize,
 }
 
 impl CommandStackViewer {
@@ -110,6 +113,8 @@ impl CommandStackViewer {
             undo,
             redo,
             clear,
+            top: None,
+            len: 0,
         }
     }
 
@@ -124,6 +129,31 @@ impl CommandStackViewer {
             } else if message.destination() == self.clear {
                 self.sender.send(Message::ClearSceneCommandStack);
             }
+        } else if let Some(ListViewMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+                && *index < self.len
+            {
+                // Undo or redo commands until the selected one becomes the top of the stack.
+                let target = self.len - 1 - *index;
+                match self.top {
+                    Some(top) if target < top => {
+                        for _ in target..top {
+                            self.sender.send(Message::UndoSceneCommand);
+                        }
+                    }
+                    Some(top) => {
+                        for _ in top..target {
+                            self.sender.send(Message::RedoSceneCommand);
+                        }
+                    }
+                    None => {
+                        for _ in 0..=target {
+                            self.sender.send(Message::RedoSceneCommand);
+                        }
+                    }
+                }
+            }
         }
     }
 
@@ -136,6 +166,8 @@ impl CommandStackViewer {
         scope_profile!();
 
         let top = command_stack.top;
+        self.top = top;
+        self.len = command_stack.commands.len();
         let items = command_stack
             .commands
             .iter_mut()
@@ -167,6 +199,16 @@ impl CommandStackViewer {
             })
             .collect();
 
+        // Selection must be synced first, otherwise the list will fix out-of-bounds selection
+        // by itself and it will be treated as user input.
+        send_sync_message(
+            ui,
+            ListViewMessage::selection(
+                self.list,
+                MessageDirection::ToWidget,
+                top.map(|top| self.len - 1 - top),
+            ),
+        );
         send_sync_message(
             ui,
             ListViewMessage::items(self.list, MessageDirection::ToWidget, items),
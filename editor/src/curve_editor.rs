@@ -304,7 +304,7 @@ impl CurveEditorWindow {
                 CurveEditorMessage::sync(
                     self.curve_editor,
                     MessageDirection::ToWidget,
-                    curve_resource.data_ref().curve.clone(),
+                    vec![curve_resource.data_ref().curve.clone()],
                 ),
             );
         }
@@ -431,12 +431,14 @@ impl CurveEditorWindow {
                     self.close(ui);
                 }
             }
-        } else if let Some(CurveEditorMessage::Sync(curve)) = message.data() {
+        } else if let Some(CurveEditorMessage::Sync(curves)) = message.data() {
             if message.destination() == self.curve_editor
                 && message.direction() == MessageDirection::FromWidget
                 && message.flags != MSG_SYNC_FLAG
             {
-                if let Some(curve_resource) = self.curve_resource.as_ref() {
+                if let (Some(curve_resource), Some(curve)) =
+                    (self.curve_resource.as_ref(), curves.first())
+                {
                     self.command_stack.do_command(
                         Box::new(ModifyCurveCommand {
                             curve_resource: curve_resource.clone(),
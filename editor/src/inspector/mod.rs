@@ -7,7 +7,13 @@ use crate::{
         },
         selection::SelectedEntity,
     },
-    animation::{self, command::signal::make_animation_signal_property_command},
+    animation::{
+        self,
+        command::{
+            marker::make_animation_marker_property_command,
+            signal::make_animation_signal_property_command,
+        },
+    },
     gui::make_image_button_with_tooltip,
     inspector::{
         editors::make_property_editors_container, handlers::node::SceneNodePropertyChangedHandler,
@@ -275,17 +281,28 @@ impl Inspector {
                             .try_get_of_type::<AnimationPlayer>(selection.animation_player)
                             .and_then(|player| player.animations().try_get(selection.animation))
                         {
-                            if let Some(animation::selection::SelectedEntity::Signal(id)) =
-                                selection.entities.first()
-                            {
-                                if let Some(signal) =
-                                    animation.signals().iter().find(|s| s.id == *id)
-                                {
-                                    self.sync_to(
-                                        signal as &dyn Reflect,
-                                        &mut engine.user_interface,
-                                    );
+                            match selection.entities.first() {
+                                Some(animation::selection::SelectedEntity::Signal(id)) => {
+                                    if let Some(signal) =
+                                        animation.signals().iter().find(|s| s.id == *id)
+                                    {
+                                        self.sync_to(
+                                            signal as &dyn Reflect,
+                                            &mut engine.user_interface,
+                                        );
+                                    }
                                 }
+                                Some(animation::selection::SelectedEntity::Marker(id)) => {
+                                    if let Some(marker) =
+                                        animation.markers().iter().find(|m| m.id == *id)
+                                    {
+                                        self.sync_to(
+                                            marker as &dyn Reflect,
+                                            &mut engine.user_interface,
+                                        );
+                                    }
+                                }
+                                _ => (),
                             }
                         }
                     }
@@ -457,22 +474,29 @@ impl Inspector {
                             .try_get_of_type::<AnimationPlayer>(selection.animation_player)
                             .and_then(|player| player.animations().try_get(selection.animation))
                         {
-                            if let Some(animation::selection::SelectedEntity::Signal(id)) =
-                                selection.entities.first()
-                            {
-                                if let Some(signal) =
-                                    animation.signals().iter().find(|s| s.id == *id)
-                                {
-                                    self.change_context(
-                                        signal as &dyn Reflect,
-                                        &mut engine.user_interface,
-                                        engine.resource_manager.clone(),
-                                        engine.serialization_context.clone(),
-                                        &scene.graph,
-                                        &editor_scene.selection,
-                                        sender,
-                                    )
-                                }
+                            let entity = match selection.entities.first() {
+                                Some(animation::selection::SelectedEntity::Signal(id)) => animation
+                                    .signals()
+                                    .iter()
+                                    .find(|s| s.id == *id)
+                                    .map(|s| s as &dyn Reflect),
+                                Some(animation::selection::SelectedEntity::Marker(id)) => animation
+                                    .markers()
+                                    .iter()
+                                    .find(|m| m.id == *id)
+                                    .map(|m| m as &dyn Reflect),
+                                _ => None,
+                            };
+                            if let Some(entity) = entity {
+                                self.change_context(
+                                    entity,
+                                    &mut engine.user_interface,
+                                    engine.resource_manager.clone(),
+                                    engine.serialization_context.clone(),
+                                    &scene.graph,
+                                    &editor_scene.selection,
+                                    sender,
+                                )
                             }
                         }
                     }
@@ -592,17 +616,24 @@ impl Inspector {
                             selection
                                 .entities
                                 .iter()
-                                .filter_map(|e| {
-                                    if let animation::selection::SelectedEntity::Signal(id) = e {
+                                .filter_map(|e| match e {
+                                    animation::selection::SelectedEntity::Signal(id) => {
                                         make_animation_signal_property_command(
                                             *id,
                                             args,
                                             selection.animation_player,
                                             selection.animation,
                                         )
-                                    } else {
-                                        None
                                     }
+                                    animation::selection::SelectedEntity::Marker(id) => {
+                                        make_animation_marker_property_command(
+                                            *id,
+                                            args,
+                                            selection.animation_player,
+                                            selection.animation,
+                                        )
+                                    }
+                                    _ => None,
                                 })
                                 .collect()
                         } else {
@@ -886,6 +886,7 @@ impl Editor {
         ];
 
         self.command_stack = CommandStack::new(false);
+        self.animation_editor.on_scene_loaded(&editor_scene, &self.engine);
         self.scene = Some(editor_scene);
 
         self.set_interaction_mode(Some(InteractionModeKind::Move));
@@ -1066,7 +1067,6 @@ impl Editor {
                     absm_editor: &self.absm_editor,
                     command_stack_panel: self.command_stack_viewer.window,
                     scene_settings: &self.scene_settings,
-                    animation_editor: &self.animation_editor,
                 },
                 settings: &mut self.settings,
             },
@@ -1099,6 +1099,9 @@ impl Editor {
             self.scene.as_mut(),
             engine,
             &self.message_sender,
+            &mut self.settings.windows.animation_editor,
+            &mut self.settings.animation_editor,
+            &self.settings.key_bindings,
         );
 
         if let Some(editor_scene) = self.scene.as_mut() {
@@ -1298,7 +1301,12 @@ impl Editor {
         self.scene_viewer.sync_to_model(self.scene.as_ref(), engine);
 
         if let Some(editor_scene) = self.scene.as_mut() {
-            self.animation_editor.sync_to_model(editor_scene, engine);
+            self.animation_editor.sync_to_model(
+                editor_scene,
+                engine,
+                &self.settings.animation_editor,
+                &self.message_sender,
+            );
             self.absm_editor.sync_to_model(editor_scene, engine);
             self.scene_settings.sync_to_model(editor_scene, engine);
             self.inspector.sync_to_model(editor_scene, engine);
@@ -1695,7 +1703,7 @@ impl Editor {
         self.asset_browser.update(&mut self.engine);
 
         if let Some(scene) = self.scene.as_ref() {
-            self.animation_editor.update(scene, &self.engine);
+            self.animation_editor.update(scene, &mut self.engine);
             self.audio_preview_panel.update(scene, &self.engine);
         }
 
@@ -1863,7 +1871,11 @@ impl Editor {
                         needs_sync = true;
                     }
                     Message::OpenAnimationEditor => {
-                        self.animation_editor.open(&self.engine.user_interface);
+                        self.animation_editor.open(
+                            &mut self.engine.user_interface,
+                            &self.settings.windows.animation_editor,
+                            &self.settings.animation_editor,
+                        );
                     }
                     Message::OpenAbsmEditor => self.absm_editor.open(&self.engine.user_interface),
                     Message::ShowDocumentation(doc) => {
@@ -1888,6 +1900,9 @@ impl Editor {
         if let Some(editor_scene) = self.scene.as_mut() {
             editor_scene.update(&mut self.engine, dt, &self.settings);
 
+            // Auxiliary geometry is cleared by the editor scene, so ghosts must be drawn after it.
+            self.animation_editor.draw_onion_skin(editor_scene, &mut self.engine);
+
             self.absm_editor.update(editor_scene, &mut self.engine);
 
             let scene = &self.engine.scenes[editor_scene.scene];
@@ -2078,6 +2093,10 @@ impl Editor {
                 }
             }
             Event::LoopDestroyed => {
+                self.animation_editor.store_layout(
+                    &self.engine.user_interface,
+                    &mut self.settings.windows.animation_editor,
+                );
                 Log::verify(self.settings.save());
             }
             _ => *control_flow = ControlFlow::Poll,
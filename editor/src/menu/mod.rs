@@ -1,5 +1,4 @@
 use crate::{
-    animation::AnimationEditor,
     menu::{
         create::CreateEntityRootMenu, edit::EditMenu, file::FileMenu, help::HelpMenu,
         utils::UtilsMenu, view::ViewMenu,
@@ -56,7 +55,6 @@ pub struct Panels<'b> {
     pub curve_editor: &'b CurveEditorWindow,
     pub absm_editor: &'b AbsmEditor,
     pub scene_settings: &'b SceneSettingsWindow,
-    pub animation_editor: &'b AnimationEditor,
 }
 
 pub struct MenuContext<'a, 'b> {
@@ -175,8 +173,12 @@ impl Menu {
             );
         }
 
-        self.utils_menu
-            .handle_ui_message(message, &ctx.panels, &ctx.engine.user_interface);
+        self.utils_menu.handle_ui_message(
+            message,
+            &ctx.panels,
+            &ctx.engine.user_interface,
+            &self.message_sender,
+        );
         self.file_menu.handle_ui_message(
             message,
             &self.message_sender,
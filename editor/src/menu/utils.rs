@@ -1,4 +1,8 @@
-use crate::menu::{create_menu_item, create_root_menu_item, Panels};
+use crate::{
+    menu::{create_menu_item, create_root_menu_item, Panels},
+    message::MessageSender,
+    Message,
+};
 use fyrox::{
     asset::core::pool::Handle,
     gui::{
@@ -55,7 +59,13 @@ impl UtilsMenu {
         }
     }
 
-    pub fn handle_ui_message(&mut self, message: &UiMessage, panels: &Panels, ui: &UserInterface) {
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        panels: &Panels,
+        ui: &UserInterface,
+        sender: &MessageSender,
+    ) {
         if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.open_path_fixer {
                 ui.send_message(WindowMessage::open_modal(
@@ -68,7 +78,8 @@ impl UtilsMenu {
             } else if message.destination() == self.absm_editor {
                 panels.absm_editor.open(ui);
             } else if message.destination() == self.animation_editor {
-                panels.animation_editor.open(ui);
+                // The editor owns the animation editor and its settings, it opens the window.
+                sender.send(Message::OpenAnimationEditor);
             }
         }
     }
@@ -0,0 +1,151 @@
+use fyrox::core::{algebra::Vector2, curve::CurveKeyKind, reflect::prelude::*};
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Interpolation of the keys that are created in the animation editor.
+#[derive(
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    Copy,
+    Clone,
+    Default,
+    Debug,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum KeyInterpolation {
+    /// Straight line to the next key.
+    #[default]
+    Linear,
+    /// Cubic key, its tangents are calculated from the neighbour keys.
+    Smooth,
+    /// The value stays the same until the next key, suits things like visibility toggles.
+    Constant,
+}
+
+impl KeyInterpolation {
+    pub const ALL: [KeyInterpolation; 3] = [
+        KeyInterpolation::Linear,
+        KeyInterpolation::Smooth,
+        KeyInterpolation::Constant,
+    ];
+
+    /// Kind of a new key. Tangents of a smooth key are zero, they should be calculated when the
+    /// key is added to a curve.
+    pub fn key_kind(self) -> CurveKeyKind {
+        match self {
+            KeyInterpolation::Linear => CurveKeyKind::Linear,
+            KeyInterpolation::Smooth => CurveKeyKind::Cubic {
+                left_tangent: 0.0,
+                right_tangent: 0.0,
+            },
+            KeyInterpolation::Constant => CurveKeyKind::Constant,
+        }
+    }
+}
+
+/// A shape of a curve, that could be applied to any curve of an animation. Points are normalized,
+/// `(0, 0)` is the first key of a curve and `(1, 1)` is the last one.
+#[derive(Deserialize, Serialize, PartialEq, Clone, Default, Debug, Reflect)]
+pub struct CurvePreset {
+    pub name: String,
+    #[reflect(description = "Normalized points of the curve between its first and last keys.")]
+    pub points: Vec<Vector2<f32>>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
+pub struct AnimationEditorSettings {
+    #[reflect(
+        description = "Values that dragged keys snap to when Ctrl is held. Keys snap to the closest value of the set."
+    )]
+    #[serde(default = "default_value_snap_set")]
+    pub value_snap_set: Vec<f32>,
+
+    #[reflect(description = "Curve presets made by a user, in addition to the built-in ones.")]
+    #[serde(default)]
+    pub curve_presets: Vec<CurvePreset>,
+
+    #[reflect(description = "Interpolation of the keys that are created in the animation editor.")]
+    #[serde(default)]
+    pub new_key_interpolation: KeyInterpolation,
+
+    #[reflect(
+        description = "Amount by which Up and Down arrows change values of selected keys. Shift makes the step ten times larger."
+    )]
+    #[serde(default = "default_key_nudge_step")]
+    pub key_nudge_step: f32,
+
+    #[reflect(
+        description = "Defines whether keys are added at the ends of a deleted time range, so curves keep their values and slopes there."
+    )]
+    #[serde(default = "default_preserve_shape_on_range_delete")]
+    pub preserve_shape_on_range_delete: bool,
+
+    #[reflect(
+        description = "Tracks with more keys than this amount are flagged in the track list, over-keyed curves slow down playback."
+    )]
+    #[serde(default = "default_key_density_threshold")]
+    pub key_density_threshold: usize,
+
+    #[reflect(
+        description = "Defines whether tangent handles of selected keys are shown in the curve editor."
+    )]
+    #[serde(default = "default_show_tangent_handles")]
+    pub show_tangent_handles: bool,
+
+    #[reflect(
+        description = "Defines whether keys that are aligned, distributed or imported to the time of another key replace it, otherwise such operation is rejected."
+    )]
+    #[serde(default)]
+    pub merge_aligned_keys: bool,
+
+    #[reflect(
+        description = "Defines whether the curve editor shows a grid with labeled values, the step of the grid adapts to the zoom."
+    )]
+    #[serde(default = "default_show_value_grid")]
+    pub show_value_grid: bool,
+}
+
+fn default_value_snap_set() -> Vec<f32> {
+    vec![0.0, 0.25, 0.5, 0.75, 1.0]
+}
+
+fn default_key_nudge_step() -> f32 {
+    0.01
+}
+
+fn default_preserve_shape_on_range_delete() -> bool {
+    true
+}
+
+fn default_key_density_threshold() -> usize {
+    300
+}
+
+fn default_show_tangent_handles() -> bool {
+    true
+}
+
+fn default_show_value_grid() -> bool {
+    true
+}
+
+impl Default for AnimationEditorSettings {
+    fn default() -> Self {
+        Self {
+            value_snap_set: default_value_snap_set(),
+            curve_presets: Default::default(),
+            new_key_interpolation: Default::default(),
+            key_nudge_step: default_key_nudge_step(),
+            preserve_shape_on_range_delete: default_preserve_shape_on_range_delete(),
+            key_density_threshold: default_key_density_threshold(),
+            show_tangent_handles: default_show_tangent_handles(),
+            merge_aligned_keys: false,
+            show_value_grid: default_show_value_grid(),
+        }
+    }
+}
@@ -2,20 +2,29 @@ use crate::{
     inspector::editors::make_property_editors_container,
     message::MessageSender,
     settings::{
-        camera::CameraSettings, debugging::DebuggingSettings, graphics::GraphicsSettings,
-        keys::KeyBindings, model::ModelSettings, move_mode::MoveInteractionModeSettings,
-        navmesh::NavmeshSettings, recent::RecentFiles, rotate_mode::RotateInteractionModeSettings,
-        selection::SelectionSettings, windows::WindowsSettings,
+        animation::{AnimationEditorSettings, CurvePreset, KeyInterpolation},
+        camera::CameraSettings,
+        debugging::DebuggingSettings,
+        graphics::GraphicsSettings,
+        keys::KeyBindings,
+        model::ModelSettings,
+        move_mode::MoveInteractionModeSettings,
+        navmesh::NavmeshSettings,
+        recent::RecentFiles,
+        rotate_mode::RotateInteractionModeSettings,
+        selection::SelectionSettings,
+        windows::WindowsSettings,
     },
     Engine, MSG_SYNC_FLAG,
 };
 use fyrox::{
-    core::{log::Log, pool::Handle, reflect::prelude::*, scope_profile},
+    core::{algebra::Vector2, log::Log, pool::Handle, reflect::prelude::*, scope_profile},
     gui::{
         button::{ButtonBuilder, ButtonMessage},
         grid::{Column, GridBuilder, Row},
         inspector::{
             editors::{
+                collection::VecCollectionPropertyEditorDefinition,
                 enumeration::EnumPropertyEditorDefinition,
                 inspectable::InspectablePropertyEditorDefinition,
                 key::{HotKeyPropertyEditorDefinition, KeyBindingPropertyEditorDefinition},
@@ -36,6 +45,7 @@ use ron::ser::PrettyConfig;
 use serde::{Deserialize, Serialize};
 use std::{fs::File, path::PathBuf, rc::Rc};
 
+pub mod animation;
 pub mod camera;
 pub mod debugging;
 pub mod graphics;
@@ -66,6 +76,8 @@ pub struct Settings {
     pub camera: CameraSettings,
     pub navmesh: NavmeshSettings,
     pub key_bindings: KeyBindings,
+    #[serde(default)]
+    pub animation_editor: AnimationEditorSettings,
     #[reflect(hidden)]
     pub recent: RecentFiles,
     #[serde(default)]
@@ -138,6 +150,12 @@ impl Settings {
         container.insert(InspectablePropertyEditorDefinition::<ModelSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<NavmeshSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<KeyBindings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<AnimationEditorSettings>::new());
+        container.insert(EnumPropertyEditorDefinition::<KeyInterpolation>::new());
+        container.insert(VecCollectionPropertyEditorDefinition::<f32>::new());
+        container.insert(InspectablePropertyEditorDefinition::<CurvePreset>::new());
+        container.insert(VecCollectionPropertyEditorDefinition::<CurvePreset>::new());
+        container.insert(VecCollectionPropertyEditorDefinition::<Vector2<f32>>::new());
         container.insert(HotKeyPropertyEditorDefinition);
         container.insert(KeyBindingPropertyEditorDefinition);
 
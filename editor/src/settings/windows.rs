@@ -7,6 +7,8 @@ pub struct WindowsSettings {
     pub window_position: Vector2<f32>,
     #[serde(default)]
     pub window_size: Vector2<f32>,
+    #[serde(default)]
+    pub animation_editor: AnimationEditorWindowSettings,
 }
 
 impl Default for WindowsSettings {
@@ -14,6 +16,41 @@ impl Default for WindowsSettings {
         Self {
             window_position: Vector2::new(0.0, 0.0),
             window_size: Vector2::new(1024.0, 768.0),
+            animation_editor: Default::default(),
+        }
+    }
+}
+
+/// Layout of the animation editor window, that is restored when the window is opened.
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Reflect)]
+pub struct AnimationEditorWindowSettings {
+    /// Position of the window, `None` - the window will be centered.
+    #[serde(default)]
+    pub position: Option<Vector2<f32>>,
+    #[serde(default = "default_animation_editor_size")]
+    pub size: Vector2<f32>,
+    /// Width of the track list.
+    #[serde(default = "default_track_list_width")]
+    pub track_list_width: f32,
+}
+
+fn default_animation_editor_size() -> Vector2<f32> {
+    Vector2::new(600.0, 500.0)
+}
+
+/// Width of the track list when there is no saved layout, or when the layout is reset.
+pub const DEFAULT_TRACK_LIST_WIDTH: f32 = 250.0;
+
+fn default_track_list_width() -> f32 {
+    DEFAULT_TRACK_LIST_WIDTH
+}
+
+impl Default for AnimationEditorWindowSettings {
+    fn default() -> Self {
+        Self {
+            position: None,
+            size: default_animation_editor_size(),
+            track_list_width: default_track_list_width(),
         }
     }
 }
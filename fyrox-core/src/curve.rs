@@ -43,12 +43,33 @@ impl Default for CurveKeyKind {
     }
 }
 
+/// A role of a key in the classic animation workflow. Tags are used only to organize keys, they
+/// do not affect interpolation.
+#[derive(Visit, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CurveKeyTag {
+    /// An extreme pose of the motion.
+    Key,
+    /// A pose that defines the transition between two keys.
+    Breakdown,
+    /// A pose that fills the gap between keys and breakdowns.
+    InBetween,
+}
+
+impl Default for CurveKeyTag {
+    #[inline]
+    fn default() -> Self {
+        Self::Key
+    }
+}
+
 #[derive(Visit, Clone, Default, Debug, PartialEq)]
 pub struct CurveKey {
     pub id: Uuid,
     location: f32,
     pub value: f32,
     pub kind: CurveKeyKind,
+    #[visit(optional)] // Backward compatibility
+    pub tag: CurveKeyTag,
 }
 
 impl CurveKey {
@@ -59,6 +80,7 @@ impl CurveKey {
             location,
             value,
             kind,
+            tag: Default::default(),
         }
     }
 }
@@ -203,12 +225,31 @@ impl Curve {
         self.keys.iter_mut().map(|k| &mut k.value)
     }
 
+    /// Sets the tag of a key with the given id, returns the previous tag of the key or `None` if
+    /// there's no such key.
+    #[inline]
+    pub fn set_key_tag(&mut self, key_id: Uuid, tag: CurveKeyTag) -> Option<CurveKeyTag> {
+        self.keys
+            .iter_mut()
+            .find(|k| k.id == key_id)
+            .map(|k| std::mem::replace(&mut k.tag, tag))
+    }
+
     #[inline]
     pub fn add_key(&mut self, new_key: CurveKey) {
         let pos = self.keys.partition_point(|k| k.location < new_key.location);
         self.keys.insert(pos, new_key);
     }
 
+    #[inline]
+    pub fn remove_key(&mut self, index: usize) -> Option<CurveKey> {
+        if index < self.keys.len() {
+            Some(self.keys.remove(index))
+        } else {
+            None
+        }
+    }
+
     #[inline]
     pub fn move_key(&mut self, key_id: usize, location: f32) {
         if let Some(key) = self.keys.get_mut(key_id) {
@@ -247,7 +288,8 @@ impl Curve {
 
 #[cfg(test)]
 mod test {
-    use crate::curve::{Curve, CurveKey, CurveKeyKind};
+    use crate::curve::{Curve, CurveKey, CurveKeyKind, CurveKeyTag};
+    use uuid::Uuid;
 
     #[test]
     fn test_curve_key_insertion_order() {
@@ -268,6 +310,27 @@ mod test {
         assert_eq!(curve.keys[4].location, 3.0);
     }
 
+    #[test]
+    fn test_curve_key_tag() {
+        let key = CurveKey::new(0.0, 0.0, CurveKeyKind::Constant);
+        let id = key.id;
+        let mut curve = Curve::from(vec![key]);
+
+        assert_eq!(curve.keys[0].tag, CurveKeyTag::Key);
+        assert_eq!(
+            curve.set_key_tag(id, CurveKeyTag::Breakdown),
+            Some(CurveKeyTag::Key)
+        );
+        assert_eq!(curve.keys[0].tag, CurveKeyTag::Breakdown);
+
+        // Unknown keys are ignored.
+        assert_eq!(
+            curve.set_key_tag(Uuid::new_v4(), CurveKeyTag::InBetween),
+            None
+        );
+        assert_eq!(curve.keys[0].tag, CurveKeyTag::Breakdown);
+    }
+
     #[test]
     fn test_curve() {
         let mut curve = Curve::default();
@@ -307,4 +370,23 @@ mod test {
         // Check interpolation.
         assert_eq!(curve.value_at(0.5), 0.5);
     }
+
+    #[test]
+    fn test_stepped_curve() {
+        let mut curve = Curve::default();
+
+        curve.add_key(CurveKey::new(0.0, 1.0, CurveKeyKind::Constant));
+        curve.add_key(CurveKey::new(1.0, 3.0, CurveKeyKind::Linear));
+        curve.add_key(CurveKey::new(2.0, 5.0, CurveKeyKind::Constant));
+
+        // Value of a constant key holds until the next key.
+        assert_eq!(curve.value_at(0.0), 1.0);
+        assert_eq!(curve.value_at(0.5), 1.0);
+        assert_eq!(curve.value_at(0.99), 1.0);
+        assert_eq!(curve.value_at(1.0), 3.0);
+
+        // Segments after non-constant keys are still interpolated.
+        assert_eq!(curve.value_at(1.5), 4.0);
+        assert_eq!(curve.value_at(2.0), 5.0);
+    }
 }
@@ -164,36 +164,34 @@ impl Control for CheckBox {
 
         if let Some(msg) = message.data::<WidgetMessage>() {
             match msg {
-                WidgetMessage::MouseDown { button, .. } => {
+                WidgetMessage::MouseDown { button, .. }
                     if *button == MouseButton::Left
                         && (message.destination() == self.handle()
-                            || self.widget.has_descendant(message.destination(), ui))
-                    {
-                        ui.capture_mouse(self.handle());
-                    }
+                            || self.widget.has_descendant(message.destination(), ui)) =>
+                {
+                    ui.capture_mouse(self.handle());
                 }
-                WidgetMessage::MouseUp { button, .. } => {
+                WidgetMessage::MouseUp { button, .. }
                     if *button == MouseButton::Left
                         && (message.destination() == self.handle()
-                            || self.widget.has_descendant(message.destination(), ui))
-                    {
-                        ui.release_mouse_capture();
-
-                        if let Some(value) = self.checked {
-                            // Invert state if it is defined.
-                            ui.send_message(CheckBoxMessage::checked(
-                                self.handle(),
-                                MessageDirection::ToWidget,
-                                Some(!value),
-                            ));
-                        } else {
-                            // Switch from undefined state to checked.
-                            ui.send_message(CheckBoxMessage::checked(
-                                self.handle(),
-                                MessageDirection::ToWidget,
-                                Some(true),
-                            ));
-                        }
+                            || self.widget.has_descendant(message.destination(), ui)) =>
+                {
+                    ui.release_mouse_capture();
+
+                    if let Some(value) = self.checked {
+                        // Invert state if it is defined.
+                        ui.send_message(CheckBoxMessage::checked(
+                            self.handle(),
+                            MessageDirection::ToWidget,
+                            Some(!value),
+                        ));
+                    } else {
+                        // Switch from undefined state to checked.
+                        ui.send_message(CheckBoxMessage::checked(
+                            self.handle(),
+                            MessageDirection::ToWidget,
+                            Some(true),
+                        ));
                     }
                 }
                 _ => (),
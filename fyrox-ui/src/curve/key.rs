@@ -1,7 +1,10 @@
-use crate::core::{
-    algebra::Vector2,
-    curve::{Curve, CurveKey, CurveKeyKind},
-    uuid::Uuid,
+use crate::{
+    brush::Brush,
+    core::{
+        algebra::Vector2,
+        curve::{Curve, CurveKey, CurveKeyKind, CurveKeyTag},
+        uuid::Uuid,
+    },
 };
 use std::cmp::Ordering;
 
@@ -10,6 +13,7 @@ pub struct CurveKeyView {
     pub position: Vector2<f32>,
     pub kind: CurveKeyKind,
     pub id: Uuid,
+    pub tag: CurveKeyTag,
 }
 
 impl From<&CurveKey> for CurveKeyView {
@@ -18,6 +22,7 @@ impl From<&CurveKey> for CurveKeyView {
             position: Vector2::new(key.location(), key.value),
             kind: key.kind.clone(),
             id: key.id,
+            tag: key.tag,
         }
     }
 }
@@ -25,11 +30,12 @@ impl From<&CurveKey> for CurveKeyView {
 #[derive(Clone)]
 pub struct KeyContainer {
     id: Uuid,
+    pub brush: Brush,
     keys: Vec<CurveKeyView>,
 }
 
-impl From<&Curve> for KeyContainer {
-    fn from(curve: &Curve) -> Self {
+impl KeyContainer {
+    pub fn new(curve: &Curve, brush: Brush) -> Self {
         Self {
             keys: curve
                 .keys()
@@ -37,11 +43,14 @@ impl From<&Curve> for KeyContainer {
                 .map(CurveKeyView::from)
                 .collect::<Vec<_>>(),
             id: curve.id(),
+            brush,
         }
     }
-}
 
-impl KeyContainer {
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
     pub fn add(&mut self, key: CurveKeyView) {
         self.keys.push(key)
     }
@@ -97,6 +106,7 @@ impl KeyContainer {
                 .map(|k| {
                     let mut key = CurveKey::new(k.position.x, k.position.y, k.kind.clone());
                     key.id = k.id;
+                    key.tag = k.tag;
                     key
                 })
                 .collect::<Vec<_>>(),
@@ -105,3 +115,76 @@ impl KeyContainer {
         curve
     }
 }
+
+/// A set of curves, that are edited together. Every key in the container has unique id, so
+/// keys could be addressed directly without knowing which curve they belong to.
+#[derive(Clone, Default)]
+pub struct CurvesContainer {
+    curves: Vec<KeyContainer>,
+}
+
+impl CurvesContainer {
+    /// Creates new container from the given curves, each curve takes a brush from the given
+    /// set of brushes (brushes will be cycled if there are more curves than brushes).
+    pub fn from_native(brushes: &[Brush], curves: &[Curve]) -> Self {
+        assert!(!brushes.is_empty());
+        Self {
+            curves: curves
+                .iter()
+                .enumerate()
+                .map(|(i, curve)| KeyContainer::new(curve, brushes[i % brushes.len()].clone()))
+                .collect(),
+        }
+    }
+
+    pub fn to_native(&self) -> Vec<Curve> {
+        self.curves.iter().map(|c| c.curve()).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.curves.is_empty()
+    }
+
+    pub fn curves(&self) -> &[KeyContainer] {
+        &self.curves
+    }
+
+    pub fn curves_mut(&mut self) -> &mut [KeyContainer] {
+        &mut self.curves
+    }
+
+    pub fn curve_ref(&self, id: Uuid) -> Option<&KeyContainer> {
+        self.curves.iter().find(|c| c.id == id)
+    }
+
+    pub fn curve_mut(&mut self, id: Uuid) -> Option<&mut KeyContainer> {
+        self.curves.iter_mut().find(|c| c.id == id)
+    }
+
+    /// Returns a curve that contains a key with the given id.
+    pub fn container_of(&self, key_id: Uuid) -> Option<&KeyContainer> {
+        self.curves.iter().find(|c| c.key_ref(key_id).is_some())
+    }
+
+    pub fn key_ref(&self, id: Uuid) -> Option<&CurveKeyView> {
+        self.curves.iter().find_map(|c| c.key_ref(id))
+    }
+
+    pub fn key_mut(&mut self, id: Uuid) -> Option<&mut CurveKeyView> {
+        self.curves.iter_mut().find_map(|c| c.key_mut(id))
+    }
+
+    pub fn remove(&mut self, id: Uuid) -> Option<CurveKeyView> {
+        self.curves.iter_mut().find_map(|c| c.remove(id))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &CurveKeyView> {
+        self.curves.iter().flat_map(|c| c.keys().iter())
+    }
+
+    pub fn sort_keys(&mut self) {
+        for curve in self.curves.iter_mut() {
+            curve.sort_keys();
+        }
+    }
+}
@@ -3,12 +3,12 @@ use crate::{
     core::{
         algebra::{Matrix3, Point2, SimdPartialOrd, Vector2, Vector3},
         color::Color,
-        curve::{Curve, CurveKeyKind},
+        curve::{Curve, CurveKeyKind, CurveKeyTag},
         math::{cubicf, inf_sup_cubicf, lerpf, wrap_angle, Rect},
         pool::Handle,
         uuid::Uuid,
     },
-    curve::key::{CurveKeyView, KeyContainer},
+    curve::key::{CurveKeyView, CurvesContainer, KeyContainer},
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     formatted_text::{FormattedText, FormattedTextBuilder},
@@ -22,22 +22,67 @@ use crate::{
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, RcUiNodeHandle, Thickness, UiNode, UserInterface, VerticalAlignment,
 };
-use fxhash::FxHashSet;
+use fxhash::{FxHashMap, FxHashSet};
 use std::{
     any::{Any, TypeId},
     cell::{Cell, RefCell},
-    ops::{Deref, DerefMut},
+    cmp::Ordering,
+    ops::{Deref, DerefMut, Range},
 };
 
 pub mod key;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum CurveEditorMessage {
-    Sync(Curve),
+    Sync(Vec<Curve>),
     ViewPosition(Vector2<f32>),
     Zoom(Vector2<f32>),
     ZoomToFit,
+    /// Changes zoom and view position so the given rectangle (in curve space) fills the editor.
+    ZoomToRect(Rect<f32>),
     HighlightZones(Vec<HighlightZone>),
+    /// Sets brushes for particular curves (by their ids). These brushes take precedence over
+    /// the default set of curve brushes and are used even if there is only one curve in the
+    /// editor.
+    CurveBrushes(Vec<(Uuid, Brush)>),
+    /// Sets a mode that defines how tangents of keys are edited. The mode is also applied to
+    /// currently selected keys, the editor sends modified curves back if any key was changed.
+    TangentMode(TangentMode),
+    /// Sets a range of locations that dragged keys cannot leave. Keys are moved as a whole group,
+    /// so their relative spacing is preserved when the group hits a limit. The area outside of the
+    /// range is shaded and keys that are already outside of it are drawn with a warning color.
+    KeyLocationLimits(Option<Range<f32>>),
+    /// The editor emits it when a user starts dragging keys or tangents. Every [`Self::Sync`]
+    /// message that comes before [`Self::DragEnded`] is a part of the same edit.
+    DragStarted,
+    /// The editor emits it when a user finishes dragging keys or tangents.
+    DragEnded,
+    /// Ids of selected keys. The editor emits it every time when a user changes the selection,
+    /// it also could be used to set the selection from the code.
+    SelectionChanged(Vec<Uuid>),
+    /// Ids of curves that should not be shown. Hidden curves are still kept in the editor, but
+    /// they cannot be picked and they are ignored by [`Self::ZoomToFit`].
+    HiddenCurves(Vec<Uuid>),
+    /// Sets a kind of the keys that are added from the context menu of the editor. Tangents of
+    /// new cubic keys are calculated from the neighbour keys, so the curve stays smooth.
+    NewKeyKind(CurveKeyKind),
+    /// Sets curves that are drawn behind the edited ones with a dimmed brush, for example to
+    /// compare timing with another animation. Reference curves cannot be picked or edited and
+    /// they are ignored by [`Self::ZoomToFit`].
+    ReferenceCurves(Vec<Curve>),
+    /// Shows or hides tangent handles of selected cubic keys. Hidden handles cannot be picked, so
+    /// keys could be dragged without hitting a handle by accident. Tangents are shown by default.
+    ShowTangents(bool),
+    /// Shows only the keys with the given tag, keys with other tags are not drawn and cannot be
+    /// picked. `None` shows every key.
+    KeyTagFilter(Option<CurveKeyTag>),
+    /// Replaces horizontal grid lines with a value grid, which has "round" steps that adapt to
+    /// the zoom and labels with precision that matches the step. Disabled by default.
+    ShowValueGrid(bool),
+    /// Sets a time step of vertical grid lines, for example a snapping interval, so the lines show
+    /// where snapped keys land. The lines are drawn at "round" multiples of the step, so they do
+    /// not get too dense when zoomed out. `None` uses spacing that adapts to the zoom.
+    TimeGridStep(Option<f32>),
 
     // Internal messages. Use only when you know what you're doing.
     // These are internal because you must use Sync message to request changes
@@ -51,11 +96,25 @@ pub enum CurveEditorMessage {
 }
 
 impl CurveEditorMessage {
-    define_constructor!(CurveEditorMessage:Sync => fn sync(Curve), layout: false);
+    define_constructor!(CurveEditorMessage:Sync => fn sync(Vec<Curve>), layout: false);
     define_constructor!(CurveEditorMessage:ViewPosition => fn view_position(Vector2<f32>), layout: false);
     define_constructor!(CurveEditorMessage:Zoom => fn zoom(Vector2<f32>), layout: false);
     define_constructor!(CurveEditorMessage:ZoomToFit => fn zoom_to_fit(), layout: false);
+    define_constructor!(CurveEditorMessage:ZoomToRect => fn zoom_to_rect(Rect<f32>), layout: false);
     define_constructor!(CurveEditorMessage:HighlightZones => fn hightlight_zones(Vec<HighlightZone>), layout: false);
+    define_constructor!(CurveEditorMessage:CurveBrushes => fn curve_brushes(Vec<(Uuid, Brush)>), layout: false);
+    define_constructor!(CurveEditorMessage:TangentMode => fn set_tangent_mode(TangentMode), layout: false);
+    define_constructor!(CurveEditorMessage:KeyLocationLimits => fn key_location_limits(Option<Range<f32>>), layout: false);
+    define_constructor!(CurveEditorMessage:DragStarted => fn drag_started(), layout: false);
+    define_constructor!(CurveEditorMessage:DragEnded => fn drag_ended(), layout: false);
+    define_constructor!(CurveEditorMessage:SelectionChanged => fn selection_changed(Vec<Uuid>), layout: false);
+    define_constructor!(CurveEditorMessage:HiddenCurves => fn hidden_curves(Vec<Uuid>), layout: false);
+    define_constructor!(CurveEditorMessage:NewKeyKind => fn new_key_kind(CurveKeyKind), layout: false);
+    define_constructor!(CurveEditorMessage:ReferenceCurves => fn reference_curves(Vec<Curve>), layout: false);
+    define_constructor!(CurveEditorMessage:ShowTangents => fn show_tangents(bool), layout: false);
+    define_constructor!(CurveEditorMessage:KeyTagFilter => fn key_tag_filter(Option<CurveKeyTag>), layout: false);
+    define_constructor!(CurveEditorMessage:ShowValueGrid => fn show_value_grid(bool), layout: false);
+    define_constructor!(CurveEditorMessage:TimeGridStep => fn time_grid_step(Option<f32>), layout: false);
     // Internal. Use only when you know what you're doing.
     define_constructor!(CurveEditorMessage:RemoveSelection => fn remove_selection(), layout: false);
     define_constructor!(CurveEditorMessage:ChangeSelectedKeysKind => fn change_selected_keys_kind(CurveKeyKind), layout: false);
@@ -64,6 +123,42 @@ impl CurveEditorMessage {
     define_constructor!(CurveEditorMessage:AddKey => fn add_key(Vector2<f32>), layout: false);
 }
 
+/// Defines how tangents of cubic keys are edited.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TangentMode {
+    /// Tangents are calculated from neighbouring keys, so the curve passes smoothly through keys.
+    Auto,
+    /// Tangents can be edited, but both of them are kept equal, so the curve stays smooth.
+    Free,
+    /// Left and right tangents can be edited independently.
+    #[default]
+    Broken,
+    /// Tangents are pointed to neighbouring keys.
+    Linear,
+    /// Keys have no tangents, value of a key holds until the next key.
+    Stepped,
+}
+
+impl TangentMode {
+    pub const ALL: [TangentMode; 5] = [
+        TangentMode::Auto,
+        TangentMode::Free,
+        TangentMode::Broken,
+        TangentMode::Linear,
+        TangentMode::Stepped,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TangentMode::Auto => "Auto",
+            TangentMode::Free => "Free",
+            TangentMode::Broken => "Broken",
+            TangentMode::Linear => "Linear",
+            TangentMode::Stepped => "Stepped",
+        }
+    }
+}
+
 /// Highlight zone in values space.
 #[derive(Clone, Debug, PartialEq)]
 pub struct HighlightZone {
@@ -74,7 +169,9 @@ pub struct HighlightZone {
 #[derive(Clone)]
 pub struct CurveEditor {
     widget: Widget,
-    key_container: KeyContainer,
+    curves: CurvesContainer,
+    curve_brushes: Vec<Brush>,
+    curve_brush_overrides: FxHashMap<Uuid, Brush>,
     zoom: Vector2<f32>,
     view_position: Vector2<f32>,
     // Transforms a point from local to view coordinates.
@@ -89,6 +186,10 @@ pub struct CurveEditor {
     inv_screen_matrix: Cell<Matrix3<f32>>,
     key_brush: Brush,
     selected_key_brush: Brush,
+    out_of_range_key_brush: Brush,
+    breakdown_key_brush: Brush,
+    in_between_key_brush: Brush,
+    out_of_range_brush: Brush,
     key_size: f32,
     grid_brush: Brush,
     operation_context: Option<OperationContext>,
@@ -103,6 +204,18 @@ pub struct CurveEditor {
     min_zoom: Vector2<f32>,
     max_zoom: Vector2<f32>,
     highlight_zones: Vec<HighlightZone>,
+    handle_delete_key: bool,
+    tangent_mode: TangentMode,
+    key_location_limits: Option<Range<f32>>,
+    live_drag_sync: bool,
+    hidden_curves: FxHashSet<Uuid>,
+    new_key_kind: CurveKeyKind,
+    reference_curves: Vec<KeyContainer>,
+    reference_curve_brush: Brush,
+    show_tangents: bool,
+    key_tag_filter: Option<CurveKeyTag>,
+    show_value_grid: bool,
+    time_grid_step: Option<f32>,
 }
 
 crate::define_widget_deref!(CurveEditor);
@@ -140,7 +253,7 @@ enum OperationContext {
         initial_view_pos: Vector2<f32>,
     },
     DragTangent {
-        key: usize,
+        key: Uuid,
         left: bool,
     },
     BoxSelection {
@@ -154,17 +267,15 @@ enum OperationContext {
 #[derive(Clone)]
 enum Selection {
     Keys { keys: FxHashSet<Uuid> },
-    // It is ok to use index directly in case of tangents since
-    // we won't change position of keys so index will be valid.
-    LeftTangent { key: usize },
-    RightTangent { key: usize },
+    LeftTangent { key: Uuid },
+    RightTangent { key: Uuid },
 }
 
 #[derive(Copy, Clone)]
 enum PickResult {
-    Key(usize),
-    LeftTangent(usize),
-    RightTangent(usize),
+    Key(Uuid),
+    LeftTangent(Uuid),
+    RightTangent(Uuid),
 }
 
 impl Selection {
@@ -189,8 +300,10 @@ impl Control for CurveEditor {
         self.update_matrices();
         self.draw_background(ctx);
         self.draw_highlight_zones(ctx);
+        self.draw_out_of_range_zones(ctx);
         self.draw_grid(ctx);
-        self.draw_curve(ctx);
+        self.draw_reference_curves(ctx);
+        self.draw_curves(ctx);
         self.draw_keys(ctx);
         self.draw_operation(ctx);
         ctx.transform_stack.pop();
@@ -202,7 +315,7 @@ impl Control for CurveEditor {
         if message.destination() == self.handle {
             if let Some(msg) = message.data::<WidgetMessage>() {
                 match msg {
-                    WidgetMessage::KeyUp(KeyCode::Delete) => {
+                    WidgetMessage::KeyUp(KeyCode::Delete) if self.handle_delete_key => {
                         self.remove_selection(ui);
                     }
                     WidgetMessage::MouseMove { pos, state } => {
@@ -213,10 +326,28 @@ impl Control for CurveEditor {
                                     entries,
                                     initial_mouse_pos,
                                 } => {
-                                    let local_delta = local_mouse_pos - initial_mouse_pos;
+                                    let mut local_delta = local_mouse_pos - initial_mouse_pos;
+                                    if let Some(limits) = self.key_location_limits.as_ref() {
+                                        let (min, max) = entries.iter().fold(
+                                            (f32::MAX, f32::MIN),
+                                            |(min, max), e| {
+                                                (
+                                                    min.min(e.initial_position.x),
+                                                    max.max(e.initial_position.x),
+                                                )
+                                            },
+                                        );
+                                        // Clamp the whole group, the left limit has priority if
+                                        // the group is wider than the range.
+                                        local_delta.x = local_delta
+                                            .x
+                                            .min(limits.end - max)
+                                            .max(limits.start - min);
+                                    }
                                     for entry in entries {
-                                        let key = self.key_container.key_mut(entry.key).unwrap();
-                                        key.position = entry.initial_position + local_delta;
+                                        if let Some(key) = self.curves.key_mut(entry.key) {
+                                            key.position = entry.initial_position + local_delta;
+                                        }
                                     }
                                     self.sort_keys();
                                 }
@@ -233,33 +364,40 @@ impl Control for CurveEditor {
                                     ));
                                 }
                                 OperationContext::DragTangent { key, left } => {
-                                    let key_pos =
-                                        self.key_container.key_index_ref(*key).unwrap().position;
-                                    let screen_key_pos = self.point_to_screen_space(key_pos);
-                                    let key = self.key_container.key_index_mut(*key).unwrap();
-                                    if let CurveKeyKind::Cubic {
-                                        left_tangent,
-                                        right_tangent,
-                                    } = &mut key.kind
+                                    let screen_key_pos = self
+                                        .curves
+                                        .key_ref(*key)
+                                        .map(|k| self.point_to_screen_space(k.position));
+                                    if let (Some(screen_key_pos), Some(key)) =
+                                        (screen_key_pos, self.curves.key_mut(*key))
                                     {
-                                        let mut local_delta = pos - screen_key_pos;
-                                        if *left {
-                                            local_delta.x = local_delta.x.min(f32::EPSILON);
-                                        } else {
-                                            local_delta.x = local_delta.x.max(f32::EPSILON);
-                                        }
-                                        let tangent =
-                                            (local_delta.y / local_delta.x).clamp(-10e6, 10e6);
-
-                                        if *left {
-                                            *left_tangent = tangent;
+                                        if let CurveKeyKind::Cubic {
+                                            left_tangent,
+                                            right_tangent,
+                                        } = &mut key.kind
+                                        {
+                                            let mut local_delta = pos - screen_key_pos;
+                                            if *left {
+                                                local_delta.x = local_delta.x.min(f32::EPSILON);
+                                            } else {
+                                                local_delta.x = local_delta.x.max(f32::EPSILON);
+                                            }
+                                            let tangent =
+                                                (local_delta.y / local_delta.x).clamp(-10e6, 10e6);
+
+                                            if self.tangent_mode != TangentMode::Broken {
+                                                *left_tangent = tangent;
+                                                *right_tangent = tangent;
+                                            } else if *left {
+                                                *left_tangent = tangent;
+                                            } else {
+                                                *right_tangent = tangent;
+                                            }
                                         } else {
-                                            *right_tangent = tangent;
+                                            unreachable!(
+                                                "attempt to edit tangents of non-cubic curve key!"
+                                            )
                                         }
-                                    } else {
-                                        unreachable!(
-                                            "attempt to edit tangents of non-cubic curve key!"
-                                        )
                                     }
                                 }
                                 OperationContext::BoxSelection {
@@ -272,6 +410,16 @@ impl Control for CurveEditor {
                                     max.set(local_mouse_pos.sup(initial_mouse_pos));
                                 }
                             }
+
+                            if self.live_drag_sync
+                                && matches!(
+                                    self.operation_context,
+                                    Some(OperationContext::DragKeys { .. })
+                                        | Some(OperationContext::DragTangent { .. })
+                                )
+                            {
+                                self.send_curve(ui);
+                            }
                         } else if state.left == ButtonState::Pressed {
                             if let Some(selection) = self.selection.as_ref() {
                                 match selection {
@@ -282,7 +430,7 @@ impl Control for CurveEditor {
                                                 .map(|k| DragEntry {
                                                     key: *k,
                                                     initial_position: self
-                                                        .key_container
+                                                        .curves
                                                         .key_ref(*k)
                                                         .unwrap()
                                                         .position,
@@ -309,6 +457,10 @@ impl Control for CurveEditor {
 
                                 if self.operation_context.is_some() {
                                     ui.capture_mouse(self.handle);
+                                    ui.send_message(CurveEditorMessage::drag_started(
+                                        self.handle,
+                                        MessageDirection::FromWidget,
+                                    ));
                                 }
                             } else {
                                 self.operation_context = Some(OperationContext::BoxSelection {
@@ -326,12 +478,33 @@ impl Control for CurveEditor {
 
                             // Send modified curve back to user.
                             match context {
-                                OperationContext::DragKeys { .. }
-                                | OperationContext::DragTangent { .. } => {
+                                OperationContext::DragKeys { .. } => {
                                     // Ensure that the order of keys is correct.
                                     self.sort_keys();
 
+                                    // Automatic tangents depend on key positions, so they must be
+                                    // recalculated for moved keys.
+                                    if matches!(
+                                        self.tangent_mode,
+                                        TangentMode::Auto | TangentMode::Linear
+                                    ) {
+                                        self.apply_tangent_mode_to_selection();
+                                    }
+
+                                    self.send_curve(ui);
+                                    ui.send_message(CurveEditorMessage::drag_ended(
+                                        self.handle,
+                                        MessageDirection::FromWidget,
+                                    ));
+                                }
+                                OperationContext::DragTangent { .. } => {
+                                    self.sort_keys();
+
                                     self.send_curve(ui);
+                                    ui.send_message(CurveEditorMessage::drag_ended(
+                                        self.handle,
+                                        MessageDirection::FromWidget,
+                                    ));
                                 }
                                 OperationContext::BoxSelection { min, max, .. } => {
                                     let min = min.get();
@@ -341,17 +514,37 @@ impl Control for CurveEditor {
                                         Rect::new(min.x, min.y, max.x - min.x, max.y - min.y);
 
                                     let mut selection = FxHashSet::default();
-                                    for key in self.key_container.keys() {
-                                        if rect.contains(key.position) {
+                                    for key in self.visible_curves().flat_map(|c| c.keys()) {
+                                        if rect.contains(key.position) && self.is_key_shown(key) {
                                             selection.insert(key.id);
                                         }
                                     }
 
+                                    // Shift adds the keys to the current selection, Ctrl toggles
+                                    // them.
+                                    let modifiers = ui.keyboard_modifiers();
+                                    if modifiers.shift || modifiers.control {
+                                        let mut keys = match self.selection.as_ref() {
+                                            Some(Selection::Keys { keys }) => keys.clone(),
+                                            _ => Default::default(),
+                                        };
+                                        for key in selection {
+                                            if modifiers.control && keys.contains(&key) {
+                                                keys.remove(&key);
+                                            } else {
+                                                keys.insert(key);
+                                            }
+                                        }
+                                        selection = keys;
+                                    }
+
                                     if !selection.is_empty() {
                                         self.set_selection(
                                             Some(Selection::Keys { keys: selection }),
                                             ui,
                                         );
+                                    } else if self.selection.is_some() {
+                                        self.set_selection(None, ui);
                                     }
                                 }
                                 _ => {}
@@ -364,12 +557,7 @@ impl Control for CurveEditor {
 
                             if let Some(picked) = pick_result {
                                 match picked {
-                                    PickResult::Key(picked_key) => {
-                                        let picked_key_id = self
-                                            .key_container
-                                            .key_index_ref(picked_key)
-                                            .unwrap()
-                                            .id;
+                                    PickResult::Key(picked_key_id) => {
                                         if let Some(selection) = self.selection.as_mut() {
                                             match selection {
                                                 Selection::Keys { keys } => {
@@ -412,6 +600,17 @@ impl Control for CurveEditor {
                                         );
                                     }
                                 }
+                            } else if ui.keyboard_modifiers().shift
+                                || ui.keyboard_modifiers().control
+                            {
+                                // Keep the selection, so the keys of the box could be added to
+                                // it (or toggled) when the box selection ends.
+                                let local_mouse_pos = self.point_to_local_space(*pos);
+                                self.operation_context = Some(OperationContext::BoxSelection {
+                                    initial_mouse_pos: local_mouse_pos,
+                                    min: Cell::new(local_mouse_pos),
+                                    max: Cell::new(local_mouse_pos),
+                                });
                             } else {
                                 self.set_selection(None, ui);
                             }
@@ -430,7 +629,7 @@ impl Control for CurveEditor {
 
                         let new_zoom = if ui.keyboard_modifiers().shift {
                             Vector2::new(self.zoom.x * k, self.zoom.y)
-                        } else if ui.keyboard_modifiers.control {
+                        } else if ui.keyboard_modifiers.control || ui.keyboard_modifiers.alt {
                             Vector2::new(self.zoom.x, self.zoom.y * k)
                         } else {
                             self.zoom * k
@@ -449,8 +648,8 @@ impl Control for CurveEditor {
                     && message.direction() == MessageDirection::ToWidget
                 {
                     match msg {
-                        CurveEditorMessage::Sync(curve) => {
-                            self.key_container = KeyContainer::from(curve);
+                        CurveEditorMessage::Sync(curves) => {
+                            self.set_curves(curves, ui);
                         }
                         CurveEditorMessage::ViewPosition(view_position) => {
                             self.set_view_position(*view_position);
@@ -468,14 +667,56 @@ impl Control for CurveEditor {
                         }
                         CurveEditorMessage::AddKey(screen_pos) => {
                             let local_pos = self.point_to_local_space(*screen_pos);
-                            self.key_container.add(CurveKeyView {
-                                position: local_pos,
-                                kind: CurveKeyKind::Linear,
-                                id: Uuid::new_v4(),
-                            });
-                            self.set_selection(None, ui);
-                            self.sort_keys();
-                            self.send_curve(ui);
+                            // A key is added to a curve that is closest to the given point.
+                            if let Some(curve) = self
+                                .curves
+                                .curves_mut()
+                                .iter_mut()
+                                .map(|curve| {
+                                    let distance =
+                                        (curve.curve().value_at(local_pos.x) - local_pos.y).abs();
+                                    (curve, distance)
+                                })
+                                .min_by(|(_, a), (_, b)| {
+                                    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+                                })
+                                .map(|(curve, _)| curve)
+                            {
+                                let kind = match self.new_key_kind {
+                                    CurveKeyKind::Cubic { .. } => {
+                                        let prev = curve
+                                            .keys()
+                                            .iter()
+                                            .rev()
+                                            .find(|k| k.position.x < local_pos.x);
+                                        let next = curve
+                                            .keys()
+                                            .iter()
+                                            .find(|k| k.position.x > local_pos.x);
+                                        let tangent = match (prev, next) {
+                                            (Some(prev), Some(next)) => {
+                                                (next.position.y - prev.position.y)
+                                                    / (next.position.x - prev.position.x)
+                                            }
+                                            _ => 0.0,
+                                        };
+                                        CurveKeyKind::Cubic {
+                                            left_tangent: tangent,
+                                            right_tangent: tangent,
+                                        }
+                                    }
+                                    ref kind => kind.clone(),
+                                };
+                                curve.add(CurveKeyView {
+                                    position: local_pos,
+                                    kind,
+                                    id: Uuid::new_v4(),
+                                    tag: Default::default(),
+                                });
+                                self.set_selection(None, ui);
+                                self.sort_keys();
+                                self.send_curve(ui);
+                            }
                         }
                         CurveEditorMessage::ZoomToFit => {
                             let mut max_y = -f32::MAX;
@@ -498,85 +739,77 @@ impl Control for CurveEditor {
                                 }
                             };
 
-                            for keys in self.key_container.keys().windows(2) {
-                                let left = &keys[0];
-                                let right = &keys[1];
-                                match (&left.kind, &right.kind) {
-                                    // Cubic-to-constant and cubic-to-linear is depicted as Hermite spline with right tangent == 0.0.
-                                    (
-                                        CurveKeyKind::Cubic {
-                                            right_tangent: left_tangent,
-                                            ..
-                                        },
-                                        CurveKeyKind::Constant,
-                                    )
-                                    | (
-                                        CurveKeyKind::Cubic {
-                                            right_tangent: left_tangent,
-                                            ..
-                                        },
-                                        CurveKeyKind::Linear,
-                                    ) => {
-                                        let (y0, y1) = inf_sup_cubicf(
-                                            left.position.y,
-                                            right.position.y,
-                                            *left_tangent,
-                                            0.0,
-                                        );
-                                        push(left.position.x, y0);
-                                        push(right.position.x, y1);
-                                    }
+                            for curve in self.visible_curves() {
+                                if let [key] = curve.keys() {
+                                    push(key.position.x, key.position.y);
+                                }
 
-                                    // Cubic-to-cubic is depicted as Hermite spline.
-                                    (
-                                        CurveKeyKind::Cubic {
-                                            right_tangent: left_tangent,
-                                            ..
-                                        },
-                                        CurveKeyKind::Cubic {
-                                            left_tangent: right_tangent,
-                                            ..
-                                        },
-                                    ) => {
-                                        let (y0, y1) = inf_sup_cubicf(
-                                            left.position.y,
-                                            right.position.y,
-                                            *left_tangent,
-                                            *right_tangent,
-                                        );
-                                        push(left.position.x, y0);
-                                        push(right.position.x, y1);
-                                    }
-                                    _ => {
-                                        push(left.position.x, left.position.y);
-                                        push(right.position.x, right.position.y);
+                                for keys in curve.keys().windows(2) {
+                                    let left = &keys[0];
+                                    let right = &keys[1];
+                                    match (&left.kind, &right.kind) {
+                                        // Cubic-to-constant and cubic-to-linear is depicted as Hermite spline with right tangent == 0.0.
+                                        (
+                                            CurveKeyKind::Cubic {
+                                                right_tangent: left_tangent,
+                                                ..
+                                            },
+                                            CurveKeyKind::Constant,
+                                        )
+                                        | (
+                                            CurveKeyKind::Cubic {
+                                                right_tangent: left_tangent,
+                                                ..
+                                            },
+                                            CurveKeyKind::Linear,
+                                        ) => {
+                                            let (y0, y1) = inf_sup_cubicf(
+                                                left.position.y,
+                                                right.position.y,
+                                                *left_tangent,
+                                                0.0,
+                                            );
+                                            push(left.position.x, y0);
+                                            push(right.position.x, y1);
+                                        }
+
+                                        // Cubic-to-cubic is depicted as Hermite spline.
+                                        (
+                                            CurveKeyKind::Cubic {
+                                                right_tangent: left_tangent,
+                                                ..
+                                            },
+                                            CurveKeyKind::Cubic {
+                                                left_tangent: right_tangent,
+                                                ..
+                                            },
+                                        ) => {
+                                            let (y0, y1) = inf_sup_cubicf(
+                                                left.position.y,
+                                                right.position.y,
+                                                *left_tangent,
+                                                *right_tangent,
+                                            );
+                                            push(left.position.x, y0);
+                                            push(right.position.x, y1);
+                                        }
+                                        _ => {
+                                            push(left.position.x, left.position.y);
+                                            push(right.position.x, right.position.y);
+                                        }
                                     }
                                 }
                             }
-
-                            let min = Vector2::new(min_x, min_y);
-                            let max = Vector2::new(max_x, max_y);
-                            let center = (min + max).scale(0.5);
-
-                            ui.send_message(CurveEditorMessage::zoom(
-                                self.handle,
-                                MessageDirection::ToWidget,
-                                Vector2::new(
-                                    self.actual_local_size().x
-                                        / (max.x - min.x).max(5.0 * f32::EPSILON),
-                                    self.actual_local_size().y
-                                        / (max.y - min.y).max(5.0 * f32::EPSILON),
-                                ),
-                            ));
-
-                            ui.send_message(CurveEditorMessage::view_position(
-                                self.handle,
-                                MessageDirection::ToWidget,
-                                Vector2::new(
-                                    self.actual_local_size().x * 0.5 - center.x,
-                                    -self.actual_local_size().y * 0.5 + center.y,
-                                ),
-                            ));
+                            // There's nothing to fit if there's no keys.
+                            if min_x <= max_x {
+                                self.zoom_to_rect(
+                                    Rect::new(min_x, min_y, max_x - min_x, max_y - min_y),
+                                    ui,
+                                );
+                            }
+                        }
+                        CurveEditorMessage::ZoomToRect(rect) => {
+                            self.zoom_to_rect(*rect, ui);
                         }
                         CurveEditorMessage::ChangeSelectedKeysValue(value) => {
                             self.change_selected_keys_value(*value, ui);
@@ -587,6 +820,63 @@ impl Control for CurveEditor {
                         CurveEditorMessage::HighlightZones(zones) => {
                             self.highlight_zones = zones.clone();
                         }
+                        CurveEditorMessage::KeyLocationLimits(limits) => {
+                            self.key_location_limits = limits.clone();
+                        }
+                        CurveEditorMessage::TangentMode(mode) => {
+                            self.tangent_mode = *mode;
+                            if self.apply_tangent_mode_to_selection() {
+                                self.send_curve(ui);
+                            }
+                        }
+                        CurveEditorMessage::HiddenCurves(curves) => {
+                            self.hidden_curves = curves.iter().cloned().collect();
+                        }
+                        CurveEditorMessage::NewKeyKind(kind) => {
+                            self.new_key_kind = kind.clone();
+                        }
+                        CurveEditorMessage::ShowTangents(show) => {
+                            self.show_tangents = *show;
+                        }
+                        CurveEditorMessage::KeyTagFilter(filter) => {
+                            self.key_tag_filter = *filter;
+                        }
+                        CurveEditorMessage::ShowValueGrid(show) => {
+                            self.show_value_grid = *show;
+                        }
+                        CurveEditorMessage::TimeGridStep(step) => {
+                            self.time_grid_step = step.filter(|step| *step > 0.0);
+                        }
+                        CurveEditorMessage::ReferenceCurves(curves) => {
+                            self.reference_curves = curves
+                                .iter()
+                                .map(|c| KeyContainer::new(c, self.reference_curve_brush.clone()))
+                                .collect();
+                        }
+                        CurveEditorMessage::CurveBrushes(brushes) => {
+                            self.curve_brush_overrides = brushes.iter().cloned().collect();
+                            let curves = self.curves.to_native();
+                            self.set_curves(&curves, ui);
+                        }
+                        CurveEditorMessage::SelectionChanged(keys) => {
+                            let keys = keys
+                                .iter()
+                                .filter(|k| self.curves.key_ref(**k).is_some())
+                                .cloned()
+                                .collect::<FxHashSet<_>>();
+                            if keys != self.selected_keys().into_iter().collect() {
+                                self.apply_selection(
+                                    if keys.is_empty() {
+                                        None
+                                    } else {
+                                        Some(Selection::Keys { keys })
+                                    },
+                                    ui,
+                                );
+                            }
+                        }
+                        // These are only sent from the widget.
+                        CurveEditorMessage::DragStarted | CurveEditorMessage::DragEnded => {}
                     }
                 }
             }
@@ -677,7 +967,57 @@ fn round_to_step(x: f32, step: f32) -> f32 {
     x - x % step
 }
 
+/// Returns the smallest multiple of the step that is not less than the min step. The multiplier
+/// is 1 to 5 or a multiple of 5, the same way as major ticks of a ruler in frames are placed, so
+/// the lines of the grid land on the ticks.
+fn multiple_grid_step(step: f32, min_step: f32) -> f32 {
+    let count = (min_step / step).ceil().max(1.0);
+    let count = if count <= 5.0 {
+        count
+    } else {
+        (count / 5.0).ceil() * 5.0
+    };
+    step * count
+}
+
+/// Min distance between lines of the value grid, in pixels. It is a bit larger than the height
+/// of a label, so labels never overlap.
+const VALUE_GRID_MIN_SPACING: f32 = 24.0;
+
+/// Returns the smallest "round" step (1, 2 or 5 multiplied by a power of ten) that is not less
+/// than the given one.
+fn round_grid_step(min_step: f32) -> f32 {
+    let magnitude = 10.0f32.powf(min_step.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|step| *step >= min_step)
+        .unwrap_or(10.0 * magnitude)
+}
+
 impl CurveEditor {
+    fn zoom_to_rect(&self, rect: Rect<f32>, ui: &UserInterface) {
+        let center = rect.center();
+
+        ui.send_message(CurveEditorMessage::zoom(
+            self.handle,
+            MessageDirection::ToWidget,
+            Vector2::new(
+                self.actual_local_size().x / rect.w().max(5.0 * f32::EPSILON),
+                self.actual_local_size().y / rect.h().max(5.0 * f32::EPSILON),
+            ),
+        ));
+
+        ui.send_message(CurveEditorMessage::view_position(
+            self.handle,
+            MessageDirection::ToWidget,
+            Vector2::new(
+                self.actual_local_size().x * 0.5 - center.x,
+                -self.actual_local_size().y * 0.5 + center.y,
+            ),
+        ));
+    }
+
     #[allow(clippy::let_and_return)] // Improves readability
     fn set_view_position(&mut self, position: Vector2<f32>) {
         self.view_position = self.view_bounds.map_or(position, |bounds| {
@@ -751,10 +1091,71 @@ impl CurveEditor {
     }
 
     fn sort_keys(&mut self) {
-        self.key_container.sort_keys();
+        self.curves.sort_keys();
+    }
+
+    fn apply_curve_brush_overrides(&mut self) {
+        for curve in self.curves.curves_mut() {
+            if let Some(brush) = self.curve_brush_overrides.get(&curve.id()) {
+                curve.brush = brush.clone();
+            }
+        }
+    }
+
+    fn set_curves(&mut self, curves: &[Curve], ui: &UserInterface) {
+        // Single curve is drawn using foreground brush, multiple curves use their own brushes
+        // to be distinguishable from each other.
+        self.curves = if curves.len() > 1 {
+            CurvesContainer::from_native(&self.curve_brushes, curves)
+        } else {
+            CurvesContainer::from_native(&[self.foreground()], curves)
+        };
+        self.apply_curve_brush_overrides();
+
+        // Curves could be synced in the middle of a drag, keys that do not exist anymore are not
+        // dragged further. The drag itself goes on, so it is still ended by the mouse up.
+        if let Some(OperationContext::DragKeys { entries, .. }) = self.operation_context.as_mut() {
+            entries.retain(|e| self.curves.key_ref(e.key).is_some());
+        }
+
+        // Remove keys that do not exist anymore from the selection.
+        let selection_valid = match self.selection.as_mut() {
+            Some(Selection::Keys { keys }) => {
+                keys.retain(|k| self.curves.key_ref(*k).is_some());
+                !keys.is_empty()
+            }
+            Some(Selection::LeftTangent { key }) | Some(Selection::RightTangent { key }) => {
+                self.curves.key_ref(*key).is_some()
+            }
+            None => true,
+        };
+        if !selection_valid {
+            self.apply_selection(None, ui);
+        }
+    }
+
+    fn selected_keys(&self) -> Vec<Uuid> {
+        match self.selection.as_ref() {
+            Some(Selection::Keys { keys }) => keys.iter().cloned().collect(),
+            Some(Selection::LeftTangent { key }) | Some(Selection::RightTangent { key }) => {
+                vec![*key]
+            }
+            None => vec![],
+        }
     }
 
     fn set_selection(&mut self, selection: Option<Selection>, ui: &UserInterface) {
+        self.apply_selection(selection, ui);
+
+        ui.send_message(CurveEditorMessage::selection_changed(
+            self.handle,
+            MessageDirection::FromWidget,
+            self.selected_keys(),
+        ));
+    }
+
+    // Changes the selection without notifying the user about changes.
+    fn apply_selection(&mut self, selection: Option<Selection>, ui: &UserInterface) {
         self.selection = selection;
 
         ui.send_message(WidgetMessage::enabled(
@@ -777,7 +1178,7 @@ impl CurveEditor {
 
         if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
             if let Some(first) = keys.iter().next() {
-                if let Some(key) = self.key_container.key_ref(*first) {
+                if let Some(key) = self.curves.key_ref(*first) {
                     ui.send_message(NumericUpDownMessage::value(
                         self.context_menu.key_location,
                         MessageDirection::ToWidget,
@@ -797,7 +1198,7 @@ impl CurveEditor {
     fn remove_selection(&mut self, ui: &mut UserInterface) {
         if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
             for &id in keys {
-                self.key_container.remove(id);
+                self.curves.remove(id);
             }
 
             self.set_selection(None, ui);
@@ -810,18 +1211,89 @@ impl CurveEditor {
     fn change_selected_keys_kind(&mut self, kind: CurveKeyKind, ui: &mut UserInterface) {
         if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
             for key in keys {
-                self.key_container.key_mut(*key).unwrap().kind = kind.clone();
+                self.curves.key_mut(*key).unwrap().kind = kind.clone();
             }
 
             self.send_curve(ui);
         }
     }
 
+    /// Applies current tangent mode to selected keys, returns `true` if any key was changed.
+    fn apply_tangent_mode_to_selection(&mut self) -> bool {
+        let selected = match self.selection.as_ref() {
+            Some(Selection::Keys { keys }) => keys,
+            _ => return false,
+        };
+
+        fn slope(a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+            let dx = b.x - a.x;
+            if dx.abs() > f32::EPSILON {
+                ((b.y - a.y) / dx).clamp(-10e6, 10e6)
+            } else {
+                0.0
+            }
+        }
+
+        let mut modified = false;
+        for curve in self.curves.curves_mut() {
+            let positions = curve.keys().iter().map(|k| k.position).collect::<Vec<_>>();
+            for (i, key) in curve.keys_mut().iter_mut().enumerate() {
+                if !selected.contains(&key.id) {
+                    continue;
+                }
+
+                let prev = i.checked_sub(1).map(|i| positions[i]);
+                let next = positions.get(i + 1).cloned();
+                let (old_left, old_right) = match key.kind {
+                    CurveKeyKind::Cubic {
+                        left_tangent,
+                        right_tangent,
+                    } => (left_tangent, right_tangent),
+                    _ => (0.0, 0.0),
+                };
+                let (left_tangent, right_tangent) = match self.tangent_mode {
+                    TangentMode::Stepped => {
+                        if key.kind != CurveKeyKind::Constant {
+                            key.kind = CurveKeyKind::Constant;
+                            modified = true;
+                        }
+                        continue;
+                    }
+                    TangentMode::Auto => {
+                        let tangent = match (prev, next) {
+                            (Some(prev), Some(next)) => slope(prev, next),
+                            _ => 0.0,
+                        };
+                        (tangent, tangent)
+                    }
+                    TangentMode::Free => {
+                        let tangent = (old_left + old_right) * 0.5;
+                        (tangent, tangent)
+                    }
+                    TangentMode::Broken => (old_left, old_right),
+                    TangentMode::Linear => (
+                        prev.map_or(0.0, |prev| slope(prev, key.position)),
+                        next.map_or(0.0, |next| slope(key.position, next)),
+                    ),
+                };
+                let kind = CurveKeyKind::Cubic {
+                    left_tangent,
+                    right_tangent,
+                };
+                if key.kind != kind {
+                    key.kind = kind;
+                    modified = true;
+                }
+            }
+        }
+        modified
+    }
+
     fn change_selected_keys_value(&mut self, value: f32, ui: &mut UserInterface) {
         if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
             let mut modified = false;
             for key in keys {
-                let key_value = &mut self.key_container.key_mut(*key).unwrap().position.y;
+                let key_value = &mut self.curves.key_mut(*key).unwrap().position.y;
                 if (*key_value).ne(&value) {
                     *key_value = value;
                     modified = true;
@@ -838,7 +1310,7 @@ impl CurveEditor {
         if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
             let mut modified = false;
             for key in keys {
-                let key_location = &mut self.key_container.key_mut(*key).unwrap().position.x;
+                let key_location = &mut self.curves.key_mut(*key).unwrap().position.x;
                 if (*key_location).ne(&location) {
                     *key_location = location;
                     modified = true;
@@ -851,11 +1323,27 @@ impl CurveEditor {
         }
     }
 
+    fn visible_curves(&self) -> impl Iterator<Item = &KeyContainer> {
+        self.curves
+            .curves()
+            .iter()
+            .filter(|c| !self.hidden_curves.contains(&c.id()))
+    }
+
+    /// Returns `true` if the key passes the tag filter.
+    fn is_key_shown(&self, key: &CurveKeyView) -> bool {
+        self.key_tag_filter.map_or(true, |tag| key.tag == tag)
+    }
+
     /// `pos` must be in screen space.
     fn pick(&self, pos: Vector2<f32>) -> Option<PickResult> {
         // Linear search is fine here, having a curve with thousands of
         // points is insane anyway.
-        for (i, key) in self.key_container.keys().iter().enumerate() {
+        for key in self
+            .visible_curves()
+            .flat_map(|c| c.keys())
+            .filter(|k| self.is_key_shown(k))
+        {
             let screen_pos = self.point_to_screen_space(key.position);
             let bounds = Rect::new(
                 screen_pos.x - self.key_size * 0.5,
@@ -864,7 +1352,12 @@ impl CurveEditor {
                 self.key_size,
             );
             if bounds.contains(pos) {
-                return Some(PickResult::Key(i));
+                return Some(PickResult::Key(key.id));
+            }
+
+            // Hidden tangents cannot be picked.
+            if !self.show_tangents {
+                continue;
             }
 
             // Check tangents.
@@ -879,14 +1372,14 @@ impl CurveEditor {
                 );
 
                 if (left_handle_pos - pos).norm() <= self.key_size * 0.5 {
-                    return Some(PickResult::LeftTangent(i));
+                    return Some(PickResult::LeftTangent(key.id));
                 }
 
                 let right_handle_pos =
                     self.tangent_screen_position(wrap_angle(right_tangent.atan()), key.position);
 
                 if (right_handle_pos - pos).norm() <= self.key_size * 0.5 {
-                    return Some(PickResult::RightTangent(i));
+                    return Some(PickResult::RightTangent(key.id));
                 }
             }
         }
@@ -902,7 +1395,7 @@ impl CurveEditor {
         ui.send_message(CurveEditorMessage::sync(
             self.handle,
             MessageDirection::FromWidget,
-            self.key_container.curve(),
+            self.curves.to_native(),
         ));
     }
 
@@ -935,10 +1428,63 @@ impl CurveEditor {
         }
     }
 
+    fn is_out_of_range(&self, location: f32) -> bool {
+        self.key_location_limits.as_ref().map_or(false, |limits| {
+            location < limits.start || location > limits.end
+        })
+    }
+
+    fn draw_out_of_range_zones(&self, ctx: &mut DrawingContext) {
+        if let Some(limits) = self.key_location_limits.as_ref() {
+            let screen_bounds = self.screen_bounds();
+            let start = self
+                .point_to_screen_space(Vector2::new(limits.start, 0.0))
+                .x;
+            let end = self.point_to_screen_space(Vector2::new(limits.end, 0.0)).x;
+
+            // Before the start of the range.
+            if start > screen_bounds.x() {
+                ctx.push_rect_filled(
+                    &Rect::new(
+                        screen_bounds.x(),
+                        screen_bounds.y(),
+                        (start - screen_bounds.x()).min(screen_bounds.w()),
+                        screen_bounds.h(),
+                    ),
+                    None,
+                );
+            }
+
+            // After the end of the range.
+            if end < screen_bounds.x() + screen_bounds.w() {
+                let left = end.max(screen_bounds.x());
+                ctx.push_rect_filled(
+                    &Rect::new(
+                        left,
+                        screen_bounds.y(),
+                        screen_bounds.x() + screen_bounds.w() - left,
+                        screen_bounds.h(),
+                    ),
+                    None,
+                );
+            }
+
+            ctx.commit(
+                self.clip_bounds(),
+                self.out_of_range_brush.clone(),
+                CommandTexture::None,
+                None,
+            );
+        }
+    }
+
     fn draw_grid(&self, ctx: &mut DrawingContext) {
         let screen_bounds = self.screen_bounds();
 
-        let step_size_x = self.grid_size.x / self.zoom.x;
+        let step_size_x = match self.time_grid_step {
+            Some(step) => multiple_grid_step(step, self.grid_size.x / self.zoom.x),
+            None => self.grid_size.x / self.zoom.x,
+        };
         let step_size_y = self.grid_size.y / self.zoom.y;
 
         let mut local_left_bottom = self.point_to_local_space(screen_bounds.left_top_corner());
@@ -956,14 +1502,16 @@ impl CurveEditor {
         let nw = ((w / step_size_x).ceil()) as usize;
         let nh = ((h / step_size_y).ceil()) as usize;
 
-        for ny in 0..=nh {
-            let k = ny as f32 / (nh) as f32;
-            let y = local_left_bottom.y - k * h;
-            ctx.push_line(
-                self.point_to_screen_space(Vector2::new(local_left_bottom.x - step_size_x, y)),
-                self.point_to_screen_space(Vector2::new(local_right_top.x + step_size_x, y)),
-                1.0,
-            );
+        if !self.show_value_grid {
+            for ny in 0..=nh {
+                let k = ny as f32 / (nh) as f32;
+                let y = local_left_bottom.y - k * h;
+                ctx.push_line(
+                    self.point_to_screen_space(Vector2::new(local_left_bottom.x - step_size_x, y)),
+                    self.point_to_screen_space(Vector2::new(local_right_top.x + step_size_x, y)),
+                    1.0,
+                );
+            }
         }
 
         for nx in 0..=nw {
@@ -995,7 +1543,9 @@ impl CurveEditor {
         // Draw values.
         let mut text = self.text.borrow_mut();
 
-        if self.show_y_values {
+        if self.show_value_grid {
+            self.draw_value_grid(&mut text, ctx);
+        } else if self.show_y_values {
             for ny in 0..=nh {
                 let k = ny as f32 / (nh) as f32;
                 let y = local_left_bottom.y - k * h;
@@ -1022,9 +1572,68 @@ impl CurveEditor {
         }
     }
 
-    fn draw_curve(&self, ctx: &mut DrawingContext) {
+    /// Draws horizontal lines at "round" values with labels at the left side of the editor.
+    fn draw_value_grid(&self, text: &mut FormattedText, ctx: &mut DrawingContext) {
         let screen_bounds = self.screen_bounds();
-        let draw_keys = self.key_container.keys();
+
+        let top = self.point_to_local_space(screen_bounds.left_top_corner()).y;
+        let bottom = self
+            .point_to_local_space(screen_bounds.right_bottom_corner())
+            .y;
+        let (min, max) = (top.min(bottom), top.max(bottom));
+
+        let step = round_grid_step(VALUE_GRID_MIN_SPACING / self.zoom.y.max(f32::EPSILON));
+        if !step.is_finite() || step <= 0.0 {
+            return;
+        }
+        // Amount of fractional digits that is enough to tell apart adjacent labels.
+        let precision = (-step.log10().floor()).max(0.0) as usize;
+
+        let first = (min / step).floor() as i64;
+        let last = (max / step).ceil() as i64;
+        let values = (first..=last).map(|i| i as f32 * step).collect::<Vec<_>>();
+
+        for value in values.iter() {
+            let y = self.point_to_screen_space(Vector2::new(0.0, *value)).y;
+            ctx.push_line(
+                Vector2::new(screen_bounds.x(), y),
+                Vector2::new(screen_bounds.x() + screen_bounds.w(), y),
+                1.0,
+            );
+        }
+        ctx.commit(
+            screen_bounds,
+            self.grid_brush.clone(),
+            CommandTexture::None,
+            None,
+        );
+
+        for value in values {
+            let y = self.point_to_screen_space(Vector2::new(0.0, value)).y;
+            text.set_text(format!("{:.*}", precision, value)).build();
+            ctx.draw_text(
+                screen_bounds,
+                Vector2::new(screen_bounds.x() + 2.0, y),
+                text,
+            );
+        }
+    }
+
+    fn draw_reference_curves(&self, ctx: &mut DrawingContext) {
+        for curve in self.reference_curves.iter() {
+            self.draw_curve(curve, ctx);
+        }
+    }
+
+    fn draw_curves(&self, ctx: &mut DrawingContext) {
+        for curve in self.visible_curves() {
+            self.draw_curve(curve, ctx);
+        }
+    }
+
+    fn draw_curve(&self, curve: &KeyContainer, ctx: &mut DrawingContext) {
+        let screen_bounds = self.screen_bounds();
+        let draw_keys = curve.keys();
 
         if let Some(first) = draw_keys.first() {
             let screen_pos = self.point_to_screen_space(first.position);
@@ -1100,14 +1709,29 @@ impl CurveEditor {
                 ),
             }
         }
-        ctx.commit(screen_bounds, self.foreground(), CommandTexture::None, None);
+        ctx.commit(
+            screen_bounds,
+            curve.brush.clone(),
+            CommandTexture::None,
+            None,
+        );
     }
 
     fn draw_keys(&self, ctx: &mut DrawingContext) {
+        for curve in self.visible_curves() {
+            self.draw_curve_keys(curve, ctx);
+        }
+    }
+
+    fn draw_curve_keys(&self, curve: &KeyContainer, ctx: &mut DrawingContext) {
         let screen_bounds = self.screen_bounds();
-        let keys_to_draw = self.key_container.keys();
+        let keys_to_draw = curve.keys();
 
         for (i, key) in keys_to_draw.iter().enumerate() {
+            if !self.is_key_shown(key) {
+                continue;
+            }
+
             let origin = self.point_to_screen_space(key.position);
             let size = Vector2::new(self.key_size, self.key_size);
             let half_size = size.scale(0.5);
@@ -1128,14 +1752,15 @@ impl CurveEditor {
                     Selection::Keys { keys } => {
                         selected = keys.contains(&key.id);
                     }
-                    Selection::LeftTangent { key } | Selection::RightTangent { key } => {
-                        selected = i == *key;
+                    Selection::LeftTangent { key: selected_key }
+                    | Selection::RightTangent { key: selected_key } => {
+                        selected = key.id == *selected_key;
                     }
                 }
             }
 
             // Show tangents for Cubic keys.
-            if selected {
+            if selected && self.show_tangents {
                 let (show_left, show_right) = match keys_to_draw.get(i.wrapping_sub(1)) {
                     Some(left) => match (&left.kind, &key.kind) {
                         (CurveKeyKind::Cubic { .. }, CurveKeyKind::Cubic { .. }) => (true, true),
@@ -1188,8 +1813,14 @@ impl CurveEditor {
                 screen_bounds,
                 if selected {
                     self.selected_key_brush.clone()
+                } else if self.is_out_of_range(key.position.x) {
+                    self.out_of_range_key_brush.clone()
                 } else {
-                    self.key_brush.clone()
+                    match key.tag {
+                        CurveKeyTag::Key => self.key_brush.clone(),
+                        CurveKeyTag::Breakdown => self.breakdown_key_brush.clone(),
+                        CurveKeyTag::InBetween => self.in_between_key_brush.clone(),
+                    }
                 },
                 CommandTexture::None,
                 None,
@@ -1218,7 +1849,8 @@ impl CurveEditor {
 
 pub struct CurveEditorBuilder {
     widget_builder: WidgetBuilder,
-    curve: Curve,
+    curves: Vec<Curve>,
+    curve_brushes: Vec<Brush>,
     view_position: Vector2<f32>,
     zoom: f32,
     view_bounds: Option<Rect<f32>>,
@@ -1228,13 +1860,23 @@ pub struct CurveEditorBuilder {
     min_zoom: Vector2<f32>,
     max_zoom: Vector2<f32>,
     highlight_zones: Vec<HighlightZone>,
+    handle_delete_key: bool,
+    live_drag_sync: bool,
 }
 
 impl CurveEditorBuilder {
     pub fn new(widget_builder: WidgetBuilder) -> Self {
         Self {
             widget_builder,
-            curve: Default::default(),
+            curves: Default::default(),
+            curve_brushes: vec![
+                Brush::Solid(Color::opaque(220, 70, 70)),
+                Brush::Solid(Color::opaque(70, 200, 70)),
+                Brush::Solid(Color::opaque(80, 120, 230)),
+                Brush::Solid(Color::opaque(220, 200, 70)),
+                Brush::Solid(Color::opaque(200, 80, 200)),
+                Brush::Solid(Color::opaque(70, 200, 200)),
+            ],
             view_position: Default::default(),
             zoom: 1.0,
             view_bounds: None,
@@ -1244,11 +1886,23 @@ impl CurveEditorBuilder {
             min_zoom: Vector2::new(0.001, 0.001),
             max_zoom: Vector2::new(1000.0, 1000.0),
             highlight_zones: Default::default(),
+            handle_delete_key: true,
+            live_drag_sync: false,
         }
     }
 
-    pub fn with_curve(mut self, curve: Curve) -> Self {
-        self.curve = curve;
+    pub fn with_curves(mut self, curves: Vec<Curve>) -> Self {
+        self.curves = curves;
+        self
+    }
+
+    /// Sets a set of brushes that will be used to draw curves, when there is more than one curve
+    /// in the editor. Brushes will be cycled if there are more curves than brushes. Empty set
+    /// will be ignored.
+    pub fn with_curve_brushes(mut self, brushes: Vec<Brush>) -> Self {
+        if !brushes.is_empty() {
+            self.curve_brushes = brushes;
+        }
         self
     }
 
@@ -1298,9 +1952,21 @@ impl CurveEditorBuilder {
         self
     }
 
-    pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
-        let keys = KeyContainer::from(&self.curve);
+    /// Defines whether the editor should remove selected keys on Delete key or not. Could be
+    /// useful to disable it, if removal is handled on the user side.
+    pub fn with_handle_delete_key(mut self, handle: bool) -> Self {
+        self.handle_delete_key = handle;
+        self
+    }
+
+    /// Defines whether the editor sends modified curves on every mouse move while dragging keys
+    /// or tangents, or just once at the end of the drag.
+    pub fn with_live_drag_sync(mut self, live: bool) -> Self {
+        self.live_drag_sync = live;
+        self
+    }
 
+    pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let add_key;
         let remove;
         let make_constant;
@@ -1423,13 +2089,24 @@ impl CurveEditorBuilder {
             self.widget_builder.foreground = Some(Brush::Solid(Color::opaque(130, 130, 130)))
         }
 
+        let curves = if self.curves.len() > 1 {
+            CurvesContainer::from_native(&self.curve_brushes, &self.curves)
+        } else {
+            CurvesContainer::from_native(
+                &[self.widget_builder.foreground.clone().unwrap()],
+                &self.curves,
+            )
+        };
+
         let editor = CurveEditor {
             widget: self
                 .widget_builder
                 .with_context_menu(context_menu.clone())
                 .with_preview_messages(true)
                 .build(),
-            key_container: keys,
+            curves,
+            curve_brushes: self.curve_brushes,
+            curve_brush_overrides: Default::default(),
             zoom: Vector2::new(1.0, 1.0),
             view_position: Default::default(),
             view_matrix: Default::default(),
@@ -1437,6 +2114,10 @@ impl CurveEditorBuilder {
             inv_screen_matrix: Default::default(),
             key_brush: Brush::Solid(Color::opaque(140, 140, 140)),
             selected_key_brush: Brush::Solid(Color::opaque(220, 220, 220)),
+            out_of_range_key_brush: Brush::Solid(Color::opaque(220, 70, 70)),
+            breakdown_key_brush: Brush::Solid(Color::opaque(90, 160, 230)),
+            in_between_key_brush: Brush::Solid(Color::opaque(110, 190, 110)),
+            out_of_range_brush: Brush::Solid(Color::from_rgba(0, 0, 0, 80)),
             key_size: 8.0,
             handle_radius: 36.0,
             operation_context: None,
@@ -1467,6 +2148,18 @@ impl CurveEditorBuilder {
             min_zoom: self.min_zoom,
             max_zoom: self.max_zoom,
             highlight_zones: self.highlight_zones,
+            handle_delete_key: self.handle_delete_key,
+            tangent_mode: Default::default(),
+            key_location_limits: None,
+            live_drag_sync: self.live_drag_sync,
+            hidden_curves: Default::default(),
+            new_key_kind: CurveKeyKind::Linear,
+            reference_curves: Default::default(),
+            reference_curve_brush: Brush::Solid(Color::from_rgba(200, 200, 200, 70)),
+            show_tangents: true,
+            key_tag_filter: None,
+            show_value_grid: false,
+            time_grid_step: None,
         };
 
         ctx.add_node(UiNode::new(editor))
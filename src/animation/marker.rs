@@ -0,0 +1,49 @@
+//! Marker is a named annotation on specific time position on the animation timeline. See
+//! [`AnimationMarker`] docs for more info.
+
+use crate::{
+    core::{reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
+    utils::NameProvider,
+};
+
+/// Marker is a named annotation on specific time position on the animation timeline, for example
+/// "foot plant" or "impact". Unlike [`super::AnimationSignal`], markers do not produce any events,
+/// they exist only to help animators to find important moments of an animation.
+#[derive(Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct AnimationMarker {
+    /// An id of the marker.
+    pub id: Uuid,
+
+    /// Name of the marker, it is shown on the timeline.
+    pub name: String,
+
+    /// A position (in seconds) on an animation time line.
+    pub time: f32,
+}
+
+impl NameProvider for AnimationMarker {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl AnimationMarker {
+    /// Creates a new animation marker with a given id, name and time position.
+    pub fn new(id: Uuid, name: &str, time: f32) -> Self {
+        Self {
+            id,
+            name: name.to_owned(),
+            time,
+        }
+    }
+}
+
+impl Default for AnimationMarker {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: Default::default(),
+            time: 0.0,
+        }
+    }
+}
@@ -26,11 +26,13 @@ use std::{
 };
 
 use crate::animation::value::{TrackValue, ValueBinding};
+pub use marker::AnimationMarker;
 pub use pose::{AnimationPose, NodePose};
 pub use signal::{AnimationEvent, AnimationSignal};
 
 pub mod container;
 pub mod machine;
+pub mod marker;
 pub mod pose;
 pub mod signal;
 pub mod spritesheet;
@@ -188,6 +190,9 @@ pub struct Animation {
     enabled: bool,
     signals: Vec<AnimationSignal>,
 
+    #[visit(optional)]
+    markers: Vec<AnimationMarker>,
+
     #[visit(optional)]
     root_motion_settings: Option<RootMotionSettings>,
 
@@ -262,6 +267,7 @@ impl Clone for Animation {
             enabled: self.enabled,
             pose: Default::default(),
             signals: self.signals.clone(),
+            markers: self.markers.clone(),
             root_motion_settings: self.root_motion_settings.clone(),
             events: Default::default(),
             time_slice: self.time_slice.clone(),
@@ -671,6 +677,37 @@ impl Animation {
         &mut self.signals
     }
 
+    /// Adds a new marker to the animation. See [`AnimationMarker`] docs for more info.
+    pub fn add_marker(&mut self, marker: AnimationMarker) -> &mut Self {
+        self.markers.push(marker);
+        self
+    }
+
+    /// Removes last marker from the container of the animation.
+    pub fn pop_marker(&mut self) -> Option<AnimationMarker> {
+        self.markers.pop()
+    }
+
+    /// Inserts a new marker at given position.
+    pub fn insert_marker(&mut self, index: usize, marker: AnimationMarker) {
+        self.markers.insert(index, marker)
+    }
+
+    /// Removes a marker at given index.
+    pub fn remove_marker(&mut self, index: usize) -> AnimationMarker {
+        self.markers.remove(index)
+    }
+
+    /// Returns a reference to the markers container.
+    pub fn markers(&self) -> &[AnimationMarker] {
+        &self.markers
+    }
+
+    /// Returns a mutable reference to the inner markers container, allowing you to modify the markers.
+    pub fn markers_mut(&mut self) -> &mut [AnimationMarker] {
+        &mut self.markers
+    }
+
     /// Removes all tracks from the animation for which the given `filter` closure returns `false`. Could be useful
     /// to remove undesired animation tracks.
     pub fn retain_tracks<F>(&mut self, filter: F)
@@ -792,6 +829,7 @@ impl Default for Animation {
             looped: true,
             pose: Default::default(),
             signals: Default::default(),
+            markers: Default::default(),
             root_motion_settings: None,
             events: Default::default(),
             time_slice: Default::default(),